@@ -1,11 +1,13 @@
 use std::{fs, fs::File, io::Read};
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
 use std::process::exit;
-use clap::{Parser as CliParser};
+use clap::{Parser as CliParser, Subcommand, ValueEnum};
 use inkwell::context::Context;
 use hermesc_parser::Parser;
 use hermesc_parser::ast::Module;
+use hermesc_parser::diagnostic::{explain, Diagnostic};
+use hermesc_parser::source_map::SourceMap;
 use hermesc_llvm::LLVM;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -14,20 +16,55 @@ const FILE_EXTENSION: &str = ".hs";
 #[derive(CliParser, Debug)]
 #[command(name = "hermes")]
 #[command(about = "Hermes Compiler", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    file: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required = true)]
+    file: Option<String>,
     #[arg(short, long, required = false, default_value_t = String::from("./out/"))]
     output: String,
     #[arg(short, long)]
     emit_ast: bool,
+    /// Parse the file as a script rather than a module
+    #[arg(long)]
+    script: bool,
+    /// How to print errors
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum ErrorFormat {
+    /// Annotated source snippets
+    Human,
+    /// One JSON object per line for each diagnostic
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Describe an error code, such as E0100
+    Explain { code: String },
 }
 
 fn main() {
     let args = Cli::parse();
 
+    if let Some(Command::Explain { code }) = &args.command {
+        match explain(code) {
+            Some(explanation) => println!("{}", explanation),
+            None => {
+                eprintln!("{} is not a valid error code", code);
+                exit(1);
+            }
+        }
+        return;
+    }
+
     println!("Hermes Compiler Version: {}", VERSION);
 
-    let file_name = args.file;
+    let file_name = args.file.unwrap();
 
     println!("Using input file: {}", file_name);
 
@@ -58,38 +95,29 @@ fn main() {
                 .split(FILE_EXTENSION)
                 .collect::<Vec<&str>>()[0];
 
-            match Parser::init(&source) {
-                Ok(mut parser) => {
-                    match parser.parse(module_name) {
-                        Ok(res) => {
-
-                            if !res.errors.is_empty() {
-                                eprintln!("Errors occurred while parsing!");
-                                exit(1);
-                            }
-
-                            println!("Successfully parsed ast!");
-
-                            // Ensure the output directory exists
-                            if !Path::new(&args.output).exists() {
-                                fs::create_dir_all(args.output.clone()).unwrap();
-                            }
-
-                            if args.emit_ast {
-                                emit_ast(&res.ast, args.output.clone())
-                            }
-
-                            if !compile(module_name, args.output.clone()) {
-                                eprintln!("Failed to compile!");
-                                exit(1);
-                            }
-                        }
-                        Err(err) => {
-                            panic!("Error during parsing: {:?}", err);
-                        }
-                    }
-                }
-                Err(e) => panic!("{:?}", e),
+            let mut parser = if args.script { Parser::init_script(&source) } else { Parser::init(&source) };
+            let res = parser.parse(module_name);
+
+            if !res.errors.is_empty() {
+                let diagnostics: Vec<Diagnostic> = res.errors.iter().map(|error| error.to_diagnostic()).collect();
+                report(&diagnostics, &file_name, &source, args.error_format);
+                exit(1);
+            }
+
+            println!("Successfully parsed ast!");
+
+            // Ensure the output directory exists
+            if !Path::new(&args.output).exists() {
+                fs::create_dir_all(args.output.clone()).unwrap();
+            }
+
+            if args.emit_ast {
+                emit_ast(&res.ast, args.output.clone())
+            }
+
+            if !compile(module_name, args.output.clone()) {
+                eprintln!("Failed to compile!");
+                exit(1);
             }
         }
         Err(error) => {
@@ -97,6 +125,24 @@ fn main() {
         }
     }
 
+    fn report(diagnostics: &[Diagnostic], file_name: &str, source: &str, format: ErrorFormat) {
+        let source_map = SourceMap::new(source);
+        match format {
+            ErrorFormat::Human => {
+                let color = std::io::stderr().is_terminal();
+                for diagnostic in diagnostics {
+                    eprintln!("{}", diagnostic.render(file_name, &source_map, color));
+                }
+                eprintln!("Errors occurred while parsing!");
+            }
+            ErrorFormat::Json => {
+                for diagnostic in diagnostics {
+                    eprintln!("{}", serde_json::to_string(&diagnostic.report(file_name, &source_map)).unwrap());
+                }
+            }
+        }
+    }
+
     fn compile(module_name: &str, output: String) -> bool {
         let llvm_ctx = Context::create();
         let llvm = LLVM::new(&llvm_ctx, module_name);
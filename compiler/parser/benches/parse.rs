@@ -0,0 +1,90 @@
+//! Parsing throughput on a large script, such as a bundled React build:
+//!
+//! ```sh
+//! HERMESC_BENCH_FILE=node_modules/react-dom/umd/react-dom.development.js cargo bench -p hermesc_parser
+//! ```
+//!
+//! Without a file, a generated bundle of about 1MB is parsed instead.
+
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use hermesc_parser::Parser;
+
+/// A module in the style of bundled library code, repeated with distinct names to fill the generated bundle.
+const MODULE: &str = r#"
+/**
+ * @license Component {n}
+ */
+var ReactComponent{n} = (function (exports) {
+  'use strict';
+  var hasOwnProperty = Object.prototype.hasOwnProperty;
+  var RESERVED_PROPS = { key: true, ref: true, __self: true, __source: true };
+  function warning{n}(format, ...args) {
+    var message = 'Warning: ' + format.replace(/%s/g, function () { return String(args.shift()); });
+    if (typeof console !== "undefined" && console.error) {
+      console.error(`${message}\n    in Component{n}`);
+    }
+  }
+  class Updater{n} extends exports.Base {
+    #queue = [];
+    constructor(props, context) {
+      super(props);
+      this.state = { count: 0, label: "item\t{n}", flags: 0x1F & ~0b1010 };
+      this.context = context ?? null;
+    }
+    static get displayName() { return "Updater{n}"; }
+    enqueue(callback, priority = 1.5e3) {
+      for (let i = 0; i < this.#queue.length; i++) {
+        if (this.#queue[i].priority >= priority) {
+          this.#queue.splice(i, 0, { callback, priority });
+          return;
+        }
+      }
+      this.#queue.push({ callback, priority });
+    }
+    async *drain() {
+      while (this.#queue.length > 0) {
+        const { callback, ...rest } = this.#queue.shift();
+        yield await callback?.(rest);
+      }
+    }
+  }
+  // Props other than the reserved ones are copied onto the element.
+  function createElement{n}(type, config, children) {
+    var props = {};
+    for (var propName in config) {
+      if (hasOwnProperty.call(config, propName) && !RESERVED_PROPS.hasOwnProperty(propName)) {
+        props[propName] = config[propName];
+      }
+    }
+    props.children = arguments.length > 3 ? Array.prototype.slice.call(arguments, 2) : children;
+    return { $$typeof: Symbol.for('react.element'), type: type, key: config.key == null ? null : '' + config.key, props };
+  }
+  exports.createElement{n} = createElement{n};
+  exports.Updater{n} = Updater{n};
+  exports.warning{n} = warning{n};
+  return exports;
+})({ Base: class {} });
+"#;
+
+fn source() -> String {
+    if let Ok(path) = std::env::var("HERMESC_BENCH_FILE") {
+        return std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {}: {}", path, err));
+    }
+    (0..500).map(|n| MODULE.replace("{n}", &n.to_string())).collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let source = source();
+    let res = Parser::init_script(&source).parse("bench");
+    assert!(res.errors.is_empty(), "the benchmark source has syntax errors: {:?}", res.errors);
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.sample_size(20);
+    group.bench_function("script", |b| b.iter(|| Parser::init_script(black_box(&source)).parse("bench")));
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -1,10 +1,15 @@
-use serde::Serialize;
-use crate::token::{KeywordKind, LitKind};
+use std::fmt;
+use serde::{Serialize, Serializer};
+use crate::span::Span;
+use crate::symbol::Symbol;
+use crate::token::{BooleanKind, KeywordKind, OpKind};
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Module {
     pub module_name: String,
-    pub body: Vec<StmtKind>,
+    pub body: Vec<Stmt>,
+    pub summary: ModuleSummary,
+    pub span: Span,
 }
 
 impl Module {
@@ -12,23 +17,122 @@ impl Module {
         Module {
             module_name: module.to_string(),
             body: Vec::new(),
+            summary: ModuleSummary::default(),
+            span: Span::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+impl Stmt {
+    pub fn new(kind: StmtKind, span: Span) -> Self {
+        Stmt { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Expr { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Pattern {
+    pub kind: PatternKind,
+    pub span: Span,
+}
+
+impl Pattern {
+    pub fn new(kind: PatternKind, span: Span) -> Self {
+        Pattern { kind, span }
+    }
+}
+
+/// The imports and exports of a module, used to build the module graph.
+///
+/// Section 16.2.1.7 [Source Text Module Records](https://tc39.es/ecma262/#sec-source-text-module-records)
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ModuleSummary {
+    /// Every module requested by an import or re-export, in source order without duplicates.
+    pub requests: Vec<String>,
+    pub imports: Vec<ImportEntry>,
+    pub exports: Vec<ExportEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImportEntry {
+    pub module_request: String,
+    pub import_name: ImportNameKind,
+    pub local_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportEntry {
+    /// `None` for `export * from "a"`.
+    pub export_name: Option<String>,
+    /// The module being re-exported from, if any.
+    pub module_request: Option<String>,
+    /// The name imported from `module_request` for re-exports.
+    pub import_name: Option<ImportNameKind>,
+    /// The local binding being exported, `*default*` for anonymous default exports.
+    pub local_name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ImportNameKind {
+    Name(String),
+    /// import * as a from "a", export * as a from "a"
+    Namespace,
+    /// export * from "a"
+    AllButDefault,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum ExprKind {
     Primary(PrimaryExprKind),
+    Paren(ParenExpr),
+    Member(MemberExpr),
+    Call(CallExpr),
+    New(NewExpr),
+    /// `super`, only valid as the object of a member expression or the callee of a call
+    Super,
+    MetaProp(MetaPropKind),
+    /// import(a), import(a, b)
+    ImportCall(ImportCall),
+    /// tag`a${b}`
+    TaggedTemplate(TaggedTemplateExpr),
+    /// `#a`, only valid as the left operand of `in`
+    PrivateName(Symbol),
+    Update(UpdateExpr),
+    Unary(UnaryExpr),
+    Await(AwaitExpr),
+    Binary(BinaryExpr),
+    Logical(LogicalExpr),
     Conditional(CondExpr),
+    Assign(AssignExpr),
+    Arrow(ArrowFunction),
     Sequence(SeqExpr),
-    Yield(YieldExpr)
+    Yield(YieldExpr),
+    /// A placeholder for an expression that could not be parsed because of a syntax error
+    Error,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum StmtKind {
     Block(BlockStmt),
     Decl(DeclKind),
-    Variable,
+    Variable(VarStmt),
     Empty(EmptyStmt),
     Expression(ExprStmt),
     If(IfStmt),
@@ -39,13 +143,27 @@ pub enum StmtKind {
     With(WithStmt),
     Throw(ThrowStmt),
     Try(TryStmt),
-    Debugger(DebugStmt)
+    Labelled(LabelledStmt),
+    Debugger(DebugStmt),
+    Import(ImportDecl),
+    Export(ExportDeclKind),
+    /// A placeholder for a statement that could not be parsed because of a syntax error
+    Error,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum BreakableStmtKind {
     Switch(SwitchStmt),
-    Iter
+    Iter(IterStmtKind)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum IterStmtKind {
+    DoWhile(DoWhileStmt),
+    While(WhileStmt),
+    For(ForStmt),
+    ForIn(ForInStmt),
+    ForOf(ForOfStmt),
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -63,82 +181,478 @@ pub enum LexicalKind {
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum HoistableDeclKind {
-    Function,
-    AsyncFunction,
-    Generator,
-    AsyncGenerator,
+    Function(Function),
+    AsyncFunction(Function),
+    Generator(Function),
+    AsyncGenerator(Function),
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum PrimaryExprKind {
     This,
-    Id(String),
-    Lit(LitKind),
-    ArrayLiteral,
-    ObjectLiteral,
-    FunctionExpr,
-    AsyncFunctionExpr,
-    ClassExpression,
-    GeneratorExpr,
-    AsyncGeneratorExpr,
-    RegExLiteral(String),
-    TemplateLiteral,
+    Id(Symbol),
+    Lit(LitValue),
+    ArrayLiteral(ArrayLit),
+    ObjectLiteral(ObjectLit),
+    FunctionExpr(Function),
+    AsyncFunctionExpr(Function),
+    ClassExpression(Class),
+    GeneratorExpr(Function),
+    AsyncGeneratorExpr(Function),
+    RegExLiteral(RegExLit),
+    TemplateLiteral(TemplateLit),
+}
+
+/// The value of a literal, as read from its source text.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum LitValue {
+    /// Section 12.9.3 [BigInt literals](https://tc39.es/ecma262/#sec-literals-numeric-literals) such as `123n` or
+    /// `0x1Fn`, as the decimal digits of their value.
+    BigInt(String),
+    Bool(BooleanKind),
+    Null,
+    /// Section 12.9.3 [Numeric Literals](https://tc39.es/ecma262/#sec-literals-numeric-literals) such as `1.5`, `1e-9` or
+    /// `0x1F`, as their Number value.
+    Num(f64),
+    String(StringLit),
+}
+
+/// Section 12.9.4 [String Literals](https://tc39.es/ecma262/#sec-literals-string-literals)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StringLit {
+    /// The string value, with escape sequences replaced by the characters they represent.
+    pub cooked: Utf16String,
+    /// The source text between the quotes.
+    pub raw: String,
+}
+
+/// Section 6.1.4 [The String Type](https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type)
+///
+/// A string value as a sequence of UTF-16 code units, which unlike a [String] can contain lone surrogates, as in
+/// `"\uD83D"`. It serializes as a string, or as its code units if it contains a lone surrogate.
+#[derive(Clone, Default, PartialEq)]
+pub struct Utf16String(pub Vec<u16>);
+
+impl Utf16String {
+    /// Section 6.1.4.1 [IsStringWellFormedUnicode](https://tc39.es/ecma262/#sec-isstringwellformedunicode)
+    pub fn is_well_formed(&self) -> bool {
+        char::decode_utf16(self.0.iter().copied()).all(|char| char.is_ok())
+    }
+
+    /// The string as UTF-8, or [None] if it contains a lone surrogate.
+    pub fn to_utf8(&self) -> Option<String> {
+        String::from_utf16(&self.0).ok()
+    }
+
+    /// The string as UTF-8, with any lone surrogates replaced by U+FFFD.
+    pub fn to_utf8_lossy(&self) -> String {
+        String::from_utf16_lossy(&self.0)
+    }
+}
+
+impl From<&str> for Utf16String {
+    fn from(string: &str) -> Self {
+        Utf16String(string.encode_utf16().collect())
+    }
+}
+
+impl PartialEq<&str> for Utf16String {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.iter().copied().eq(other.encode_utf16())
+    }
+}
+
+impl fmt::Debug for Utf16String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for char in char::decode_utf16(self.0.iter().copied()) {
+            match char {
+                Ok(char) => write!(f, "{}", char.escape_debug())?,
+                Err(err) => write!(f, "\\u{{{:x}}}", err.unpaired_surrogate())?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+impl Serialize for Utf16String {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_utf8() {
+            Some(string) => serializer.serialize_str(&string),
+            None => self.0.serialize(serializer),
+        }
+    }
+}
+
+/// Section 12.9.5 [Regular Expression Literals](https://tc39.es/ecma262/#sec-literals-regular-expression-literals)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RegExLit {
+    /// The source text between the slashes, which is not validated against the pattern grammar.
+    pub pattern: String,
+    pub flags: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum MetaPropKind {
+    /// new.target
+    NewTarget,
+    /// import.meta
+    ImportMeta,
+}
+
+/// An expression that may be spread with `...` in array literals and argument lists.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum SpreadableKind {
+    Expr(Expr),
+    Spread(Expr),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ArrayLit {
+    /// Elisions (holes) are represented by `None`.
+    pub elements: Vec<Option<SpreadableKind>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ObjectLit {
+    pub props: Vec<Prop>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Prop {
+    pub kind: PropKind,
+    pub span: Span,
+}
+
+impl Prop {
+    pub fn new(kind: PropKind, span: Span) -> Self {
+        Prop { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum PropKind {
+    /// { a: b }
+    KeyValue(KeyValueProp),
+    /// { a }
+    Shorthand(Symbol),
+    /// { a() {} }, { get a() {} }, { set a(b) {} }
+    Method(MethodProp),
+    /// { ...a }
+    Spread(Expr),
+    /// { a = b }, only valid when the object literal is reinterpreted as an assignment pattern
+    CoverInit(CoverInitProp),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum PropNameKind {
+    Id(Symbol),
+    Lit(LitValue),
+    Computed(Box<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct KeyValueProp {
+    pub key: PropNameKind,
+    pub value: Expr,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CoverInitProp {
+    pub id: Symbol,
+    pub init: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum MethodKind {
+    Constructor,
+    Method,
+    Get,
+    Set,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MethodProp {
+    pub kind: MethodKind,
+    pub key: PropNameKind,
+    pub value: Function,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Function {
+    pub id: Option<Symbol>,
+    pub params: Vec<Pattern>,
+    pub body: Vec<Stmt>,
+    pub is_async: bool,
+    pub is_generator: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ArrowFunction {
+    pub params: Vec<Pattern>,
+    pub body: ArrowBodyKind,
+    pub is_async: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ArrowBodyKind {
+    /// x => x
+    Expr(Box<Expr>),
+    /// x => { return x; }
+    Block(Vec<Stmt>),
+}
+
+/// Binding targets, as used by function parameters.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum PatternKind {
+    Id(Symbol),
+    Object(ObjectPattern),
+    Array(ArrayPattern),
+    /// ...a
+    Rest(Box<Pattern>),
+    /// a = b
+    Assign(AssignPattern),
+    /// a.b, only valid as an assignment target
+    Member(MemberExpr),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ObjectPattern {
+    pub props: Vec<ObjectPatternProp>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ObjectPatternProp {
+    pub kind: ObjectPatternPropKind,
+    pub span: Span,
+}
+
+impl ObjectPatternProp {
+    pub fn new(kind: ObjectPatternPropKind, span: Span) -> Self {
+        ObjectPatternProp { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ObjectPatternPropKind {
+    /// { a: b }
+    KeyValue(KeyValuePatternProp),
+    /// { a }, { a = b }
+    Shorthand(ShorthandPatternProp),
+    /// { ...a }
+    Rest(Box<Pattern>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct KeyValuePatternProp {
+    pub key: PropNameKind,
+    pub value: Pattern,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ShorthandPatternProp {
+    pub id: Symbol,
+    pub default: Option<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ArrayPattern {
+    /// Elisions (holes) are represented by `None`.
+    pub elements: Vec<Option<Pattern>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AssignExpr {
+    pub op: OpKind,
+    pub target: Box<Pattern>,
+    pub value: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AssignPattern {
+    pub target: Box<Pattern>,
+    pub default: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum MemberPropKind {
+    /// a.b
+    Id(Symbol),
+    /// a[b]
+    Computed(Box<Expr>),
+    /// a.#b
+    Private(Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MemberExpr {
+    pub object: Box<Expr>,
+    pub prop: MemberPropKind,
+    /// Accessed with `?.`
+    pub optional: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CallExpr {
+    pub callee: Box<Expr>,
+    pub args: Vec<SpreadableKind>,
+    /// Called with `?.`
+    pub optional: bool,
+}
+
+/// Section 13.3.11 [Tagged Templates](https://tc39.es/ecma262/#sec-tagged-templates)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TaggedTemplateExpr {
+    pub tag: Box<Expr>,
+    pub quasi: TemplateLit,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NewExpr {
+    pub callee: Box<Expr>,
+    /// `None` when constructed without an argument list, as in `new Foo`.
+    pub args: Option<Vec<SpreadableKind>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct LetDecl {
-    pub identifier: String,
-    pub expression: ExprKind
+    pub declarators: Vec<VarDeclarator>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct ConstDecl {
-    pub identifier: String,
-    pub expression: ExprKind
+    pub declarators: Vec<VarDeclarator>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VarStmt {
+    pub declarators: Vec<VarDeclarator>,
+}
+
+/// Operators are either punctuators (`+`, `!`, ...) or keywords (`typeof`, `in`, ...).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum OperatorKind {
+    Punc(OpKind),
+    Keyword(KeywordKind),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ParenExpr {
+    pub expr: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UpdateExpr {
+    pub op: OpKind,
+    pub prefix: bool,
+    pub arg: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UnaryExpr {
+    pub op: OperatorKind,
+    pub arg: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AwaitExpr {
+    pub arg: Box<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BinaryExpr {
+    pub op: OperatorKind,
+    pub lhs: Box<Expr>,
+    pub rhs: Box<Expr>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
-pub struct AdditiveExpr {
-    pub lhs: ExprKind,
-    pub rhs: ExprKind
+pub struct LogicalExpr {
+    pub op: OpKind,
+    pub lhs: Box<Expr>,
+    pub rhs: Box<Expr>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct CondExpr {
-    pub cond: Box<ExprKind>,
-    pub consequent: Box<ExprKind>,
-    pub alternate: Box<ExprKind>
+    pub cond: Box<Expr>,
+    pub consequent: Box<Expr>,
+    pub alternate: Box<Expr>
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SeqExpr {
-    pub exprs: Vec<ExprKind>,
+    pub exprs: Vec<Expr>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct YieldExpr {
     pub delegate: bool,
-    pub arg: Option<Box<ExprKind>>,
+    pub arg: Option<Box<Expr>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct BlockStmt {
-    pub stmts: Vec<StmtKind>
+    pub stmts: Vec<Stmt>
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct BreakStmt {
-    pub label: Option<String>,
+    pub label: Option<Symbol>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Class {
+    pub id: Option<Symbol>,
+    pub super_class: Option<Box<Expr>>,
+    pub body: Vec<ClassElement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClassElement {
+    pub kind: ClassElementKind,
+    pub span: Span,
+}
+
+impl ClassElement {
+    pub fn new(kind: ClassElementKind, span: Span) -> Self {
+        ClassElement { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ClassElementKind {
+    Method(ClassMethod),
+    Field(ClassField),
+    /// static { }
+    StaticBlock(Vec<Stmt>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ClassElementNameKind {
+    Public(PropNameKind),
+    /// #a
+    Private(Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClassMethod {
+    pub kind: MethodKind,
+    pub key: ClassElementNameKind,
+    pub value: Function,
+    pub is_static: bool,
+}
 
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClassField {
+    pub key: ClassElementNameKind,
+    pub value: Option<Expr>,
+    pub is_static: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct ContinueStmt {
-    pub label: Option<String>,
+    pub label: Option<Symbol>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -148,30 +662,137 @@ pub struct DebugStmt;
 pub struct EmptyStmt;
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
-pub struct ExprStmt(pub ExprKind);
+pub struct ExprStmt(pub Expr);
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct IfStmt {
-    pub cond: ExprKind,
-    pub body: Box<StmtKind>,
-    pub alternative: Option<Box<StmtKind>>,
+    pub cond: Expr,
+    pub body: Box<Stmt>,
+    pub alternative: Option<Box<Stmt>>,
+}
+
+/// Section 13.2.8 [Template Literals](https://tc39.es/ecma262/#sec-template-literals)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TemplateLit {
+    /// The characters around the substitutions, of which there is always one more than substitutions.
+    pub quasis: Vec<TemplateElement>,
+    pub exprs: Vec<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TemplateElement {
+    /// [None] if the characters contain an invalid escape sequence, which is only allowed in tagged templates.
+    pub cooked: Option<Utf16String>,
+    /// The source text, with line terminator sequences normalized to `\n`.
+    pub raw: String,
+    /// The span of the characters, without the `` ` ``, `${` or `}` around them.
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImportCall {
+    pub source: Box<Expr>,
+    pub options: Option<Box<Expr>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImportDecl {
+    /// Empty for imports only evaluated for their side effects, as in `import "a"`.
+    pub specifiers: Vec<ImportSpecifierKind>,
+    pub source: String,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ImportSpecifierKind {
+    /// import a from "a"
+    Default(Symbol),
+    /// import * as a from "a"
+    Namespace(Symbol),
+    /// import { a as b } from "a"
+    Named(ImportSpecifier),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImportSpecifier {
+    pub imported: String,
+    pub local: Symbol,
+    pub span: Span,
+}
+
+/// with { type: "json" }
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImportAttribute {
+    pub key: String,
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ExportDeclKind {
+    /// export { a as b }, export { a } from "a"
+    Named(NamedExport),
+    /// export * from "a", export * as a from "a"
+    All(ExportAll),
+    /// export var a
+    Var(VarStmt),
+    /// export let a, export function a() {}, export class A {}
+    Decl(DeclKind),
+    /// export default a
+    Default(ExportDefaultKind),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NamedExport {
+    pub specifiers: Vec<ExportSpecifier>,
+    pub source: Option<String>,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportSpecifier {
+    pub local: String,
+    pub exported: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportAll {
+    pub exported: Option<String>,
+    pub source: String,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ExportDefaultKind {
+    /// The name of a default exported function is optional.
+    Function(HoistableDeclKind),
+    /// The name of a default exported class is optional.
+    Class(Class),
+    Expr(Expr),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LabelledStmt {
+    pub label: Symbol,
+    pub body: Box<Stmt>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct ReturnStmt {
-    pub return_value: Option<Box<ExprKind>>,
+    pub return_value: Option<Box<Expr>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SwitchStmt {
-    pub switch_expr: ExprKind,
-    pub cases: Option<Vec<SwitchCase>>,
-    pub default: Option<SwitchCase>,
+    pub switch_expr: Expr,
+    /// Cases in source order, including the default case, as execution falls through from one to the next.
+    pub cases: Vec<SwitchCase>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct ThrowStmt {
-    pub throws_expr: ExprKind,
+    pub throws_expr: Expr,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -183,18 +804,82 @@ pub struct TryStmt {
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct WithStmt {
-    pub with_expr: ExprKind,
-    pub with_block: Box<StmtKind>,
+    pub with_expr: Expr,
+    pub with_block: Box<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DoWhileStmt {
+    pub body: Box<Stmt>,
+    pub cond: Expr,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WhileStmt {
+    pub cond: Expr,
+    pub body: Box<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ForStmt {
+    pub init: Option<ForInitKind>,
+    pub test: Option<Expr>,
+    pub update: Option<Expr>,
+    pub body: Box<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ForInStmt {
+    pub left: ForHeadKind,
+    pub right: Expr,
+    pub body: Box<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ForOfStmt {
+    pub left: ForHeadKind,
+    pub right: Expr,
+    pub body: Box<Stmt>,
+    /// for await (a of b)
+    pub is_await: bool,
+}
+
+/// The first clause of a `for (init; test; update)` statement.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ForInitKind {
+    Var(Vec<VarDeclarator>),
+    Let(Vec<VarDeclarator>),
+    Const(Vec<VarDeclarator>),
+    Expr(Expr),
+}
+
+/// The left side of a `for-in` or `for-of` statement.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ForHeadKind {
+    Var(Pattern),
+    Let(Pattern),
+    Const(Pattern),
+    Pattern(Pattern),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VarDeclarator {
+    pub id: Pattern,
+    pub init: Option<Expr>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct CatchClause {
-    pub catch_param: Option<Box<ExprKind>>,
+    pub catch_param: Option<Pattern>,
     pub catch_block: Box<BlockStmt>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct SwitchCase {
-    pub test: Option<ExprKind>,
-    pub body: Vec<StmtKind>,
-}
\ No newline at end of file
+    /// `None` for the default case.
+    pub test: Option<Expr>,
+    pub body: Vec<Stmt>,
+    pub span: Span,
+}
@@ -0,0 +1,758 @@
+use std::fmt;
+use serde::Serialize;
+use crate::source_map::SourceMap;
+use crate::span::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityKind {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for SeverityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeverityKind::Error => write!(f, "error"),
+            SeverityKind::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelKind {
+    /// The location of the problem, underlined with `^`.
+    Primary,
+    /// Related locations that help explain the problem, underlined with `-`.
+    Secondary,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub kind: LabelKind,
+    pub span: Span,
+    pub message: String,
+}
+
+/// A fix for the problem, which replaces the text of the span with the replacement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub span: Span,
+    pub message: String,
+    pub replacement: String,
+}
+
+/// A problem found in the source, shared by the lexer, parser and later passes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: SeverityKind,
+    /// The stable error code, which `hermesc explain` describes in more detail.
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: SeverityKind, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity,
+            code: None,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(SeverityKind::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(SeverityKind::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_primary_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label { kind: LabelKind::Primary, span, message: message.into() });
+        self
+    }
+
+    pub fn with_secondary_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label { kind: LabelKind::Secondary, span, message: message.into() });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn with_suggestion(mut self, span: Span, message: impl Into<String>, replacement: impl Into<String>) -> Self {
+        self.suggestions.push(Suggestion { span, message: message.into(), replacement: replacement.into() });
+        self
+    }
+
+    /// The span of the first primary label, which is the location reported for the diagnostic.
+    pub fn primary_span(&self) -> Option<Span> {
+        self.labels.iter().find(|label| label.kind == LabelKind::Primary).map(|label| label.span)
+    }
+
+    /// Renders the diagnostic as an annotated source snippet in the style of rustc, using ANSI colors if `color` is
+    /// set:
+    ///
+    /// ```text
+    /// error[E0100]: unexpected `)`
+    ///  --> main.hs:1:9
+    ///   |
+    /// 1 | let a = );
+    ///   |         ^ unexpected `)`
+    /// ```
+    pub fn render(&self, file_name: &str, source_map: &SourceMap, color: bool) -> String {
+        let style = Style { color };
+        let severity_color = match self.severity {
+            SeverityKind::Error => RED,
+            SeverityKind::Warning => YELLOW,
+        };
+
+        let mut labels: Vec<(usize, usize, usize, &Label)> = self.labels.iter()
+            .map(|label| {
+                let start = source_map.lookup(label.span.start);
+                let end = source_map.lookup(label.span.end);
+                // Spans over several lines are only underlined up to the end of their first line.
+                let end_column = if end.line == start.line { end.column } else { usize::MAX };
+                (start.line, start.column, end_column, label)
+            })
+            .collect();
+        labels.sort_by_key(|&(line, column, _, label)| (line, column, label.kind == LabelKind::Secondary));
+
+        let gutter = labels.iter().map(|&(line, ..)| (line + 1).to_string().len()).max().unwrap_or(1);
+        let pad = " ".repeat(gutter);
+
+        let mut out = String::new();
+        let header = match self.code {
+            Some(code) => format!("{}[{}]", self.severity, code),
+            None => self.severity.to_string(),
+        };
+        out += &format!("{}{}\n", style.paint(&header, severity_color), style.paint(&format!(": {}", self.message), BOLD));
+
+        let location = self.primary_span().or(labels.first().map(|&(.., label)| label.span));
+        if let Some(span) = location {
+            let pos = source_map.lookup(span.start);
+            out += &format!("{}{} {}:{}:{}\n", pad, style.paint("-->", BLUE), file_name, pos.line + 1, pos.column + 1);
+        }
+
+        if !labels.is_empty() {
+            out += &format!("{} {}\n", pad, style.paint("|", BLUE));
+        }
+
+        let mut prev_line = None;
+        for &(line, column, end_column, label) in &labels {
+            if prev_line != Some(line) {
+                if prev_line.is_some_and(|prev| line > prev + 1) {
+                    out += &format!("{}\n", style.paint("...", BLUE));
+                }
+                // Tabs are shown as single spaces so that columns in characters line up with the underlines.
+                let text = source_map.line_text(line).replace('\t', " ");
+                out += &format!("{} {} {}\n", style.paint(&format!("{:>gutter$}", line + 1), BLUE), style.paint("|", BLUE), text);
+                prev_line = Some(line);
+            }
+
+            let line_len = source_map.line_text(line).chars().count();
+            let width = end_column.min(line_len).saturating_sub(column).max(1);
+            let (marker, marker_color) = match label.kind {
+                LabelKind::Primary => ("^", severity_color),
+                LabelKind::Secondary => ("-", BLUE),
+            };
+            let mut annotation = marker.repeat(width);
+            if !label.message.is_empty() {
+                annotation += " ";
+                annotation += &label.message;
+            }
+            out += &format!("{} {} {}{}\n", pad, style.paint("|", BLUE), " ".repeat(column), style.paint(&annotation, marker_color));
+        }
+
+        if !labels.is_empty() && (!self.notes.is_empty() || self.help.is_some() || !self.suggestions.is_empty()) {
+            out += &format!("{} {}\n", pad, style.paint("|", BLUE));
+        }
+        for note in &self.notes {
+            out += &format!("{} {} {}: {}\n", pad, style.paint("=", BLUE), style.paint("note", BOLD), note);
+        }
+        if let Some(help) = &self.help {
+            out += &format!("{} {} {}: {}\n", pad, style.paint("=", BLUE), style.paint("help", BOLD), help);
+        }
+        for suggestion in &self.suggestions {
+            out += &format!("{} {} {}: {}\n", pad, style.paint("=", BLUE), style.paint("help", BOLD), suggestion.message);
+        }
+
+        out
+    }
+
+    /// Resolves the spans of the diagnostic to lines and columns, for `hermesc --error-format=json`.
+    pub fn report<'a>(&'a self, file_name: &'a str, source_map: &SourceMap) -> DiagnosticReport<'a> {
+        DiagnosticReport {
+            severity: self.severity,
+            code: self.code,
+            message: &self.message,
+            file: file_name,
+            span: self.primary_span().map(|span| SpanReport::new(span, source_map)),
+            labels: self.labels.iter()
+                .map(|label| LabelReport {
+                    kind: label.kind,
+                    message: &label.message,
+                    span: SpanReport::new(label.span, source_map),
+                })
+                .collect(),
+            notes: &self.notes,
+            help: self.help.as_deref(),
+            suggestions: self.suggestions.iter()
+                .map(|suggestion| SuggestionReport {
+                    message: &suggestion.message,
+                    replacement: &suggestion.replacement,
+                    span: SpanReport::new(suggestion.span, source_map),
+                })
+                .collect(),
+            rendered: self.render(file_name, source_map, false),
+        }
+    }
+}
+
+/// A [Diagnostic] with its spans resolved to lines and columns, which serializes to the JSON emitted for editors
+/// and CI.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DiagnosticReport<'a> {
+    pub severity: SeverityKind,
+    pub code: Option<&'static str>,
+    pub message: &'a str,
+    pub file: &'a str,
+    /// The span of the primary label.
+    pub span: Option<SpanReport>,
+    pub labels: Vec<LabelReport<'a>>,
+    pub notes: &'a [String],
+    pub help: Option<&'a str>,
+    pub suggestions: Vec<SuggestionReport<'a>>,
+    /// The diagnostic as rendered for humans, without colors.
+    pub rendered: String,
+}
+
+/// A span with its lines and columns, which start from one. Columns count characters, with the UTF-16 columns used by
+/// editors alongside.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SpanReport {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub utf16_column_start: usize,
+    pub utf16_column_end: usize,
+}
+
+impl SpanReport {
+    pub fn new(span: Span, source_map: &SourceMap) -> Self {
+        let start = source_map.lookup(span.start);
+        let end = source_map.lookup(span.end);
+        SpanReport {
+            byte_start: span.start,
+            byte_end: span.end,
+            line_start: start.line + 1,
+            line_end: end.line + 1,
+            column_start: start.column + 1,
+            column_end: end.column + 1,
+            utf16_column_start: start.utf16_column + 1,
+            utf16_column_end: end.utf16_column + 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LabelReport<'a> {
+    pub kind: LabelKind,
+    pub message: &'a str,
+    pub span: SpanReport,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SuggestionReport<'a> {
+    pub message: &'a str,
+    pub replacement: &'a str,
+    pub span: SpanReport,
+}
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Describes an error code in detail, as printed by `hermesc explain <code>`.
+pub fn explain(code: &str) -> Option<&'static str> {
+    let explanation = match code {
+        "E0001" => "\
+An invalid token was found in the source.
+
+The lexer could not recognize the characters at this position as part of any
+token. Check for a typo, or characters copied from outside of the source code.",
+        "E0002" => "\
+A numeric literal is malformed.
+
+Numeric literals need at least one digit after a prefix such as `0x`, and after
+the `e` of an exponent. Separators must be single `_` characters between digits,
+and a literal can not be directly followed by an identifier or another digit:
+
+    let a = 0x;        // error
+    let b = 1__000;    // error
+    let c = 1_000;     // ok
+    let d = 3in [3];   // error
+    let e = 3 in [3];  // ok
+
+Only integers can have the BigInt suffix `n`, as in `123n` or `0x1Fn`.",
+        "E0003" => "\
+A numeric literal with a leading zero was found in strict mode code.
+
+Outside of strict mode, literals such as `017` are octal numbers, and literals
+such as `08` are decimal numbers. Modules are always strict mode code, which does
+not allow either:
+
+    let a = 017;  // error
+    let b = 0o17; // ok, 15
+    let c = 08;   // error
+    let d = 8;    // ok
+
+Use the `0o` prefix for octal numbers, or remove the leading zeros of decimal
+numbers.",
+        "E0004" => "\
+A string literal was not closed before the end of its line.
+
+String literals can not contain unescaped line breaks:
+
+    let a = \"abc
+    def\";
+
+Close the string on the same line, use `\\n` to include a line break, or end the
+line with `\\` to continue the string on the next line without one.",
+        "E0005" => "\
+An escape sequence in a string literal is malformed.
+
+Hexadecimal escapes take exactly two hexadecimal digits, and Unicode escapes take
+exactly four, or a code point of at most 10FFFF in braces:
+
+    let a = \"\\x4\";         // error
+    let b = \"\\x41\";        // ok, \"A\"
+    let c = \"\\u{110000}\";  // error
+    let d = \"\\u{1F600}\";   // ok
+
+To include a backslash itself, escape it as `\\\\`.",
+        "E0006" => "\
+A legacy octal escape sequence was found in strict mode code.
+
+Outside of strict mode, escapes such as `\\01` represent characters by their
+octal code, and `\\8` and `\\9` represent the digits themselves. Modules are
+always strict mode code, which does not allow either:
+
+    let a = \"\\01\";   // error
+    let b = \"\\x01\";  // ok
+    let c = \"\\8\";    // error
+    let d = \"8\";     // ok
+
+`\\0` on its own is allowed, as long as it is not followed by a digit.",
+        "E0007" => "\
+A template literal was not closed before the end of the input.
+
+Template literals can span lines, so a missing closing backtick is only found at
+the end of the input. Check that every `` ` `` is matched, and that every `${` in
+a template is closed by a `}`:
+
+    let a = `abc${d`;  // error
+    let b = `abc${d}`; // ok",
+        "E0008" => "\
+A regular expression literal was not closed before the end of the line.
+
+A regular expression literal can not span lines. A `/` ends the pattern unless it
+is escaped, or within a character class:
+
+    let a = /a[/b/;  // error, the class is not closed
+    let b = /a\\/b/;  // ok
+    let c = /a[/]b/; // ok",
+        "E0009" => "\
+A regular expression literal has invalid flags.
+
+The flags following the closing `/` must each be one of `d`, `g`, `i`, `m`, `s`,
+`u`, `v` and `y`, and can only be used once. The `u` and `v` flags can not be
+combined, as `v` is an extension of `u`:
+
+    let a = /abc/gg; // error
+    let b = /abc/uv; // error
+    let c = /abc/gi; // ok",
+        "E0010" => "\
+A multi-line comment was not closed before the end of the input.
+
+Multi-line comments can span lines, so a missing `*/` is only found at the end of
+the input. Check that every `/*` is matched by a `*/`:
+
+    /* a
+    let b = 1;       // error, still within the comment
+    /* a */
+    let b = 1;       // ok",
+        "E0100" => "\
+A token appeared where the grammar does not allow it.
+
+For example, a statement can not start with `)`, and two expressions on the same
+line must be separated by a semicolon:
+
+    let a = 1 let b = 2;
+
+Check the code before the token for a missing operator, separator or closing
+bracket.",
+        "E0101" => "\
+An expression was assigned to, but it is not a valid assignment target.
+
+Only identifiers, member expressions such as `a.b` or `a[b]`, and array or object
+destructuring patterns can be assigned to:
+
+    a + b = c;      // error
+    [a, b + 1] = c; // error
+    [a, b.c] = d;   // ok
+
+Optional chains such as `a?.b` can not be assigned to either.",
+        "E0102" => "\
+A statement was followed by another token on the same line without a semicolon.
+
+Semicolons can only be left out at the end of a line, before a `}` or at the end
+of the input:
+
+    let a = 1 let b = 2; // error
+    let a = 1; let b = 2; // ok
+
+Insert a `;` or a line break between the statements.",
+        "E0103" => "\
+Statements, expressions or patterns were nested too deeply.
+
+The parser limits how deeply code can be nested, such as parentheses within
+parentheses or blocks within blocks, to 256 levels to avoid running out of stack
+space. Code nested this deeply is rare outside of generated code:
+
+    let a = ((((((((((/* ...hundreds more... */ 1))))))))));
+
+Split the code up using variables or functions.",
+        "E0104" => "\
+A template literal that is not tagged contains an invalid escape sequence.
+
+Tagged templates can contain escape sequences that are not valid in strings, as
+the tag function receives the raw text. Other templates can not:
+
+    let a = `\\unicode`;            // error
+    let b = String.raw`\\unicode`;  // ok
+    let c = `\\\\unicode`;           // ok
+
+Escape the backslash as `\\\\` to include it in the template.",
+        "E0105" => "\
+A reserved word was written with escape sequences and used as an identifier.
+
+Escape sequences in a name, such as `\\u0069f` for `if`, do not make it an
+identifier. Reserved words can only be used as property names, escaped or not:
+
+    let \\u0069f = 1; // error
+    a.\\u0069f = 1;   // ok
+    a.if = 1;         // ok",
+        "E0106" => "\
+A word that is reserved in the current context was used as an identifier.
+
+Strict mode code, which includes modules and class bodies, reserves words such
+as `let`, `static` and `implements`. `yield` is reserved inside generators and
+`await` inside async functions and modules. They remain valid property names:
+
+    \"use strict\";
+    var let = 1;       // error
+    function* g() {
+        var yield = 1; // error
+    }
+    a.static = 1;      // ok",
+        "E0107" => "\
+A let or const declaration binds the name `let`.
+
+Outside of strict mode code, `let` is an identifier that `var` declarations and
+functions can bind. Let and const declarations can never bind it, as it would be
+ambiguous with another declaration:
+
+    let let = 1;        // error
+    const [let] = a;    // error
+    for (let let of a); // error
+    var let = 1;        // ok",
+        "E0108" => "\
+A yield or await expression was used in the parameters of a function.
+
+The default values of parameters are evaluated before the body of a generator or
+async function starts running, when it can not yet be suspended:
+
+    function* g(a = yield) {}           // error
+    async function f(a = await b) {}    // error
+    async (a = await b) => a;           // error
+    async function f() { let a = await b; } // ok",
+        "E0109" => "\
+`eval` or `arguments` was bound or assigned to in strict mode code.
+
+In strict mode code, which includes modules and classes, these names can only be
+referenced:
+
+    \"use strict\";
+    eval = 1;               // error
+    arguments++;            // error
+    function f(eval) {}     // error
+    let arguments;          // error
+    eval(code);             // ok",
+        "E0110" => "\
+`arguments` was referenced in a class field initializer or static block.
+
+Class field initializers and static blocks are evaluated as if in a method with no
+arguments, so they have no `arguments` object. It can still be referenced within a
+nested function, other than an arrow function:
+
+    class A { x = arguments; }                      // error
+    class A { static { f(arguments); } }            // error
+    class A { x = () => arguments; }                // error
+    class A { x = function() { return arguments; } } // ok",
+        "E0111" => "\
+A `break` without a label was used outside of any loop or switch statement.
+
+    if (a) { break; }           // error
+    while (a) { break; }        // ok
+    b: { if (a) break b; }      // ok, breaks out of the labelled block",
+        "E0112" => "\
+A `continue` was used outside of any loop, or with the label of a statement that
+is not a loop.
+
+    if (a) { continue; }        // error
+    b: { continue b; }          // error
+    b: while (a) { continue b; } // ok",
+        "E0113" => "\
+A `break` or `continue` referred to a label that is not on any enclosing
+statement. Labels can not be referred to across function boundaries:
+
+    while (a) { break b; }                  // error
+    b: while (a) { f(() => { break b; }); } // error
+    b: while (a) { break b; }               // ok",
+        "E0114" => "\
+A labelled statement was nested in another statement with the same label:
+
+    a: a: ;                     // error
+    a: while (b) { a: ; }       // error
+    a: ; a: ;                   // ok",
+        "E0115" => "\
+A with statement was used in strict mode code, which includes modules and
+classes:
+
+    \"use strict\";
+    with (o) { x = 1; }         // error
+    o.x = 1;                    // ok",
+        "E0116" => "\
+A string literal naming an import or export contained a lone surrogate, a UTF-16
+code unit in the range U+D800 to U+DFFF that is not part of a surrogate pair:
+
+    export { a as \"\\uD83D\" };               // error
+    export { a as \"\\uD83D\\uDE00\" };         // ok
+    import { \"\\uDE00\" as b } from \"mod\";    // error",
+        "E0117" => "\
+A class had more than one constructor:
+
+    class A { constructor() {} constructor(a) {} }      // error
+    class A { constructor() {} static constructor() {} } // ok, a static method",
+        "E0118" => "\
+A getter had parameters, or a setter did not have exactly one parameter. The
+parameter of a setter can not be a rest parameter:
+
+    ({ get a(b) {} });          // error
+    ({ set a() {} });           // error
+    ({ set a(...b) {} });       // error
+    ({ get a() {}, set a(b) {} }); // ok",
+        "E0119" => "\
+A name was bound by more than one parameter. This is only allowed in functions
+outside of strict mode code whose parameters are all plain identifiers, and never
+in arrow functions or methods:
+
+    function f(a, a) {}                     // ok
+    function f(a, [a]) {}                   // error
+    function f(a, a) { \"use strict\"; }      // error
+    (a, a) => a;                            // error
+    ({ m(a, a) {} });                       // error",
+        "E0120" => "\
+An identifier reference was the operand of `delete` in strict mode code, which
+includes modules and classes. Only properties can be deleted:
+
+    \"use strict\";
+    delete x;                   // error
+    delete (x);                 // error
+    delete o.x;                 // ok",
+        _ => return None,
+    };
+    Some(explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::LexerErrorKind;
+    use crate::source_map::SourceMap;
+    use crate::span::Span;
+    use crate::token::{PuncKind, TokenKind};
+    use crate::{ParseErrorKind, Parser};
+    use super::{explain, Diagnostic};
+
+    #[test]
+    fn test_render() {
+        let source = "let a = 1;\nlet b = );\n";
+        let diagnostic = Diagnostic::error("unexpected `)`")
+            .with_code("E0100")
+            .with_primary_label(Span::new(19, 20), "expected an expression")
+            .with_secondary_label(Span::new(15, 16), "while parsing this declaration")
+            .with_note("a note")
+            .with_help("some help");
+
+        assert_eq!(
+            diagnostic.render("main.hs", &SourceMap::new(source), false),
+            "error[E0100]: unexpected `)`\n \
+             --> main.hs:2:9\n  \
+              |\n\
+             2 | let b = );\n  \
+              |     - while parsing this declaration\n  \
+              |         ^ expected an expression\n  \
+              |\n  \
+              = note: a note\n  \
+              = help: some help\n"
+        );
+    }
+
+    #[test]
+    fn test_render_multiline() {
+        let source = "a\n\nfoo(bar,\n  baz) = 1;";
+        let diagnostic = Diagnostic::error("invalid assignment target")
+            .with_primary_label(Span::new(3, 17), "")
+            .with_secondary_label(Span::new(0, 1), "first");
+
+        assert_eq!(
+            diagnostic.render("main.hs", &SourceMap::new(source), false),
+            "error: invalid assignment target\n \
+             --> main.hs:3:1\n  \
+              |\n\
+             1 | a\n  \
+              | - first\n\
+             ...\n\
+             3 | foo(bar,\n  \
+              | ^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let source = "let a = 1;\n[a, b + c] = d;";
+        let errors = Parser::init(source).parse("test").errors;
+
+        assert_eq!(
+            errors[0].to_diagnostic().render("main.hs", &SourceMap::new(source), false),
+            "error[E0101]: invalid assignment target\n \
+             --> main.hs:2:5\n  \
+              |\n\
+             2 | [a, b + c] = d;\n  \
+              |     ^^^^^ can not be assigned to\n  \
+              |\n  \
+              = help: only identifiers, member expressions and destructuring patterns can be assigned to\n"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let source = "let é = 1 let b = 2;";
+        let err = Parser::init(source).parse("test").errors.remove(0);
+        let diagnostic = err.to_diagnostic();
+        let report = serde_json::to_value(diagnostic.report("main.hs", &SourceMap::new(source))).unwrap();
+
+        let span = serde_json::json!({
+            "byte_start": 10,
+            "byte_end": 10,
+            "line_start": 1,
+            "line_end": 1,
+            "column_start": 10,
+            "column_end": 10,
+            "utf16_column_start": 10,
+            "utf16_column_end": 10,
+        });
+        assert_eq!(report["severity"], "error");
+        assert_eq!(report["code"], "E0102");
+        assert_eq!(report["message"], "expected `;`, found identifier `let`");
+        assert_eq!(report["file"], "main.hs");
+        assert_eq!(report["span"], span);
+        assert_eq!(report["labels"][0]["kind"], "primary");
+        assert_eq!(report["suggestions"][0]["replacement"], ";");
+        assert_eq!(report["suggestions"][0]["span"], span);
+        assert_eq!(report["rendered"], diagnostic.render("main.hs", &SourceMap::new(source), false));
+    }
+
+    #[test]
+    fn test_explain() {
+        let codes = [
+            LexerErrorKind::InvalidToken.code(),
+            LexerErrorKind::InvalidNumericLiteral.code(),
+            LexerErrorKind::LegacyOctalLiteral.code(),
+            LexerErrorKind::UnterminatedString.code(),
+            LexerErrorKind::InvalidEscape.code(),
+            LexerErrorKind::LegacyOctalEscape.code(),
+            LexerErrorKind::UnterminatedTemplate.code(),
+            LexerErrorKind::UnterminatedRegEx.code(),
+            LexerErrorKind::InvalidRegExFlags.code(),
+            LexerErrorKind::UnterminatedComment.code(),
+            ParseErrorKind::UnexpectedToken(TokenKind::Punc(PuncKind::Comma), Vec::new()).code(),
+            ParseErrorKind::InvalidAssignmentTarget.code(),
+            ParseErrorKind::MissingSemicolon(TokenKind::Eof).code(),
+            ParseErrorKind::NestingTooDeep.code(),
+            ParseErrorKind::InvalidTemplateEscape.code(),
+            ParseErrorKind::EscapedKeyword.code(),
+            ParseErrorKind::ReservedWord(String::new()).code(),
+            ParseErrorKind::LexicallyBoundLet.code(),
+            ParseErrorKind::YieldOrAwaitInParameters.code(),
+            ParseErrorKind::StrictEvalOrArguments(String::new()).code(),
+            ParseErrorKind::ArgumentsInClassInitializer.code(),
+            ParseErrorKind::IllegalBreak.code(),
+            ParseErrorKind::IllegalContinue.code(),
+            ParseErrorKind::UndefinedLabel(String::new()).code(),
+            ParseErrorKind::DuplicateLabel(String::new()).code(),
+            ParseErrorKind::StrictWith.code(),
+            ParseErrorKind::IllFormedExportName.code(),
+            ParseErrorKind::DuplicateConstructor.code(),
+            ParseErrorKind::InvalidAccessorParams.code(),
+            ParseErrorKind::DuplicateParameter(String::new()).code(),
+            ParseErrorKind::StrictDelete.code(),
+        ];
+        for code in codes {
+            assert!(explain(code).is_some(), "missing explanation for {}", code);
+        }
+        assert!(explain("E9999").is_none());
+    }
+}
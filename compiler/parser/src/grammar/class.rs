@@ -1,10 +1,396 @@
-use crate::ast::{Class, StmtKind};
-use crate::Parser;
+use crate::ast::{Class, ClassElement, ClassElementKind, ClassElementNameKind, ClassField, ClassMethod, LitValue, MethodKind, PropNameKind};
+use crate::ParseErrorKind::UnexpectedToken;
+use crate::{ParseErrorKind, Parser};
+use crate::symbol::sym;
+use crate::token::{BraceKind, KeywordKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
 
-impl Parser {
+impl Parser<'_> {
 
     /// Section 15.7 [Class Definitions](https://tc39.es/ecma262/#sec-class-definitions)
     pub(crate) fn parse_class_stmt(&mut self) -> Class {
-        todo!()
+        self.parse_class(true)
     }
-}
\ No newline at end of file
+
+    /// Section 16.2.3 [Exports](https://tc39.es/ecma262/#sec-exports), where the name of a default exported class
+    /// declaration is optional.
+    pub(crate) fn parse_default_class_decl(&mut self) -> Class {
+        self.parse_class(false)
+    }
+
+    /// Section 15.7 [ClassExpression](https://tc39.es/ecma262/#prod-ClassExpression)
+    pub(crate) fn parse_class_expr(&mut self) -> Class {
+        self.parse_class(false)
+    }
+
+    /// All parts of a class, including its name and heritage, are strict mode code.
+    fn parse_class(&mut self, require_id: bool) -> Class {
+        self.with_strict(|p| p.parse_strict_class(require_id))
+    }
+
+    fn parse_strict_class(&mut self, require_id: bool) -> Class {
+        self.expect(TokenKind::Keyword(KeywordKind::Class));
+
+        let mut id = None;
+        if let TokenKind::Id(name) = self.peek_kind() {
+            self.check_identifier(self.peek());
+            self.check_strict_target(name, self.peek().span);
+            self.bump();
+            id = Some(name);
+        } else if require_id {
+            self.error_and_recover(self.unexpected());
+        }
+
+        let mut super_class = None;
+        if self.eat(TokenKind::Keyword(KeywordKind::Extends)) {
+            super_class = Some(Box::new(self.nested_expr(Self::parse_lhs_expr)));
+        }
+        let derived = super_class.is_some();
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        let mut body = Vec::new();
+        let mut has_constructor = false;
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            match self.peek_kind() {
+                TokenKind::Punc(PuncKind::SemiColon) => self.bump(),
+                _ => {
+                    let pos = self.pos();
+                    let element = self.with_in(true, |p| p.parse_class_element(derived));
+                    // A class can only have a single constructor.
+                    if let ClassElementKind::Method(ClassMethod { kind: MethodKind::Constructor, .. }) = element.kind {
+                        if has_constructor {
+                            self.push_error_at(ParseErrorKind::DuplicateConstructor, element.span);
+                        }
+                        has_constructor = true;
+                    }
+                    body.push(element);
+                    // Recover within the class body, so that the elements after a syntax error are still parsed.
+                    if self.recovering {
+                        self.synchronize(self.pos() != pos);
+                    }
+                }
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        Class { id, super_class, body }
+    }
+
+    /// Section 15.7 [ClassElement](https://tc39.es/ecma262/#prod-ClassElement), within a class that is `derived` if it
+    /// has a heritage.
+    fn parse_class_element(&mut self, derived: bool) -> ClassElement {
+        let start = self.start();
+        let kind = self.parse_class_element_kind(derived);
+        ClassElement::new(kind, self.finish(start))
+    }
+
+    fn parse_class_element_kind(&mut self, derived: bool) -> ClassElementKind {
+        let mut is_static = false;
+        let mut key = None;
+
+        if self.eat(TokenKind::Id(sym::STATIC)) {
+            if self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Left))) {
+                let body = self.with_function(false, true, false, true, false, |p| {
+                    p.with_params(false, false, Self::parse_function_body)
+                });
+                return ClassElementKind::StaticBlock(body);
+            }
+
+            // `static` is the name of the element rather than a modifier, as in `static() {}` or `static = 1`.
+            if self.at_class_element_name_end() {
+                key = Some(ClassElementNameKind::Public(PropNameKind::Id(sym::STATIC)));
+            } else {
+                is_static = true;
+            }
+        }
+
+        let mut kind = MethodKind::Method;
+        let mut is_async = false;
+        let mut is_generator = false;
+
+        let key = match key {
+            Some(key) => key,
+            None if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication))) => {
+                is_generator = true;
+                self.parse_class_element_name()
+            }
+            None => {
+                let unescaped = !self.peek().escaped;
+                let key = self.parse_class_element_name();
+                match &key {
+                    // `get`, `set` and `async` are only modifiers when followed by another element name, with no line
+                    // terminator allowed after `async`, and never when escaped. A `*` after a line terminator starts
+                    // a generator method instead, as in `get\n*gen() {}`, as accessors can not be generators.
+                    ClassElementNameKind::Public(PropNameKind::Id(name))
+                        if matches!(*name, sym::GET | sym::SET | sym::ASYNC)
+                            && unescaped
+                            && !self.at_class_element_name_end()
+                            && !(self.has_newline_before()
+                                && (*name == sym::ASYNC
+                                    || self.at(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication))))) =>
+                    {
+                        match *name {
+                            sym::GET => kind = MethodKind::Get,
+                            sym::SET => kind = MethodKind::Set,
+                            _ => {
+                                is_async = true;
+                                is_generator = self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)));
+                            }
+                        }
+                        self.parse_class_element_name()
+                    }
+                    _ => key,
+                }
+            }
+        };
+
+        if self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))) {
+            if !is_static && is_constructor(&key) {
+                // The constructor cannot be an accessor, generator or async.
+                if is_async || is_generator || kind != MethodKind::Method {
+                    self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+                } else {
+                    kind = MethodKind::Constructor;
+                }
+            }
+
+            let value = match kind {
+                MethodKind::Get | MethodKind::Set => self.parse_accessor_function(&kind),
+                MethodKind::Constructor => self.parse_constructor_function(derived),
+                MethodKind::Method => self.parse_method_function(is_async, is_generator),
+            };
+            return ClassElementKind::Method(ClassMethod { kind, key, value, is_static });
+        }
+
+        // Fields cannot have method modifiers.
+        if is_async || is_generator || kind != MethodKind::Method {
+            self.error_and_recover(self.unexpected());
+        }
+
+        // Initializers are evaluated as if in a method, so `yield` and `await` are not operators and `super.x` is allowed,
+        // but have no `arguments`.
+        let mut value = None;
+        if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Assign))) {
+            value = Some(self.with_function(false, true, false, true, false, |p| {
+                p.with_params(false, false, Self::parse_assign_expr)
+            }));
+        }
+
+        self.consume_semicolon();
+
+        ClassElementKind::Field(ClassField { key, value, is_static })
+    }
+
+    /// Section 15.7 [ClassElementName](https://tc39.es/ecma262/#prod-ClassElementName)
+    fn parse_class_element_name(&mut self) -> ClassElementNameKind {
+        if let TokenKind::PrivateId(name) = self.peek_kind() {
+            self.bump();
+            return ClassElementNameKind::Private(name);
+        }
+        ClassElementNameKind::Public(self.parse_prop_name())
+    }
+
+    /// Checks if the next token ends an element name within a class body.
+    fn at_class_element_name_end(&self) -> bool {
+        matches!(
+            self.peek_kind(),
+            TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))
+            | TokenKind::Punc(PuncKind::Op(OpKind::Assign))
+            | TokenKind::Punc(PuncKind::SemiColon)
+            | TokenKind::Punc(PuncKind::Brace(BraceKind::Right))
+            | TokenKind::Eof
+        )
+    }
+}
+
+fn is_constructor(key: &ClassElementNameKind) -> bool {
+    match key {
+        ClassElementNameKind::Public(PropNameKind::Id(name)) => *name == sym::CONSTRUCTOR,
+        ClassElementNameKind::Public(PropNameKind::Lit(LitValue::String(name))) => name.cooked == "constructor",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{BinaryExpr, Class, ClassElement, ClassElementKind, ClassElementNameKind, ClassField, ClassMethod, DeclKind, ExprKind, ExprStmt, Function, LitValue, MemberExpr, MemberPropKind, MethodKind, OperatorKind, ParenExpr, PatternKind, PrimaryExprKind, PropNameKind, ReturnStmt, Stmt, StmtKind};
+    use crate::token::KeywordKind;
+    use crate::test_utils::{assert_ast_eq, id, parse_single, IntoNode};
+    use crate::ParseErrorKind::{ArgumentsInClassInitializer, DuplicateConstructor, InvalidAccessorParams};
+    use crate::span::Span;
+    use super::Parser;
+
+    fn public(name: &str) -> ClassElementNameKind {
+        ClassElementNameKind::Public(PropNameKind::Id(name.into()))
+    }
+
+    fn function(body: Vec<Stmt>, is_async: bool, is_generator: bool) -> Function {
+        Function { id: None, params: vec![], body, is_async, is_generator }
+    }
+
+    fn method(kind: MethodKind, key: ClassElementNameKind, is_static: bool) -> ClassElement {
+        ClassElementKind::Method(ClassMethod { kind, key, value: function(vec![], false, false), is_static }).node()
+    }
+
+    #[test]
+    fn test_class_decl() {
+        let source = "class A extends B {\n\
+            constructor() {}\n\
+            static create() {}\n\
+            get value() { return this.#value; }\n\
+            set value(v) {}\n\
+            async *items() {}\n\
+            #value = 1;\n\
+            static count\n\
+            [key] = 2;\n\
+            static {}\n\
+            static() {}\n\
+            has(o) { return #value in o; }\n\
+        }";
+
+        let expected = Class {
+            id: Some("A".into()),
+            super_class: Some(Box::new(id("B"))),
+            body: vec![
+                method(MethodKind::Constructor, public("constructor"), false),
+                method(MethodKind::Method, public("create"), true),
+                ClassElementKind::Method(ClassMethod {
+                    kind: MethodKind::Get,
+                    key: public("value"),
+                    value: function(
+                        vec![StmtKind::Return(ReturnStmt {
+                            return_value: Some(Box::new(ExprKind::Member(MemberExpr {
+                                object: Box::new(ExprKind::Primary(PrimaryExprKind::This).node()),
+                                prop: MemberPropKind::Private("value".into()),
+                                optional: false,
+                            }).node())),
+                        }).node()],
+                        false,
+                        false,
+                    ),
+                    is_static: false,
+                }).node(),
+                ClassElementKind::Method(ClassMethod {
+                    kind: MethodKind::Set,
+                    key: public("value"),
+                    value: Function {
+                        id: None,
+                        params: vec![PatternKind::Id("v".into()).node()],
+                        body: vec![],
+                        is_async: false,
+                        is_generator: false,
+                    },
+                    is_static: false,
+                }).node(),
+                ClassElementKind::Method(ClassMethod {
+                    kind: MethodKind::Method,
+                    key: public("items"),
+                    value: function(vec![], true, true),
+                    is_static: false,
+                }).node(),
+                ClassElementKind::Field(ClassField {
+                    key: ClassElementNameKind::Private("value".into()),
+                    value: Some(ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(1.0))).node()),
+                    is_static: false,
+                }).node(),
+                ClassElementKind::Field(ClassField { key: public("count"), value: None, is_static: true }).node(),
+                ClassElementKind::Field(ClassField {
+                    key: ClassElementNameKind::Public(PropNameKind::Computed(Box::new(id("key")))),
+                    value: Some(ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(2.0))).node()),
+                    is_static: false,
+                }).node(),
+                ClassElementKind::StaticBlock(vec![]).node(),
+                method(MethodKind::Method, public("static"), false),
+                ClassElementKind::Method(ClassMethod {
+                    kind: MethodKind::Method,
+                    key: public("has"),
+                    value: Function {
+                        id: None,
+                        params: vec![PatternKind::Id("o".into()).node()],
+                        body: vec![StmtKind::Return(ReturnStmt {
+                            return_value: Some(Box::new(ExprKind::Binary(BinaryExpr {
+                                op: OperatorKind::Keyword(KeywordKind::In),
+                                lhs: Box::new(ExprKind::PrivateName("value".into()).node()),
+                                rhs: Box::new(id("o")),
+                            }).node())),
+                        }).node()],
+                        is_async: false,
+                        is_generator: false,
+                    },
+                    is_static: false,
+                }).node(),
+            ],
+        };
+
+        assert_ast_eq!(parse_single(source), StmtKind::Decl(DeclKind::Class(expected)).node());
+    }
+
+    #[test]
+    fn test_get_field_before_generator() {
+        // A line terminator before `*` ends a `get` field, as a getter can not be a generator.
+        let StmtKind::Decl(DeclKind::Class(class)) = parse_single("class A { get\n*gen() {} }").kind else {
+            panic!("Expected class declaration");
+        };
+        assert_ast_eq!(
+            class.body,
+            vec![
+                ClassElementKind::Field(ClassField { key: public("get"), value: None, is_static: false }).node(),
+                ClassElementKind::Method(ClassMethod {
+                    kind: MethodKind::Method,
+                    key: public("gen"),
+                    value: function(vec![], false, true),
+                    is_static: false,
+                }).node(),
+            ]
+        );
+        assert_eq!(Parser::init("class A { get *gen() {} }").parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_class_expr() {
+        assert_ast_eq!(
+            parse_single("(class {});"),
+            StmtKind::Expression(ExprStmt(ExprKind::Paren(ParenExpr {
+                expr: Box::new(ExprKind::Primary(PrimaryExprKind::ClassExpression(Class {
+                    id: None,
+                    super_class: None,
+                    body: vec![],
+                })).node()),
+            }).node())).node()
+        );
+    }
+
+    #[test]
+    fn test_invalid_class() {
+        let mut p = Parser::init("class {}");
+        assert!(!p.parse("test").errors.is_empty());
+
+        let mut p = Parser::init("class A { get x = 1; }");
+        assert!(!p.parse("test").errors.is_empty());
+
+        let mut p = Parser::init("class A { async constructor() {} }");
+        assert_eq!(p.parse("test").errors.len(), 1);
+
+        // Initializers have no `arguments`, unless within a nested function other than an arrow function.
+        for source in ["class A { static x = arguments; }", "class A { x = () => ({ arguments }); }", "class A { static { arguments; } }"] {
+            let errors = Parser::init(source).parse("test").errors;
+            assert_eq!(errors.len(), 1, "{}", source);
+            assert!(matches!(errors[0].kind, ArgumentsInClassInitializer), "{}", source);
+        }
+        let mut p = Parser::init("class A { x = function() { return arguments; }; m() { arguments; } }");
+        assert!(p.parse("test").errors.is_empty());
+
+        let errors = Parser::init("class A { constructor() {} 'constructor'() {} }").parse("test").errors;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, DuplicateConstructor));
+        assert_eq!(errors[0].span, Span::new(27, 45));
+        let mut p = Parser::init("class A { constructor() {} static constructor() {} get constructor() {} }");
+        assert_eq!(p.parse("test").errors.len(), 1);
+
+        let mut p = Parser::init("class A { get a(b) {} set c(d, e) {} static set f(...g) {} get h() {} set i([j] = k) {} }");
+        let errors = p.parse("test").errors;
+        assert_eq!(errors.iter().map(|error| error.span).collect::<Vec<_>>(), [Span::new(16, 17), Span::new(28, 32), Span::new(50, 54)]);
+        assert!(errors.iter().all(|error| matches!(error.kind, InvalidAccessorParams)));
+    }
+}
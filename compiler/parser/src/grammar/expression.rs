@@ -1,18 +1,22 @@
-use crate::ast::{CondExpr, ExprKind, PrimaryExprKind, SeqExpr, YieldExpr};
+use crate::ast::{ArrayLit, AssignExpr, AwaitExpr, BinaryExpr, CallExpr, CondExpr, CoverInitProp, Expr, ExprKind, ImportCall, KeyValueProp, LogicalExpr, MemberExpr, MemberPropKind, MetaPropKind, MethodKind, MethodProp, NewExpr, ObjectLit, OperatorKind, ParenExpr, PrimaryExprKind, Prop, PropKind, PropNameKind, RegExLit, SeqExpr, SpreadableKind, TaggedTemplateExpr, UnaryExpr, UpdateExpr, YieldExpr};
 use crate::ast::ExprKind::Sequence;
-use crate::Parser;
-use crate::token::{BraceKind, BracketKind, KeywordKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
+use crate::grammar::pattern::collect_bound_names;
+use crate::ParseErrorKind::UnexpectedToken;
+use crate::{ParseErrorKind, Parser};
+use crate::span::Span;
+use crate::symbol::{sym, Symbol};
+use crate::token::{BooleanKind, BraceKind, BracketKind, KeywordKind, LitKind, OpKind, ParenthesesKind, PuncKind, TemplateKind, TokenKind};
 
-impl Parser {
-    pub(crate) fn parse_paren_expr(&mut self) -> ExprKind {
+impl Parser<'_> {
+    pub(crate) fn parse_paren_expr(&mut self) -> Expr {
         self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
-        let expr = self.parse_expr();
+        let expr = self.with_in(true, Self::parse_expr);
         self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
         expr
     }
 
     /// Section 13 [Expression](https://tc39.es/ecma262/#sec-ecmascript-language-expressions)
-    pub(crate) fn parse_expr(&mut self) -> ExprKind {
+    pub(crate) fn parse_expr(&mut self) -> Expr {
 
         let first = self.parse_assign_expr();
 
@@ -20,149 +24,942 @@ impl Parser {
             return first;
         }
 
-        self.parse_sequence_expr(first);
-
-        todo!()
+        self.parse_sequence_expr(first)
     }
 
     /// Section 13.16 [Comma Operator](https://tc39.es/ecma262/#sec-comma-operator)
-    pub(crate) fn parse_sequence_expr(&mut self, first: ExprKind) -> ExprKind {
+    pub(crate) fn parse_sequence_expr(&mut self, first: Expr) -> Expr {
+        let start = first.span.start;
         let mut expressions = vec![first];
         while self.eat(TokenKind::Punc(PuncKind::Comma)) {
             let expr = self.parse_assign_expr();
             expressions.push(expr)
         }
-        Sequence(SeqExpr { exprs: expressions })
+        Expr::new(Sequence(SeqExpr { exprs: expressions }), self.finish(start))
+    }
+
+    /// Runs the provided expression parse function one level deeper, producing an [ExprKind::Error] when nested too
+    /// deeply.
+    pub(crate) fn nested_expr(&mut self, parse: fn(&mut Self) -> Expr) -> Expr {
+        let start = self.start();
+        self.nested(parse).unwrap_or_else(|| Expr::new(ExprKind::Error, self.finish(start)))
     }
 
     /// Section 13.15 [Assignment Operator](https://tc39.es/ecma262/#prod-AssignmentExpression)
-    pub(crate) fn parse_assign_expr(&mut self) -> ExprKind {
+    pub(crate) fn parse_assign_expr(&mut self) -> Expr {
+        // Every nested expression, such as an array element or a parenthesized expression, is an assignment expression.
+        self.nested_expr(Self::parse_unguarded_assign_expr)
+    }
+
+    fn parse_unguarded_assign_expr(&mut self) -> Expr {
+        let start = self.start();
 
         // [+Yield] YieldExpression[?In,?Await]
-        if self.at(TokenKind::Keyword(KeywordKind::Yield)) {
-            return self.parse_yield_expr();
+        if self.allow_yield && self.at_id(sym::YIELD) {
+            let expr = self.parse_yield_expr();
+            self.yield_await_exprs.push(expr.span);
+            return expr;
         }
 
-        // TODO: Arrow function
+        // Section 15.3 [ArrowFunction](https://tc39.es/ecma262/#prod-ArrowFunction)
+        if self.is_id_arrow_fn_ahead(0) {
+            let params = vec![self.parse_binding_target()];
+            let arrow = self.parse_arrow_function(params, false);
+            return Expr::new(ExprKind::Arrow(arrow), self.finish(start));
+        }
 
-        let lhs = self.parse_lhs_expr();
+        // Section 15.9 [AsyncArrowFunction](https://tc39.es/ecma262/#prod-AsyncArrowFunction)
+        if self.is_async_id_arrow_fn_ahead() {
+            self.bump();
+            // The parameter is bound within the async function, where `await` is reserved.
+            let params = vec![self.with_params(self.allow_yield, true, Self::parse_binding_target)];
+            let arrow = self.parse_arrow_function(params, true);
+            return Expr::new(ExprKind::Arrow(arrow), self.finish(start));
+        }
 
-        if self.is_assignment_op() {
+        // Arrow functions with parenthesized parameters are parsed by the primary expression at the start.
+        self.arrow_start = start;
+        let cover_inits = self.cover_inits.len();
+        let lhs = self.parse_binary_logical_expr();
+
+        if std::mem::take(&mut self.after_arrow) {
+            return lhs;
+        }
+
+        if let Some(op) = self.peek_op() && self.is_assignment_op() {
             self.bump();
-            return self.parse_assign_expr();
+
+            // Compound assignments only accept identifiers and member expressions as targets.
+            let target = match op {
+                OpKind::Assign => self.reinterpret_as_pattern(lhs),
+                _ => self.reinterpret_as_simple_target(lhs),
+            };
+            self.cover_inits.truncate(cover_inits);
+
+            let value = self.parse_assign_expr();
+            let assign = AssignExpr { op, target: Box::new(target), value: Box::new(value) };
+            return Expr::new(ExprKind::Assign(assign), self.finish(start));
         }
 
-        self.parse_conditional_expr_with_condition(lhs)
+        let expr = self.parse_conditional_expr_with_condition(lhs);
+
+        // Object and array literals may still be reinterpreted as a pattern by an enclosing expression.
+        if !matches!(expr.kind, ExprKind::Primary(PrimaryExprKind::ObjectLiteral(_) | PrimaryExprKind::ArrayLiteral(_))) {
+            self.report_cover_inits(cover_inits);
+        }
+
+        expr
     }
 
     /// Section 13.14 [Conditional Operator](https://tc39.es/ecma262/#sec-conditional-operator)
-    pub(crate) fn parse_conditional_expr_with_condition(&mut self, cond: ExprKind) -> ExprKind {
-        if !self.eat(TokenKind::Punc(PuncKind::Question)) {
+    pub(crate) fn parse_conditional_expr_with_condition(&mut self, cond: Expr) -> Expr {
+        if self.peek_kind() != TokenKind::Punc(PuncKind::Question) {
             return cond;
         }
+        self.bump();
 
-        let consequent = Box::new(self.parse_assign_expr());
+        let consequent = Box::new(self.with_in(true, Self::parse_assign_expr));
 
         self.expect(TokenKind::Punc(PuncKind::Colon));
 
         let alternate =  Box::new(self.parse_assign_expr());
 
-        ExprKind::Conditional(CondExpr{ cond: Box::new(cond), consequent, alternate, })
+        let start = cond.span.start;
+        Expr::new(ExprKind::Conditional(CondExpr{ cond: Box::new(cond), consequent, alternate, }), self.finish(start))
     }
 
     /// Section 13.13 [Binary Logical Operator](https://tc39.es/ecma262/#sec-binary-logical-operators)
-    pub(crate) fn parse_binary_logical_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_binary_logical_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_logical_and_expr();
+
+        while let Some(op @ (OpKind::Or | OpKind::NullishCoalescing)) = self.peek_op() {
+            // `??` cannot be combined with `&&` or `||` without parentheses.
+            let mixed = match op {
+                OpKind::NullishCoalescing => is_logical(&lhs, &[OpKind::And, OpKind::Or]),
+                _ => is_logical(&lhs, &[OpKind::NullishCoalescing]),
+            };
+            if mixed {
+                self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+            }
+
+            self.bump();
+            let rhs = self.parse_logical_and_expr();
+
+            if op == OpKind::NullishCoalescing && is_logical(&rhs, &[OpKind::And]) {
+                self.push_error(UnexpectedToken(TokenKind::Punc(PuncKind::Op(OpKind::And)), Vec::new()));
+            }
+
+            lhs = logical(op, lhs, rhs);
+        }
+
+        lhs
+    }
+
+    fn parse_logical_and_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_binary_bitwise_expr();
+        while self.eat_op(OpKind::And) {
+            let rhs = self.parse_binary_bitwise_expr();
+            lhs = logical(OpKind::And, lhs, rhs);
+        }
+        lhs
     }
 
     /// Section 13.12 [Binary Bitwise Operator](https://tc39.es/ecma262/#sec-binary-bitwise-operators)
-    pub(crate) fn parse_binary_bitwise_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_binary_bitwise_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_bitwise_xor_expr();
+        while let Some(op @ OpKind::BitOr) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_bitwise_xor_expr());
+        }
+        lhs
+    }
+
+    fn parse_bitwise_xor_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_bitwise_and_expr();
+        while let Some(op @ OpKind::BitXor) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_bitwise_and_expr());
+        }
+        lhs
+    }
+
+    fn parse_bitwise_and_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_equality_expr();
+        while let Some(op @ OpKind::BitAnd) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_equality_expr());
+        }
+        lhs
     }
 
     /// Section 13.11 [Equality Operator](https://tc39.es/ecma262/#sec-equality-operators)
-    pub(crate) fn parse_equality_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_equality_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_relation_expr();
+        while let Some(op @ (OpKind::Equal | OpKind::NotEqual | OpKind::StrictEquality | OpKind::StrictInequality)) =
+            self.peek_op()
+        {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_relation_expr());
+        }
+        lhs
     }
 
     /// Section 13.10 [Relational Operator](https://tc39.es/ecma262/#sec-relational-operators)
-    pub(crate) fn parse_relation_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_relation_expr(&mut self) -> Expr {
+        let mut lhs = match self.peek_kind() {
+            // Private names can only appear on the left of `in`, as in `#a in b`.
+            TokenKind::PrivateId(name) if self.peek_nth_kind(1) == TokenKind::Keyword(KeywordKind::In) => {
+                let start = self.start();
+                self.bump();
+                Expr::new(ExprKind::PrivateName(name), self.finish(start))
+            }
+            _ => self.parse_bitwise_shift_expr(),
+        };
+        loop {
+            let op = match self.peek_kind() {
+                TokenKind::Punc(PuncKind::Op(
+                    op @ (OpKind::LessThan | OpKind::GreaterThan | OpKind::LessThanEqual | OpKind::GreaterThanEqual),
+                )) => OperatorKind::Punc(op),
+                TokenKind::Keyword(KeywordKind::In) if self.allow_in => OperatorKind::Keyword(KeywordKind::In),
+                TokenKind::Keyword(KeywordKind::InstanceOf) => OperatorKind::Keyword(KeywordKind::InstanceOf),
+                _ => return lhs,
+            };
+            self.bump();
+            lhs = binary(op, lhs, self.parse_bitwise_shift_expr());
+        }
     }
 
     /// Section 13.9 [Bitwise Shift Operator](https://tc39.es/ecma262/#sec-bitwise-shift-operators)
-    pub(crate) fn parse_bitwise_shift_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_bitwise_shift_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_additive_expr();
+        while let Some(op @ (OpKind::LeftShift | OpKind::RightShift | OpKind::ZeroFillRightShift)) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_additive_expr());
+        }
+        lhs
     }
 
     /// Section 13.8 [Additive Operator](https://tc39.es/ecma262/#sec-additive-operators)
-    pub(crate) fn parse_additive_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_additive_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_multiplicative_expr();
+        while let Some(op @ (OpKind::Addition | OpKind::Subtraction)) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_multiplicative_expr());
+        }
+        lhs
     }
 
     /// Section 13.7 [Multiplicative Operator](https://tc39.es/ecma262/#sec-multiplicative-operators)
-    pub(crate) fn parse_multiplicative_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_multiplicative_expr(&mut self) -> Expr {
+        let mut lhs = self.parse_exponent_expr();
+        while let Some(op @ (OpKind::Multiplication | OpKind::Division | OpKind::Mod)) = self.peek_op() {
+            self.bump();
+            lhs = binary(OperatorKind::Punc(op), lhs, self.parse_exponent_expr());
+        }
+        lhs
     }
 
     /// Section 13.6 [Exponentiation Operator](https://tc39.es/ecma262/#sec-exp-operator)
-    pub(crate) fn parse_exponent_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_exponent_expr(&mut self) -> Expr {
+        if self.is_unary_op() {
+            let unary = self.parse_unary_expr();
+
+            // A unary expression cannot be the base of an exponent without parentheses.
+            if self.peek_op() == Some(OpKind::Exponential) {
+                self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+                self.bump();
+                return binary(OperatorKind::Punc(OpKind::Exponential), unary, self.nested_expr(Self::parse_exponent_expr));
+            }
+
+            return unary;
+        }
+
+        let base = self.parse_update_expr();
+
+        // Exponentiation is right associative, so the exponent recurses rather than loops.
+        if self.eat_op(OpKind::Exponential) {
+            return binary(OperatorKind::Punc(OpKind::Exponential), base, self.nested_expr(Self::parse_exponent_expr));
+        }
+
+        base
     }
 
     /// Section 13.5 [Unary Operator](https://tc39.es/ecma262/#sec-unary-operators)
-    pub(crate) fn parse_unary_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_unary_expr(&mut self) -> Expr {
+        let start = self.start();
+        let op = match self.peek_kind() {
+            // [+Await] AwaitExpression[?Yield]
+            TokenKind::Id(_) if self.allow_await && self.at_id(sym::AWAIT) => {
+                self.bump();
+                let arg = Box::new(self.nested_expr(Self::parse_unary_expr));
+                self.yield_await_exprs.push(self.finish(start));
+                return Expr::new(ExprKind::Await(AwaitExpr { arg }), self.finish(start));
+            }
+            TokenKind::Keyword(k @ (KeywordKind::Delete | KeywordKind::Void | KeywordKind::TypeOf)) => {
+                OperatorKind::Keyword(k)
+            }
+            TokenKind::Punc(PuncKind::Op(
+                op @ (OpKind::Addition | OpKind::Subtraction | OpKind::BitNot | OpKind::Not),
+            )) => OperatorKind::Punc(op),
+            _ => return self.parse_update_expr(),
+        };
+
+        self.bump();
+
+        let arg = Box::new(self.nested_expr(Self::parse_unary_expr));
+
+        // In strict mode code, an identifier reference can not be deleted, even when parenthesized as in `delete (a)`.
+        if self.strict && op == OperatorKind::Keyword(KeywordKind::Delete) {
+            let mut target = &*arg;
+            while let ExprKind::Paren(ParenExpr { expr }) = &target.kind {
+                target = expr;
+            }
+            if matches!(target.kind, ExprKind::Primary(PrimaryExprKind::Id(_))) {
+                self.push_error_at(ParseErrorKind::StrictDelete, target.span);
+            }
+        }
+
+        Expr::new(ExprKind::Unary(UnaryExpr { op, arg }), self.finish(start))
     }
 
     /// Section 13.4 [Update Operator](https://tc39.es/ecma262/#sec-update-expressions)
-    pub(crate) fn parse_update_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_update_expr(&mut self) -> Expr {
+        let start = self.start();
+        if let Some(op @ (OpKind::Increment | OpKind::Decrement)) = self.peek_op() {
+            self.bump();
+            let arg = Box::new(self.nested_expr(Self::parse_unary_expr));
+            self.check_update_target(&arg);
+            return Expr::new(ExprKind::Update(UpdateExpr { op, prefix: true, arg }), self.finish(start));
+        }
+
+        let arg = self.parse_lhs_expr();
+
+        // A line terminator before `++` or `--` makes it the prefix operator of the next statement instead.
+        if let Some(op @ (OpKind::Increment | OpKind::Decrement)) = self.peek_op() && !self.has_newline_before() {
+            self.check_update_target(&arg);
+            self.bump();
+            return Expr::new(ExprKind::Update(UpdateExpr { op, prefix: false, arg: Box::new(arg) }), self.finish(start));
+        }
+
+        arg
     }
 
     /// Section 13.3 [Left-Hand-Side Expressions](https://tc39.es/ecma262/#sec-left-hand-side-expressions)
-    pub(crate) fn parse_lhs_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_lhs_expr(&mut self) -> Expr {
+        let expr = match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::New) => self.nested_expr(Self::parse_new_expr),
+            // Import calls are call expressions, so cannot be the callee of `new`.
+            TokenKind::Keyword(KeywordKind::Import) if self.peek_nth_kind(1) == TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) => {
+                self.parse_import_call()
+            }
+            _ => self.parse_member_base_expr(),
+        };
+
+        self.parse_member_tail(expr, true)
+    }
+
+    /// Parses the start of a member expression chain, before any property access or arguments.
+    fn parse_member_base_expr(&mut self) -> Expr {
+        match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::Super) => self.parse_super_expr(),
+            TokenKind::Keyword(KeywordKind::Import) => self.parse_import_meta_expr(),
+            _ => self.parse_primary_expr(),
+        }
+    }
+
+    /// Parses any property accesses, calls and optional chains that follow the provided expression. Calls are not
+    /// allowed within the callee of a `new` expression, as the first argument list belongs to the `new` itself.
+    fn parse_member_tail(&mut self, object: Expr, allow_call: bool) -> Expr {
+        let start = object.span.start;
+        let mut expr = object;
+        let mut optional = false;
+        loop {
+            let kind = match self.peek_kind() {
+                TokenKind::Punc(PuncKind::Dot) => {
+                    self.bump();
+                    let prop = self.parse_member_name();
+                    member(expr, prop, false)
+                }
+                TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                    let prop = self.parse_computed_member_prop();
+                    member(expr, prop, false)
+                }
+                TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) if allow_call => {
+                    let args = self.parse_args();
+                    ExprKind::Call(CallExpr { callee: Box::new(expr), args, optional: false })
+                }
+                TokenKind::Punc(PuncKind::Op(OpKind::OptionalChain)) => {
+                    // Optional chains cannot be used in the callee of a `new` expression.
+                    if !allow_call {
+                        self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+                    }
+                    self.bump();
+                    optional = true;
+                    match self.peek_kind() {
+                        TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) => {
+                            let args = self.parse_args();
+                            ExprKind::Call(CallExpr { callee: Box::new(expr), args, optional: true })
+                        }
+                        TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                            let prop = self.parse_computed_member_prop();
+                            member(expr, prop, true)
+                        }
+                        _ => {
+                            let prop = self.parse_member_name();
+                            member(expr, prop, true)
+                        }
+                    }
+                }
+                // Section 13.3.11 [Tagged Templates](https://tc39.es/ecma262/#sec-tagged-templates), which cannot be
+                // part of an optional chain.
+                TokenKind::Template(TemplateKind::NoSubstitution | TemplateKind::Head) => {
+                    if optional {
+                        self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+                    }
+                    let quasi = self.parse_template_lit(true);
+                    ExprKind::TaggedTemplate(TaggedTemplateExpr { tag: Box::new(expr), quasi })
+                }
+                _ => return expr,
+            };
+            expr = Expr::new(kind, self.finish(start));
+        }
+    }
+
+    /// Parses the property name following `.` or `?.`, which may be a private name.
+    fn parse_member_name(&mut self) -> MemberPropKind {
+        if let TokenKind::PrivateId(name) = self.peek_kind() {
+            self.bump();
+            return MemberPropKind::Private(name);
+        }
+        MemberPropKind::Id(self.parse_identifier_name())
+    }
+
+    fn parse_computed_member_prop(&mut self) -> MemberPropKind {
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)));
+        let prop = self.with_in(true, Self::parse_expr);
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)));
+        MemberPropKind::Computed(Box::new(prop))
+    }
+
+    /// Section 13.3.5 [The new Operator](https://tc39.es/ecma262/#sec-new-operator)
+    pub(crate) fn parse_new_expr(&mut self) -> Expr {
+        let start = self.start();
+        self.expect(TokenKind::Keyword(KeywordKind::New));
+
+        if self.eat(TokenKind::Punc(PuncKind::Dot)) {
+            self.expect(TokenKind::Id(sym::TARGET));
+            let span = self.finish(start);
+            // `new.target` is only allowed within functions other than arrow functions, and class bodies.
+            if !self.allow_new_target {
+                self.push_error_at(UnexpectedToken(TokenKind::Keyword(KeywordKind::New), Vec::new()), span);
+            }
+            return Expr::new(ExprKind::MetaProp(MetaPropKind::NewTarget), span);
+        }
+
+        let callee = match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::New) => self.nested_expr(Self::parse_new_expr),
+            _ => self.parse_member_base_expr(),
+        };
+
+        let callee = self.parse_member_tail(callee, false);
+
+        let mut args = None;
+        if self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))) {
+            args = Some(self.parse_args());
+        }
+
+        Expr::new(ExprKind::New(NewExpr { callee: Box::new(callee), args }), self.finish(start))
+    }
+
+    /// Section 13.3.7 [The super Keyword](https://tc39.es/ecma262/#sec-super-keyword)
+    pub(crate) fn parse_super_expr(&mut self) -> Expr {
+        let start = self.start();
+        self.expect(TokenKind::Keyword(KeywordKind::Super));
+
+        // `super` must be immediately followed by a property access, only allowed within methods, or arguments, only
+        // allowed within the constructor of a derived class.
+        let span = self.finish(start);
+        match self.peek_kind() {
+            TokenKind::Punc(PuncKind::Dot) | TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                if !self.allow_super_property {
+                    self.push_error_at(UnexpectedToken(TokenKind::Keyword(KeywordKind::Super), Vec::new()), span);
+                }
+            }
+            TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) => {
+                if !self.allow_super_call {
+                    self.push_error_at(UnexpectedToken(TokenKind::Keyword(KeywordKind::Super), Vec::new()), span);
+                }
+            }
+            kind => self.push_error(UnexpectedToken(kind, Vec::new())),
+        }
+
+        Expr::new(ExprKind::Super, span)
+    }
+
+    /// Section 13.3.12 [Meta Properties](https://tc39.es/ecma262/#sec-meta-properties)
+    pub(crate) fn parse_import_meta_expr(&mut self) -> Expr {
+        let start = self.start();
+        self.expect(TokenKind::Keyword(KeywordKind::Import));
+        self.expect(TokenKind::Punc(PuncKind::Dot));
+        self.expect(TokenKind::Id(sym::META));
+        let span = self.finish(start);
+        // `import.meta` is only allowed in modules.
+        if !self.module {
+            self.push_error_at(UnexpectedToken(TokenKind::Keyword(KeywordKind::Import), Vec::new()), span);
+        }
+        Expr::new(ExprKind::MetaProp(MetaPropKind::ImportMeta), span)
+    }
+
+    /// Section 13.3.10 [Import Calls](https://tc39.es/ecma262/#sec-import-calls)
+    pub(crate) fn parse_import_call(&mut self) -> Expr {
+        let start = self.start();
+        self.expect(TokenKind::Keyword(KeywordKind::Import));
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
+
+        let source = Box::new(self.with_in(true, Self::parse_assign_expr));
+
+        let mut options = None;
+        if self.eat(TokenKind::Punc(PuncKind::Comma)) && !self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) {
+            options = Some(Box::new(self.with_in(true, Self::parse_assign_expr)));
+            self.eat(TokenKind::Punc(PuncKind::Comma));
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+
+        Expr::new(ExprKind::ImportCall(ImportCall { source, options }), self.finish(start))
+    }
+
+    /// Section 13.3.8 [Argument Lists](https://tc39.es/ecma262/#sec-argument-lists)
+    pub(crate) fn parse_args(&mut self) -> Vec<SpreadableKind> {
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
+
+        let mut args = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) && !self.is_end() {
+            args.push(self.with_in(true, Self::parse_spreadable_expr));
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+
+        args
+    }
+
+    fn parse_spreadable_expr(&mut self) -> SpreadableKind {
+        if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+            return SpreadableKind::Spread(self.parse_assign_expr());
+        }
+        SpreadableKind::Expr(self.parse_assign_expr())
     }
 
     /// Section 13.2 [Primary Expressions](https://tc39.es/ecma262/#sec-primary-expression)
-    pub(crate) fn parse_primary_expr(&mut self) -> ExprKind {
-        todo!()
+    pub(crate) fn parse_primary_expr(&mut self) -> Expr {
+        let start = self.start();
+        let kind = match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::This) => return self.parse_this_expr(),
+            TokenKind::Keyword(KeywordKind::Function) => ExprKind::Primary(self.parse_function_expr()),
+            TokenKind::Keyword(KeywordKind::Class) => {
+                ExprKind::Primary(PrimaryExprKind::ClassExpression(self.parse_class_expr()))
+            }
+            TokenKind::Id(_) if self.is_async_function_ahead() => ExprKind::Primary(self.parse_function_expr()),
+            TokenKind::Id(_) if self.is_async_call_ahead() && start == self.arrow_start => {
+                self.bump();
+                self.parse_async_arrow_or_call(start)
+            }
+            TokenKind::Id(id) => {
+                self.check_identifier(self.peek());
+                self.check_arguments(id, self.peek().span);
+                self.bump();
+                ExprKind::Primary(PrimaryExprKind::Id(id))
+            }
+            TokenKind::Lit(lit) => {
+                let span = self.peek().span;
+                let value = self.tokens.borrow().lit_value(lit, span);
+                self.bump();
+                ExprKind::Primary(PrimaryExprKind::Lit(value))
+            }
+            TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) => self.parse_paren_or_arrow(start),
+            TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                ExprKind::Primary(PrimaryExprKind::ArrayLiteral(self.parse_array_lit()))
+            }
+            TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => {
+                ExprKind::Primary(PrimaryExprKind::ObjectLiteral(self.parse_object_lit()))
+            }
+            TokenKind::Template(TemplateKind::NoSubstitution | TemplateKind::Head) => {
+                ExprKind::Primary(PrimaryExprKind::TemplateLiteral(self.parse_template_lit(false)))
+            }
+            TokenKind::Punc(PuncKind::Op(OpKind::Division | OpKind::DivisionAssign)) => match self.parse_regex_lit() {
+                Some(regex) => ExprKind::Primary(PrimaryExprKind::RegExLiteral(regex)),
+                None => ExprKind::Error,
+            },
+            _ => {
+                // The tokens checked for here are the alternatives of a primary expression, rather than useful
+                // suggestions.
+                self.expected.clear();
+                self.error_and_recover(self.unexpected());
+                ExprKind::Error
+            }
+        };
+        Expr::new(kind, self.finish(start))
+    }
+
+    /// Section 13.2 [CoverParenthesizedExpressionAndArrowParameterList](https://tc39.es/ecma262/#prod-CoverParenthesizedExpressionAndArrowParameterList)
+    ///
+    /// Parses a parenthesized expression, which is reinterpreted as the parameters of an arrow function if it starts
+    /// an assignment expression and is followed by `=>`.
+    fn parse_paren_or_arrow(&mut self, start: usize) -> ExprKind {
+        let can_be_arrow = start == self.arrow_start;
+        let cover_inits = self.cover_inits.len();
+        let yield_await_exprs = self.yield_await_exprs.len();
+        let cover = self.parse_paren_cover();
+
+        if can_be_arrow && self.peek_kind() == TokenKind::Punc(PuncKind::Op(OpKind::Arrow)) {
+            self.report_yield_await_exprs(yield_await_exprs);
+            return self.finish_arrow_cover(cover, cover_inits, false);
+        }
+
+        // Only arrow parameters may be empty, end with a comma or contain a rest element.
+        if let Some(spread) = cover.spread {
+            self.push_error_at(UnexpectedToken(TokenKind::Punc(PuncKind::Op(OpKind::Spread)), Vec::new()), spread);
+        } else if cover.items.is_empty() || cover.trailing_comma.is_some() {
+            self.push_error_at(UnexpectedToken(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)), Vec::new()), cover.end);
+        }
+
+        let mut expressions: Vec<Expr> = cover.items.into_iter().map(|item| match item {
+            SpreadableKind::Expr(expr) | SpreadableKind::Spread(expr) => expr,
+        }).collect();
+        let expr = match expressions.len() {
+            0 => Expr::new(ExprKind::Error, cover.end),
+            1 => expressions.remove(0),
+            _ => {
+                let span = expressions[0].span.to(expressions[expressions.len() - 1].span);
+                Expr::new(Sequence(SeqExpr { exprs: expressions }), span)
+            }
+        };
+        ExprKind::Paren(ParenExpr { expr: Box::new(expr) })
+    }
+
+    /// Parses the arguments of a call to `async`, which are reinterpreted as the parameters of an async arrow function
+    /// if followed by `=>`. The `async` is already consumed.
+    fn parse_async_arrow_or_call(&mut self, start: usize) -> ExprKind {
+        let callee = Expr::new(ExprKind::Primary(PrimaryExprKind::Id(sym::ASYNC)), self.finish(start));
+        let cover_inits = self.cover_inits.len();
+        let yield_await_exprs = self.yield_await_exprs.len();
+        let cover = self.parse_paren_cover();
+
+        if self.peek_kind() == TokenKind::Punc(PuncKind::Op(OpKind::Arrow)) {
+            self.report_yield_await_exprs(yield_await_exprs);
+            return self.finish_arrow_cover(cover, cover_inits, true);
+        }
+
+        ExprKind::Call(CallExpr { callee: Box::new(callee), args: cover.items, optional: false })
+    }
+
+    /// Parses the contents of parentheses, allowing everything either an expression or arrow parameters allow.
+    fn parse_paren_cover(&mut self) -> ParenCover {
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
+
+        let mut cover = ParenCover { items: Vec::new(), spread: None, trailing_comma: None, end: Span::default() };
+        while !self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) && !self.is_end() {
+            if self.at(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+                cover.spread = cover.spread.or(Some(self.peek().span));
+            }
+            cover.items.push(self.with_in(true, Self::parse_spreadable_expr));
+
+            let comma = self.peek().span;
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+            if self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) {
+                cover.trailing_comma = Some(comma);
+            }
+        }
+
+        cover.end = self.peek().span;
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+
+        cover
+    }
+
+    /// Reinterprets the contents of parentheses as arrow parameters, and parses the rest of the arrow function.
+    fn finish_arrow_cover(&mut self, cover: ParenCover, cover_inits: usize, is_async: bool) -> ExprKind {
+        // A comma cannot follow the rest parameter.
+        if let Some(comma) = cover.trailing_comma && matches!(cover.items.last(), Some(SpreadableKind::Spread(_))) {
+            self.push_error_at(UnexpectedToken(TokenKind::Punc(PuncKind::Comma), Vec::new()), comma);
+        }
+
+        let params = self.reinterpret_as_params(cover.items);
+        self.cover_inits.truncate(cover_inits);
+
+        // The parameters of an async arrow function are parsed as the arguments of a call, where `await` may be an
+        // identifier outside of modules and async functions, but are bound within the async function.
+        if is_async && !self.module && !self.allow_await {
+            let mut names = Vec::new();
+            for param in &params {
+                collect_bound_names(param, &mut names);
+            }
+            for (name, span) in names {
+                if name == sym::AWAIT {
+                    self.push_error_at(ParseErrorKind::ReservedWord(name.to_string()), span);
+                }
+            }
+        }
+
+        let arrow = self.parse_arrow_function(params, is_async);
+        self.after_arrow = true;
+        ExprKind::Arrow(arrow)
+    }
+
+    /// Section 13.2.7 [Regular Expression Literals](https://tc39.es/ecma262/#sec-primary-expression-regular-expression-literals)
+    ///
+    /// Tokens are lexed as if a `/` is always division. Where an expression is expected instead, the `/` or `/=` token
+    /// is lexed again as a regular expression literal, along with any tokens already peeked at past it.
+    pub(crate) fn parse_regex_lit(&mut self) -> Option<RegExLit> {
+        match self.tokens.get_mut().rescan_regex() {
+            Ok(token) => {
+                if token.kind != TokenKind::Lit(LitKind::RegEx) {
+                    return None;
+                }
+                let regex = self.tokens.borrow().regex_lit(token.span);
+                self.bump();
+                Some(regex)
+            }
+            Err(err) => {
+                self.error_and_recover_at(ParseErrorKind::Lexer(err.error), err.span);
+                self.skip_lexer_errors();
+                None
+            }
+        }
+    }
+
+    /// Section 13.2.4 [Array Initializer](https://tc39.es/ecma262/#sec-array-initializer)
+    pub(crate) fn parse_array_lit(&mut self) -> ArrayLit {
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)));
+
+        let mut elements = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right))) && !self.is_end() {
+            if self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                elements.push(None);
+                continue;
+            }
+
+            let element = self.with_in(true, Self::parse_spreadable_expr);
+            let is_spread = matches!(element, SpreadableKind::Spread(_));
+            elements.push(Some(element));
+
+            let comma = self.peek().span;
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+            if is_spread && self.at(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right))) {
+                self.rest_commas.push(comma);
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)));
+
+        ArrayLit { elements }
+    }
+
+    /// Section 13.2.5 [Object Initializer](https://tc39.es/ecma262/#sec-object-initializer)
+    pub(crate) fn parse_object_lit(&mut self) -> ObjectLit {
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        let mut props = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            let prop = self.with_in(true, Self::parse_prop_def);
+            let is_spread = matches!(prop.kind, PropKind::Spread(_));
+            props.push(prop);
+
+            let comma = self.peek().span;
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+            if is_spread && self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) {
+                self.rest_commas.push(comma);
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        ObjectLit { props }
+    }
+
+    fn parse_prop_def(&mut self) -> Prop {
+        let start = self.start();
+        let kind = self.parse_prop_def_kind();
+        Prop::new(kind, self.finish(start))
     }
 
-    pub(crate) fn parse_yield_expr(&mut self) -> ExprKind {
-        self.expect(TokenKind::Keyword(KeywordKind::Yield));
+    fn parse_prop_def_kind(&mut self) -> PropKind {
+        if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+            return PropKind::Spread(self.parse_assign_expr());
+        }
+
+        if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication))) {
+            let key = self.parse_prop_name();
+            return PropKind::Method(MethodProp { kind: MethodKind::Method, key, value: self.parse_method_function(false, true) });
+        }
+
+        let token = self.peek();
+        let key = self.parse_prop_name();
+
+        // `get`, `set` and `async` are only modifiers when followed by another property name, and never when escaped.
+        if let PropNameKind::Id(name) = &key
+            && !token.escaped
+            && !self.at_prop_name_end()
+            && !(*name == sym::ASYNC && self.has_newline_before())
+        {
+            match *name {
+                sym::GET | sym::SET => {
+                    let kind = if *name == sym::GET { MethodKind::Get } else { MethodKind::Set };
+                    let key = self.parse_prop_name();
+                    let value = self.parse_accessor_function(&kind);
+                    return PropKind::Method(MethodProp { kind, key, value });
+                }
+                sym::ASYNC => {
+                    let is_generator = self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)));
+                    let key = self.parse_prop_name();
+                    let value = self.parse_method_function(true, is_generator);
+                    return PropKind::Method(MethodProp { kind: MethodKind::Method, key, value });
+                }
+                _ => {}
+            }
+        }
+
+        if self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))) {
+            let value = self.parse_method_function(false, false);
+            return PropKind::Method(MethodProp { kind: MethodKind::Method, key, value });
+        }
+
+        if self.eat(TokenKind::Punc(PuncKind::Colon)) {
+            return PropKind::KeyValue(KeyValueProp { key, value: self.parse_assign_expr() });
+        }
+
+        // Shorthand properties are identifier references.
+        if let PropNameKind::Id(name) = key {
+            self.check_shorthand(token);
+            self.check_arguments(name, token.span);
+        }
+
+        match key {
+            PropNameKind::Id(name) if self.at_prop_name_end() => PropKind::Shorthand(name),
+            PropNameKind::Id(id) if self.at(TokenKind::Punc(PuncKind::Op(OpKind::Assign))) => {
+                self.cover_inits.push(self.peek().span);
+                self.bump();
+                let init = Box::new(self.parse_assign_expr());
+                PropKind::CoverInit(CoverInitProp { id, init })
+            }
+            _ => {
+                self.error_and_recover(self.unexpected());
+                PropKind::Shorthand(sym::EMPTY)
+            }
+        }
+    }
+
+    /// Checks if the next token ends a property name within an object literal.
+    fn at_prop_name_end(&self) -> bool {
+        matches!(
+            self.peek_kind(),
+            TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))
+            | TokenKind::Punc(PuncKind::Colon)
+            | TokenKind::Punc(PuncKind::Comma)
+            | TokenKind::Punc(PuncKind::Brace(BraceKind::Right))
+        )
+    }
+
+    /// Section 13.2.5 [PropertyName](https://tc39.es/ecma262/#prod-PropertyName)
+    pub(crate) fn parse_prop_name(&mut self) -> PropNameKind {
+        match self.peek_kind() {
+            TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                self.bump();
+                let expr = self.with_in(true, Self::parse_assign_expr);
+                self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)));
+                PropNameKind::Computed(Box::new(expr))
+            }
+            TokenKind::Lit(LitKind::Bool(_) | LitKind::Null) => PropNameKind::Id(self.parse_identifier_name()),
+            TokenKind::Lit(lit) => {
+                let span = self.peek().span;
+                let value = self.tokens.borrow().lit_value(lit, span);
+                self.bump();
+                PropNameKind::Lit(value)
+            }
+            _ => PropNameKind::Id(self.parse_identifier_name()),
+        }
+    }
+
+    /// Section 12.7 [IdentifierName](https://tc39.es/ecma262/#prod-IdentifierName)
+    pub(crate) fn parse_identifier_name(&mut self) -> Symbol {
+        let name = match self.peek_kind() {
+            TokenKind::Id(id) => id,
+            TokenKind::Lit(LitKind::Bool(BooleanKind::True)) => sym::TRUE,
+            TokenKind::Lit(LitKind::Bool(BooleanKind::False)) => sym::FALSE,
+            TokenKind::Lit(LitKind::Null) => sym::NULL,
+            TokenKind::Keyword(keyword) => keyword.symbol(),
+            _ => {
+                self.error_and_recover(self.unexpected());
+                return sym::EMPTY;
+            }
+        };
+        self.bump();
+        name
+    }
+
+    pub(crate) fn parse_yield_expr(&mut self) -> Expr {
+        let start = self.start();
+        self.expect(TokenKind::Id(sym::YIELD));
+        if self.has_newline_before() {
+            return Expr::new(ExprKind::Yield(YieldExpr{ delegate: false, arg: None }), self.finish(start));
+        }
+
         let mut delegate = false;
         if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication))) { // Might want to generalize lexer to star
             delegate = true;
         }
 
-        let not_assign_expr = matches!(self.curr_token.kind,
+        let not_assign_expr = !delegate && matches!(self.peek_kind(),
                     TokenKind::Punc(PuncKind::SemiColon)
                     | TokenKind::Eof
-                    | TokenKind::Punc(PuncKind::Bracket(BracketKind::Left))
-                    | TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))
-                    | TokenKind::Punc(PuncKind::Brace(BraceKind::Left))
+                    | TokenKind::Punc(PuncKind::Bracket(BracketKind::Right))
+                    | TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))
+                    | TokenKind::Punc(PuncKind::Brace(BraceKind::Right))
                     | TokenKind::Punc(PuncKind::Colon)
                     | TokenKind::Punc(PuncKind::Comma)
+                    | TokenKind::Template(TemplateKind::Middle | TemplateKind::Tail)
             );
 
         if not_assign_expr {
-            return ExprKind::Yield(YieldExpr{ delegate, arg: None });
+            return Expr::new(ExprKind::Yield(YieldExpr{ delegate, arg: None }), self.finish(start));
         }
 
-        ExprKind::Yield(YieldExpr{ delegate, arg: Some(Box::new(self.parse_assign_expr())) })
+        let arg = Some(Box::new(self.parse_assign_expr()));
+        Expr::new(ExprKind::Yield(YieldExpr{ delegate, arg }), self.finish(start))
     }
 
-    pub(crate) fn parse_this_expr(&mut self) -> ExprKind {
+    pub(crate) fn parse_this_expr(&mut self) -> Expr {
+        let start = self.start();
         self.expect(TokenKind::Keyword(KeywordKind::This));
-        ExprKind::Primary(PrimaryExprKind::This)
+        Expr::new(ExprKind::Primary(PrimaryExprKind::This), self.finish(start))
+    }
+
+    pub(crate) fn is_unary_op(&self) -> bool {
+        (self.allow_await && self.at_id(sym::AWAIT)) || matches!(
+            self.peek_kind(),
+            TokenKind::Keyword(KeywordKind::Delete)
+            | TokenKind::Keyword(KeywordKind::Void)
+            | TokenKind::Keyword(KeywordKind::TypeOf)
+            | TokenKind::Punc(PuncKind::Op(OpKind::Addition))
+            | TokenKind::Punc(PuncKind::Op(OpKind::Subtraction))
+            | TokenKind::Punc(PuncKind::Op(OpKind::BitNot))
+            | TokenKind::Punc(PuncKind::Op(OpKind::Not))
+        )
     }
 
     pub(crate) fn is_assignment_op(&self) -> bool {
         matches!(
             self.peek_kind(),
-            TokenKind::Punc(PuncKind::Op(OpKind::Equal))
+            TokenKind::Punc(PuncKind::Op(OpKind::Assign))
             | TokenKind::Punc(PuncKind::Op(OpKind::AdditonAssign))
             | TokenKind::Punc(PuncKind::Op(OpKind::SubtractionAssign))
             | TokenKind::Punc(PuncKind::Op(OpKind::MultiplicationAssign))
@@ -182,28 +979,492 @@ impl Parser {
     }
 }
 
+/// The contents of parentheses, which are either a parenthesized expression or the parameters of an arrow function
+/// depending on whether `=>` follows.
+struct ParenCover {
+    items: Vec<SpreadableKind>,
+    /// The first `...`, which only arrow parameters allow.
+    spread: Option<Span>,
+    /// A comma directly before the closing parenthesis, which only arrow parameters allow.
+    trailing_comma: Option<Span>,
+    /// The closing parenthesis.
+    end: Span,
+}
+
+fn member(object: Expr, prop: MemberPropKind, optional: bool) -> ExprKind {
+    ExprKind::Member(MemberExpr { object: Box::new(object), prop, optional })
+}
+
+fn binary(op: OperatorKind, lhs: Expr, rhs: Expr) -> Expr {
+    let span = lhs.span.to(rhs.span);
+    Expr::new(ExprKind::Binary(BinaryExpr { op, lhs: Box::new(lhs), rhs: Box::new(rhs) }), span)
+}
+
+fn logical(op: OpKind, lhs: Expr, rhs: Expr) -> Expr {
+    let span = lhs.span.to(rhs.span);
+    Expr::new(ExprKind::Logical(LogicalExpr { op, lhs: Box::new(lhs), rhs: Box::new(rhs) }), span)
+}
+
+/// Checks if the expression is an unparenthesized logical expression using one of the provided operators.
+fn is_logical(expr: &Expr, ops: &[OpKind]) -> bool {
+    matches!(&expr.kind, ExprKind::Logical(logical) if ops.contains(&logical.op))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::ast::{ExprKind, ExprStmt, StmtKind, YieldExpr};
+    use crate::ast::{ArrayLit, AssignExpr, BinaryExpr, CallExpr, ClassElementKind, ClassMethod, DeclKind, Expr, ExprKind, ExprStmt, Function, KeyValueProp, LitValue, LogicalExpr, MemberExpr, MemberPropKind, MetaPropKind, MethodKind, MethodProp, NewExpr, ObjectLit, OperatorKind, ParenExpr, PatternKind, PropKind, PropNameKind, RegExLit, ReturnStmt, SpreadableKind, StmtKind, UnaryExpr, UpdateExpr, YieldExpr};
     use crate::ast::ExprKind::{Primary, Yield};
-    use crate::ast::PrimaryExprKind::This;
+    use crate::ast::PrimaryExprKind::{ArrayLiteral, FunctionExpr, GeneratorExpr, Lit, ObjectLiteral, RegExLiteral, This};
+    use crate::token::{Comment, CommentKind, KeywordKind, OpKind, TokenKind};
+    use crate::{LexerErrorKind, ParseErrorKind};
+    use crate::span::Span;
+    use crate::test_utils::{assert_ast_eq, error_count, id, num, parse_single, parse_single_expr, IntoNode};
     use super::Parser;
 
+    fn binary(op: OpKind, lhs: Expr, rhs: Expr) -> Expr {
+        ExprKind::Binary(BinaryExpr { op: OperatorKind::Punc(op), lhs: Box::new(lhs), rhs: Box::new(rhs) }).node()
+    }
+
+    fn member(object: Expr, prop: &str, optional: bool) -> Expr {
+        ExprKind::Member(MemberExpr { object: Box::new(object), prop: MemberPropKind::Id(prop.into()), optional }).node()
+    }
+
+    fn call(callee: Expr, args: Vec<Expr>, optional: bool) -> Expr {
+        let args = args.into_iter().map(SpreadableKind::Expr).collect();
+        ExprKind::Call(CallExpr { callee: Box::new(callee), args, optional }).node()
+    }
+
+    fn logical(op: OpKind, lhs: Expr, rhs: Expr) -> Expr {
+        ExprKind::Logical(LogicalExpr { op, lhs: Box::new(lhs), rhs: Box::new(rhs) }).node()
+    }
+
     #[test]
     fn test_this() {
-        let mut p = Parser::init("this").unwrap();
-        let res = p.parse("test").unwrap();
+        let mut p = Parser::init("this");
+        let res = p.parse("test");
         assert!(res.errors.is_empty());
         assert_eq!(res.ast.body.len(), 1);
-        assert_eq!(res.ast.body[0], StmtKind::Expression(ExprStmt(Primary(This))));
+        assert_ast_eq!(res.ast.body[0], StmtKind::Expression(ExprStmt(Primary(This).node())).node());
     }
 
     #[test]
     fn test_yield() {
-        let mut p = Parser::init("yield;").unwrap();
-        let res = p.parse("test").unwrap();
-        assert!(res.errors.is_empty());
+        let body = match parse_single_expr("(function* () { yield; yield* a; });").kind {
+            ExprKind::Paren(ParenExpr { expr }) => match expr.kind {
+                Primary(GeneratorExpr(function)) => function.body,
+                expr => panic!("Expected generator expression, found {:?}", expr),
+            },
+            expr => panic!("Expected parenthesized expression, found {:?}", expr),
+        };
+        assert_eq!(body.len(), 2);
+        assert_ast_eq!(body[0], StmtKind::Expression(ExprStmt(Yield(YieldExpr{ delegate: false, arg: None }).node())).node());
+        assert_ast_eq!(body[1], StmtKind::Expression(ExprStmt(Yield(YieldExpr{ delegate: true, arg: Some(Box::new(id("a"))) }).node())).node());
+    }
+
+    #[test]
+    fn test_binary_precedence() {
+        assert_ast_eq!(
+            parse_single_expr("a + b * c;"),
+            binary(OpKind::Addition, id("a"), binary(OpKind::Multiplication, id("b"), id("c")))
+        );
+        assert_ast_eq!(
+            parse_single_expr("a - b - c;"),
+            binary(OpKind::Subtraction, binary(OpKind::Subtraction, id("a"), id("b")), id("c"))
+        );
+        assert_ast_eq!(
+            parse_single_expr("a << 1 < b == c & d ^ e | f;"),
+            binary(
+                OpKind::BitOr,
+                binary(
+                    OpKind::BitXor,
+                    binary(
+                        OpKind::BitAnd,
+                        binary(
+                            OpKind::Equal,
+                            binary(OpKind::LessThan, binary(OpKind::LeftShift, id("a"), num(1)), id("b")),
+                            id("c")
+                        ),
+                        id("d")
+                    ),
+                    id("e")
+                ),
+                id("f")
+            )
+        );
+        assert_ast_eq!(
+            parse_single_expr("(a + b) * c;"),
+            binary(
+                OpKind::Multiplication,
+                ExprKind::Paren(ParenExpr { expr: Box::new(binary(OpKind::Addition, id("a"), id("b"))) }).node(),
+                id("c")
+            )
+        );
+    }
+
+    #[test]
+    fn test_relational_keywords() {
+        assert_ast_eq!(
+            parse_single_expr("a instanceof b in c;"),
+            ExprKind::Binary(BinaryExpr {
+                op: OperatorKind::Keyword(KeywordKind::In),
+                lhs: Box::new(ExprKind::Binary(BinaryExpr {
+                    op: OperatorKind::Keyword(KeywordKind::InstanceOf),
+                    lhs: Box::new(id("a")),
+                    rhs: Box::new(id("b")),
+                }).node()),
+                rhs: Box::new(id("c")),
+            }).node()
+        );
+    }
+
+    #[test]
+    fn test_exponent() {
+        assert_ast_eq!(
+            parse_single_expr("2 ** 3 ** 2;"),
+            binary(OpKind::Exponential, num(2), binary(OpKind::Exponential, num(3), num(2)))
+        );
+
+        let mut p = Parser::init("-2 ** 2;");
+        let res = p.parse("test");
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_unary_and_update() {
+        assert_ast_eq!(
+            parse_single_expr("typeof -a;"),
+            ExprKind::Unary(UnaryExpr {
+                op: OperatorKind::Keyword(KeywordKind::TypeOf),
+                arg: Box::new(ExprKind::Unary(UnaryExpr {
+                    op: OperatorKind::Punc(OpKind::Subtraction),
+                    arg: Box::new(id("a")),
+                }).node()),
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("!++a;"),
+            ExprKind::Unary(UnaryExpr {
+                op: OperatorKind::Punc(OpKind::Not),
+                arg: Box::new(ExprKind::Update(UpdateExpr { op: OpKind::Increment, prefix: true, arg: Box::new(id("a")) }).node()),
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("a-- * 2;"),
+            binary(
+                OpKind::Multiplication,
+                ExprKind::Update(UpdateExpr { op: OpKind::Decrement, prefix: false, arg: Box::new(id("a")) }).node(),
+                num(2)
+            )
+        );
+        assert_ast_eq!(
+            parse_single_expr("++(a.b);"),
+            ExprKind::Update(UpdateExpr {
+                op: OpKind::Increment,
+                prefix: true,
+                arg: Box::new(ExprKind::Paren(ParenExpr { expr: Box::new(member(id("a"), "b", false)) }).node()),
+            }).node()
+        );
+
+        // Only identifiers and member expressions outside optional chains can be updated.
+        for (source, span) in [("1++", Span::new(0, 1)), ("++f()", Span::new(2, 5)), ("a?.b++", Span::new(0, 4)),
+                               ("--a?.b.c", Span::new(2, 8)), ("(a + b)--", Span::new(0, 7))] {
+            let errs = Parser::init(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::InvalidAssignmentTarget), "{}", source);
+            assert_eq!(errs[0].span, span, "{}", source);
+        }
+
+        // Strict mode code can only delete properties.
+        for (source, span) in [("delete a;", Span::new(7, 8)), ("delete ((a));", Span::new(9, 10))] {
+            let errs = Parser::init(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::StrictDelete), "{}", source);
+            assert_eq!(errs[0].span, span, "{}", source);
+        }
+        assert_eq!(error_count("delete a.b; delete (a[b]); delete a();"), 0);
+        assert!(Parser::init_script("delete a;").parse("test").errors.is_empty());
+    }
+
+    #[test]
+    fn test_logical() {
+        assert_ast_eq!(
+            parse_single_expr("a || b && c;"),
+            logical(OpKind::Or, id("a"), logical(OpKind::And, id("b"), id("c")))
+        );
+        assert_ast_eq!(
+            parse_single_expr("a ?? b ?? c;"),
+            logical(OpKind::NullishCoalescing, logical(OpKind::NullishCoalescing, id("a"), id("b")), id("c"))
+        );
+
+        let mut p = Parser::init("a && b ?? c;");
+        let res = p.parse("test");
+        assert_eq!(res.errors.len(), 1);
+
+        let mut p = Parser::init("a ?? b || c;");
+        let res = p.parse("test");
+        assert_eq!(res.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_member_and_call() {
+        assert_ast_eq!(
+            parse_single_expr("a.b[c](d, ...e);"),
+            ExprKind::Call(CallExpr {
+                callee: Box::new(ExprKind::Member(MemberExpr {
+                    object: Box::new(member(id("a"), "b", false)),
+                    prop: MemberPropKind::Computed(Box::new(id("c"))),
+                    optional: false,
+                }).node()),
+                args: vec![SpreadableKind::Expr(id("d")), SpreadableKind::Spread(id("e"))],
+                optional: false,
+            }).node()
+        );
+        assert_ast_eq!(parse_single_expr("f()();"), call(call(id("f"), vec![], false), vec![], false));
+        // Reserved words are valid property names.
+        assert_ast_eq!(parse_single_expr("a.default.class"), member(member(id("a"), "default", false), "class", false));
+    }
+
+    #[test]
+    fn test_optional_chain() {
+        assert_ast_eq!(
+            parse_single_expr("a?.b.c?.(d);"),
+            call(member(member(id("a"), "b", true), "c", false), vec![id("d")], true)
+        );
+        assert_ast_eq!(
+            parse_single_expr("a?.[0];"),
+            ExprKind::Member(MemberExpr {
+                object: Box::new(id("a")),
+                prop: MemberPropKind::Computed(Box::new(num(0))),
+                optional: true,
+            }).node()
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        assert_ast_eq!(
+            parse_single_expr("new a.B(c).d;"),
+            member(
+                ExprKind::New(NewExpr {
+                    callee: Box::new(member(id("a"), "B", false)),
+                    args: Some(vec![SpreadableKind::Expr(id("c"))]),
+                }).node(),
+                "d",
+                false
+            )
+        );
+        assert_ast_eq!(
+            parse_single_expr("new new A()();"),
+            ExprKind::New(NewExpr {
+                callee: Box::new(ExprKind::New(NewExpr { callee: Box::new(id("A")), args: Some(vec![]) }).node()),
+                args: Some(vec![]),
+            }).node()
+        );
+        assert_ast_eq!(parse_single_expr("new A;"), ExprKind::New(NewExpr { callee: Box::new(id("A")), args: None }).node());
+    }
+
+    #[test]
+    fn test_super_and_meta_props() {
+        let StmtKind::Decl(DeclKind::Class(class)) = parse_single("class A extends B { constructor() { super(a); super.a; } }").kind else {
+            panic!("Expected class declaration");
+        };
+        let ClassElementKind::Method(ClassMethod { value, .. }) = &class.body[0].kind else { panic!("Expected constructor") };
+        assert_ast_eq!(
+            value.body,
+            vec![
+                StmtKind::Expression(ExprStmt(call(ExprKind::Super.node(), vec![id("a")], false))).node(),
+                StmtKind::Expression(ExprStmt(member(ExprKind::Super.node(), "a", false))).node(),
+            ]
+        );
+        assert_ast_eq!(
+            parse_single_expr("import.meta.url;"),
+            member(ExprKind::MetaProp(MetaPropKind::ImportMeta).node(), "url", false)
+        );
+
+        let mut p = Parser::init("super;");
+        let res = p.parse("test");
+        assert_eq!(res.errors.len(), 1);
+
+        // `super.x` is only allowed within methods, and `super()` within the constructors of derived classes.
+        for source in ["super.a;", "function f() { super.a; }", "class A { constructor() { super(); } }", "class A extends B { a = super(); }"] {
+            let errors = Parser::init(source).parse("test").errors;
+            assert_eq!(errors.len(), 1, "{}", source);
+            assert!(matches!(errors[0].kind, ParseErrorKind::UnexpectedToken(TokenKind::Keyword(KeywordKind::Super), _)), "{}", source);
+        }
+        let source = "({ m() { super.a; } }); class A extends B { constructor() { () => super(); } a = () => super.b; static { super.c; } }";
+        assert_eq!(error_count(source), 0);
+
+        // `new.target` is only allowed where a function other than an arrow function provides it.
+        let ExprKind::Paren(ParenExpr { expr }) = parse_single_expr("(function () { new.target; });").kind else {
+            panic!("Expected parenthesized expression");
+        };
+        let Primary(FunctionExpr(function)) = expr.kind else { panic!("Expected function expression") };
+        assert_ast_eq!(function.body, vec![StmtKind::Expression(ExprStmt(ExprKind::MetaProp(MetaPropKind::NewTarget).node())).node()]);
+        for source in ["new.target;", "() => new.target;", "{ new.target }"] {
+            assert_eq!(error_count(source), 1, "{}", source);
+        }
+        for source in ["function f() { () => new.target }", "class A { a = new.target; static { new.target } }"] {
+            assert_eq!(error_count(source), 0, "{}", source);
+        }
+
+        // `import.meta` is only allowed in modules.
+        let errors = Parser::init_script("import.meta;").parse("test").errors;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, ParseErrorKind::UnexpectedToken(TokenKind::Keyword(KeywordKind::Import), _)));
+        assert_eq!(errors[0].span, Span::new(0, 11));
+    }
+
+    #[test]
+    fn test_array_literal() {
+        assert_ast_eq!(
+            parse_single_expr("[a, , ...b, null,];"),
+            Primary(ArrayLiteral(ArrayLit {
+                elements: vec![
+                    Some(SpreadableKind::Expr(id("a"))),
+                    None,
+                    Some(SpreadableKind::Spread(id("b"))),
+                    Some(SpreadableKind::Expr(Primary(Lit(LitValue::Null)).node())),
+                ]
+            })).node()
+        );
+    }
+
+    #[test]
+    fn test_object_literal() {
+        assert_ast_eq!(
+            parse_single_expr("({a: 1, b, [c]: 2, ...d, get e() { return 3; }, set e(v) {}, get: 4, f() {}});"),
+            ExprKind::Paren(ParenExpr { expr: Box::new(Primary(ObjectLiteral(ObjectLit {
+                props: vec![
+                    PropKind::KeyValue(KeyValueProp { key: PropNameKind::Id("a".into()), value: num(1) }).node(),
+                    PropKind::Shorthand("b".into()).node(),
+                    PropKind::KeyValue(KeyValueProp { key: PropNameKind::Computed(Box::new(id("c"))), value: num(2) }).node(),
+                    PropKind::Spread(id("d")).node(),
+                    PropKind::Method(MethodProp {
+                        kind: MethodKind::Get,
+                        key: PropNameKind::Id("e".into()),
+                        value: Function {
+                            id: None,
+                            params: vec![],
+                            body: vec![StmtKind::Return(ReturnStmt { return_value: Some(Box::new(num(3))) }).node()],
+                            is_async: false,
+                            is_generator: false,
+                        },
+                    }).node(),
+                    PropKind::Method(MethodProp {
+                        kind: MethodKind::Set,
+                        key: PropNameKind::Id("e".into()),
+                        value: Function {
+                            id: None,
+                            params: vec![PatternKind::Id("v".into()).node()],
+                            body: vec![],
+                            is_async: false,
+                            is_generator: false,
+                        },
+                    }).node(),
+                    PropKind::KeyValue(KeyValueProp { key: PropNameKind::Id("get".into()), value: num(4) }).node(),
+                    PropKind::Method(MethodProp {
+                        kind: MethodKind::Method,
+                        key: PropNameKind::Id("f".into()),
+                        value: Function { id: None, params: vec![], body: vec![], is_async: false, is_generator: false },
+                    }).node(),
+                ]
+            })).node()) }).node()
+        );
+
+        // A getter takes no parameters, and a setter a single one.
+        for source in ["({ get a(b) {} });", "({ set a() {} });", "({ set a(b, c) {} });", "({ set a(...b) {} });"] {
+            let errs = Parser::init(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::InvalidAccessorParams), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_regex_literal() {
+        let regex = |pattern: &str, flags: &str| {
+            Primary(RegExLiteral(RegExLit { pattern: pattern.into(), flags: flags.into() })).node()
+        };
+
+        assert_ast_eq!(parse_single_expr("/ab+c/gi"), regex("ab+c", "gi"));
+        assert_ast_eq!(parse_single_expr("/=/g"), regex("=", "g"));
+        let assign = |op: OpKind, name: &str, value: Expr| {
+            ExprKind::Assign(AssignExpr { op, target: Box::new(PatternKind::Id(name.into()).node()), value: Box::new(value) }).node()
+        };
+        assert_ast_eq!(parse_single_expr("x = /[/]\\//.y"), assign(OpKind::Assign, "x", member(regex("[/]\\/", ""), "y", false)));
+        assert_ast_eq!(parse_single_expr("f(/a/, /b/)"), call(id("f"), vec![regex("a", ""), regex("b", "")], false));
+
+        // Where an operator is expected, `/` is still division.
+        assert_ast_eq!(
+            parse_single_expr("a / b / c"),
+            binary(OpKind::Division, binary(OpKind::Division, id("a"), id("b")), id("c"))
+        );
+        assert_ast_eq!(parse_single_expr("a /= /b/"), assign(OpKind::DivisionAssign, "a", regex("b", "")));
+
+        let mut p = Parser::init("if (a) /b/.exec(c)\n/d/g");
+        let res = p.parse("test");
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
         assert_eq!(res.ast.body.len(), 1);
-        assert_eq!(res.ast.body[0], StmtKind::Expression(ExprStmt(Yield(YieldExpr{ delegate: false, arg: None }))));
+
+        // The `//` within the pattern was first lexed as a comment.
+        let res = Parser::init("x = /[//]/ // y").parse("test");
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.comments.into_values().collect::<Vec<_>>(), vec![Comment { kind: CommentKind::SingleLine, text: " y".into() }]);
+    }
+
+    #[test]
+    fn test_escaped_keywords() {
+        let errors = |source: &str| Parser::init(source).parse("test").errors;
+
+        // Escaped reserved words are only allowed as property names.
+        assert_ast_eq!(parse_single_expr("a.\\u0069f"), member(id("a"), "if", false));
+        assert!(errors("({ \\u0069f: 1, n\\u0065w() {} })").is_empty());
+        assert_ast_eq!(parse_single_expr("\\u0061sync"), id("async"));
+
+        for (source, span) in [("\\u0069f", Span::new(0, 7)), ("let \\u{74}his = 1", Span::new(4, 13)), ("function n\\u0065w() {}", Span::new(9, 17)), ("var \\u0065num;", Span::new(4, 13))] {
+            let errs = errors(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::EscapedKeyword), "{}", source);
+            assert_eq!(errs[0].span, span, "{}", source);
+        }
+
+        // Escaped contextual keywords are not keywords either.
+        assert!(!errors("for (a \\u006ff b) {}").is_empty());
+
+        // `enum` is always reserved, and the names of shorthand properties are identifiers rather than property names.
+        assert!(errors("a.enum; ({ enum: 1, if() {} });").is_empty());
+        for source in ["var enum = 1;", "enum = 2;", "function enum() {}", "({ if });", "({ enum } = a);", "let { true } = a;"] {
+            assert_eq!(errors(source).len(), 1, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_regex_errors() {
+        let errors = |source: &str| Parser::init(source).parse("test").errors;
+
+        let errs = errors("x = /abc\ny;");
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(errs[0].kind, ParseErrorKind::Lexer(LexerErrorKind::UnterminatedRegEx)));
+        assert_eq!(errs[0].span, Span::new(4, 8));
+        assert_eq!(errs[0].kind.code(), "E0008");
+
+        let errs = errors("x = /a/gg;\ny = /b/;");
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(errs[0].kind, ParseErrorKind::Lexer(LexerErrorKind::InvalidRegExFlags)));
+        assert_eq!(errs[0].span, Span::new(7, 9));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expr_spans() {
+        let expr = parse_single_expr("a.b(c) + -d");
+        assert_eq!(expr.span, Span::new(0, 11));
+        let ExprKind::Binary(BinaryExpr { lhs, rhs, .. }) = expr.kind else { panic!("Expected binary expression") };
+        assert_eq!(lhs.span, Span::new(0, 6));
+        assert_eq!(rhs.span, Span::new(9, 11));
+
+        let mut p = Parser::init("  x = 1;\nfoo");
+        let module = p.parse("test").ast;
+        assert_eq!(module.span, Span::new(0, 12));
+        assert_eq!(module.body[0].span, Span::new(2, 8));
+        assert_eq!(module.body[1].span, Span::new(9, 12));
+    }
+}
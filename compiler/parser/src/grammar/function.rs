@@ -0,0 +1,508 @@
+use crate::ast::{ArrowBodyKind, ArrowFunction, ExprKind, ExprStmt, Function, HoistableDeclKind, LitValue, MethodKind, Pattern, PatternKind, PrimaryExprKind, Stmt, StmtKind};
+use crate::grammar::pattern::collect_bound_names;
+use crate::ParseErrorKind::UnexpectedToken;
+use crate::{ParseErrorKind, Parser};
+use crate::span::Span;
+use crate::symbol::sym;
+use crate::token::{BraceKind, KeywordKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
+
+impl Parser<'_> {
+
+    /// Section 15.1 [Parameter Lists](https://tc39.es/ecma262/#sec-parameter-lists)
+    pub(crate) fn parse_formal_params(&mut self) -> Vec<Pattern> {
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
+
+        let yield_await_exprs = self.yield_await_exprs.len();
+        let mut params = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) && !self.is_end() {
+            // The rest parameter must be the last parameter and cannot have a trailing comma.
+            if self.at(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+                params.push(self.parse_binding_rest());
+                break;
+            }
+
+            params.push(self.with_in(true, Self::parse_binding_element));
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+        self.report_yield_await_exprs(yield_await_exprs);
+
+        params
+    }
+
+    /// Reports the yield and await expressions parsed since `start`, which are within parameters.
+    pub(crate) fn report_yield_await_exprs(&mut self, start: usize) {
+        for span in self.yield_await_exprs.split_off(start) {
+            self.push_error_at(ParseErrorKind::YieldOrAwaitInParameters, span);
+        }
+    }
+
+    /// Section 15.1.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-parameter-lists-static-semantics-early-errors)
+    ///
+    /// Reports each name bound by the parameters that an earlier parameter already binds, as in `(a, [a]) => a`.
+    fn check_duplicate_params(&mut self, params: &[Pattern]) {
+        let mut names = Vec::new();
+        for param in params {
+            collect_bound_names(param, &mut names);
+        }
+        for (i, &(name, span)) in names.iter().enumerate() {
+            if names[..i].iter().any(|&(prev, _)| prev == name) {
+                self.push_error_at(ParseErrorKind::DuplicateParameter(name.to_string()), span);
+            }
+        }
+    }
+
+    /// Section 15.2 [FunctionBody](https://tc39.es/ecma262/#prod-FunctionBody), which starts with a directive prologue.
+    pub(crate) fn parse_function_body(&mut self) -> Vec<Stmt> {
+        let strict = self.strict;
+        let stmts = self.with_in(true, |p| {
+            p.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+            let mut stmts = Vec::new();
+            let mut prologue = true;
+            p.prologue_escapes = Some(Vec::new());
+            while !p.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !p.is_end() {
+                if let Some(stmt) = p.parse_stmt_list_item(Self::parse_stmt) {
+                    prologue = prologue && p.apply_directive(&stmt);
+                    stmts.push(stmt);
+                }
+            }
+
+            p.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+            stmts
+        });
+        self.strict = strict;
+        self.prologue_escapes = None;
+        stmts
+    }
+
+    /// Section 15.2 [Function Definitions](https://tc39.es/ecma262/#sec-function-definitions)
+    pub(crate) fn parse_function_decl(&mut self) -> HoistableDeclKind {
+        self.parse_hoistable_decl(true)
+    }
+
+    /// Section 16.2.3 [Exports](https://tc39.es/ecma262/#sec-exports), where the name of a default exported function
+    /// declaration is optional.
+    pub(crate) fn parse_default_function_decl(&mut self) -> HoistableDeclKind {
+        self.parse_hoistable_decl(false)
+    }
+
+    fn parse_hoistable_decl(&mut self, require_id: bool) -> HoistableDeclKind {
+        let function = self.parse_function(require_id);
+        match (function.is_async, function.is_generator) {
+            (false, false) => HoistableDeclKind::Function(function),
+            (true, false) => HoistableDeclKind::AsyncFunction(function),
+            (false, true) => HoistableDeclKind::Generator(function),
+            (true, true) => HoistableDeclKind::AsyncGenerator(function),
+        }
+    }
+
+    /// Section 15.2 [Function Definitions](https://tc39.es/ecma262/#sec-function-definitions)
+    pub(crate) fn parse_function_expr(&mut self) -> PrimaryExprKind {
+        let function = self.parse_function(false);
+        match (function.is_async, function.is_generator) {
+            (false, false) => PrimaryExprKind::FunctionExpr(function),
+            (true, false) => PrimaryExprKind::AsyncFunctionExpr(function),
+            (false, true) => PrimaryExprKind::GeneratorExpr(function),
+            (true, true) => PrimaryExprKind::AsyncGeneratorExpr(function),
+        }
+    }
+
+    /// Parses `[async] function [*] [name] (params) { body }`, the shared form of function, generator, async function
+    /// and async generator declarations and expressions.
+    fn parse_function(&mut self, require_id: bool) -> Function {
+        let is_async = self.eat(TokenKind::Id(sym::ASYNC));
+
+        self.expect(TokenKind::Keyword(KeywordKind::Function));
+
+        let is_generator = self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)));
+
+        let mut id = None;
+        if let TokenKind::Id(name) = self.peek_kind() {
+            self.check_identifier(self.peek());
+            self.check_strict_target(name, self.peek().span);
+            self.bump();
+            id = Some(name);
+        } else if require_id {
+            self.error_and_recover(self.unexpected());
+        }
+
+        let (params, body) = self.with_function(true, true, true, false, false, |p| {
+            p.with_params(is_generator, is_async, |p| (p.parse_formal_params(), p.parse_function_body()))
+        });
+
+        // Duplicate parameters are only allowed in the simple parameter lists of functions outside of strict mode code.
+        let is_simple = params.iter().all(|param| matches!(param.kind, PatternKind::Id(_)));
+        if self.strict || has_use_strict_directive(&body) || !is_simple {
+            self.check_duplicate_params(&params);
+        }
+
+        Function { id, params, body, is_async, is_generator }
+    }
+
+    /// Section 15.4 [Method Definitions](https://tc39.es/ecma262/#sec-method-definitions)
+    pub(crate) fn parse_method_function(&mut self, is_async: bool, is_generator: bool) -> Function {
+        self.parse_method_like_function(is_async, is_generator, false)
+    }
+
+    /// Section 15.7 [Class Definitions](https://tc39.es/ecma262/#sec-class-definitions)
+    ///
+    /// Parses the function of a class constructor, where `super()` is allowed if the class is `derived` from another.
+    pub(crate) fn parse_constructor_function(&mut self, derived: bool) -> Function {
+        self.parse_method_like_function(false, false, derived)
+    }
+
+    /// Parses the function of a method, where `super.x` is always allowed.
+    fn parse_method_like_function(&mut self, is_async: bool, is_generator: bool, allow_super_call: bool) -> Function {
+        let (params, body) = self.with_function(true, true, true, true, allow_super_call, |p| {
+            p.with_params(is_generator, is_async, |p| (p.parse_formal_params(), p.parse_function_body()))
+        });
+        // Methods have unique parameters, as in strict mode code.
+        self.check_duplicate_params(&params);
+        Function { id: None, params, body, is_async, is_generator }
+    }
+
+    /// Section 15.4.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-method-definitions-static-semantics-early-errors)
+    ///
+    /// Parses the function of a getter or setter, which take no parameters and a single parameter other than a rest
+    /// parameter respectively.
+    pub(crate) fn parse_accessor_function(&mut self, kind: &MethodKind) -> Function {
+        let start = self.start();
+        let value = self.parse_method_function(false, false);
+
+        let valid = match kind {
+            MethodKind::Get => value.params.is_empty(),
+            _ => matches!(&value.params[..], [param] if !matches!(param.kind, PatternKind::Rest(_))),
+        };
+        if !valid {
+            let span = match (value.params.first(), value.params.last()) {
+                (Some(first), Some(last)) => Span::new(first.span.start, last.span.end),
+                _ => self.finish(start),
+            };
+            self.push_error_at(ParseErrorKind::InvalidAccessorParams, span);
+        }
+
+        value
+    }
+
+    /// Section 15.3 [Arrow Function Definitions](https://tc39.es/ecma262/#sec-arrow-function-definitions)
+    ///
+    /// Parses the `=>` and body of an arrow function whose parameters are already parsed.
+    pub(crate) fn parse_arrow_function(&mut self, params: Vec<Pattern>, is_async: bool) -> ArrowFunction {
+        // The arrow must be on the same line as the parameters.
+        if self.has_newline_before() {
+            self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Op(OpKind::Arrow)));
+
+        // Arrow functions have unique parameters, as in strict mode code.
+        self.check_duplicate_params(&params);
+
+        // The body of an arrow function is never a generator, and shares `new.target`, `arguments` and `super` with the
+        // enclosing code.
+        let (new_target, arguments) = (self.allow_new_target, self.allow_arguments);
+        let (super_property, super_call) = (self.allow_super_property, self.allow_super_call);
+        let body = self.with_function(true, new_target, arguments, super_property, super_call, |p| {
+            p.with_params(false, is_async, |p| match p.peek_kind() {
+                TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => ArrowBodyKind::Block(p.parse_function_body()),
+                _ => ArrowBodyKind::Expr(Box::new(p.parse_assign_expr())),
+            })
+        });
+
+        ArrowFunction { params, body, is_async }
+    }
+
+    /// Checks for an arrow function with a single identifier parameter, as in `x => x`. Parenthesized parameters are
+    /// parsed as a parenthesized expression first, see [Parser::parse_paren_or_arrow].
+    pub(crate) fn is_id_arrow_fn_ahead(&self, offset: usize) -> bool {
+        matches!(self.peek_nth_kind(offset), TokenKind::Id(_))
+            && self.peek_nth_kind(offset + 1) == TokenKind::Punc(PuncKind::Op(OpKind::Arrow))
+    }
+
+    /// Checks for `async` immediately followed by a single identifier parameter and `=>`.
+    pub(crate) fn is_async_id_arrow_fn_ahead(&self) -> bool {
+        self.at_id(sym::ASYNC) && !self.peek_nth(1).preceded_by_newline && self.is_id_arrow_fn_ahead(1)
+    }
+
+    /// Checks for `async` immediately followed by `(`, which starts either the parameters of an async arrow function
+    /// or the arguments of a call to a function named `async`.
+    pub(crate) fn is_async_call_ahead(&self) -> bool {
+        self.at_id(sym::ASYNC)
+            && self.peek_nth_kind(1) == TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))
+            && !self.peek_nth(1).preceded_by_newline
+    }
+
+    /// Checks for `async` immediately followed by `function`.
+    pub(crate) fn is_async_function_ahead(&self) -> bool {
+        self.at_id(sym::ASYNC)
+            && self.peek_nth_kind(1) == TokenKind::Keyword(KeywordKind::Function)
+            && !self.peek_nth(1).preceded_by_newline
+    }
+}
+
+/// Checks if a function body starts with a `"use strict"` directive, which makes its parameters strict mode code too.
+fn has_use_strict_directive(body: &[Stmt]) -> bool {
+    body.iter()
+        .map_while(|stmt| match &stmt.kind {
+            StmtKind::Expression(ExprStmt(expr)) => match &expr.kind {
+                ExprKind::Primary(PrimaryExprKind::Lit(LitValue::String(lit))) => Some(lit),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|lit| lit.raw == "use strict")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{ArrayPattern, ArrowBodyKind, ArrowFunction, AssignPattern, CallExpr, DeclKind, ExprKind, Function, HoistableDeclKind, KeyValuePatternProp, MethodKind, MethodProp, ObjectLit, ObjectPattern, ObjectPatternPropKind, ParenExpr, PatternKind, PrimaryExprKind, PropKind, PropNameKind, ReturnStmt, ShorthandPatternProp, SpreadableKind, StmtKind};
+    use crate::ParseErrorKind::{DuplicateParameter, ReservedWord, UnexpectedToken, YieldOrAwaitInParameters};
+    use crate::span::Span;
+    use crate::token::{ParenthesesKind, PuncKind, TokenKind};
+    use crate::test_utils::{assert_ast_eq, error_count, id, num, parse_module, parse_single, parse_single_expr, IntoNode};
+    use super::Parser;
+
+    fn empty_function(id: Option<&str>, is_async: bool, is_generator: bool) -> Function {
+        Function { id: id.map(|id| id.into()), params: vec![], body: vec![], is_async, is_generator }
+    }
+
+    #[test]
+    fn test_function_decls() {
+        assert_ast_eq!(
+            parse_single("function f() {}"),
+            StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::Function(empty_function(Some("f"), false, false)))).node()
+        );
+        assert_ast_eq!(
+            parse_single("async function f() {}"),
+            StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::AsyncFunction(empty_function(Some("f"), true, false)))).node()
+        );
+        assert_ast_eq!(
+            parse_single("function* f() {}"),
+            StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::Generator(empty_function(Some("f"), false, true)))).node()
+        );
+        assert_ast_eq!(
+            parse_single("async function* f() {}"),
+            StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::AsyncGenerator(empty_function(Some("f"), true, true)))).node()
+        );
+
+        let mut p = Parser::init("function () {}");
+        let err = p.parse("test").errors.remove(0);
+        assert!(matches!(err.kind, UnexpectedToken(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)), _)));
+    }
+
+    #[test]
+    fn test_function_exprs() {
+        assert_ast_eq!(
+            parse_single_expr("(function () {});"),
+            ExprKind::Paren(ParenExpr {
+                expr: Box::new(ExprKind::Primary(PrimaryExprKind::FunctionExpr(empty_function(None, false, false))).node())
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("(async function* g() {});"),
+            ExprKind::Paren(ParenExpr {
+                expr: Box::new(ExprKind::Primary(PrimaryExprKind::AsyncGeneratorExpr(empty_function(Some("g"), true, true))).node())
+            }).node()
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let stmt = parse_single("function f(a, b = 1, {c, d: [e] = [], f = 2, ...g}, [, h, ...i], ...j) { return a; }");
+        let expected = Function {
+            id: Some("f".into()),
+            params: vec![
+                PatternKind::Id("a".into()).node(),
+                PatternKind::Assign(AssignPattern {
+                    target: Box::new(PatternKind::Id("b".into()).node()),
+                    default: Box::new(num(1)),
+                }).node(),
+                PatternKind::Object(ObjectPattern {
+                    props: vec![
+                        ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id: "c".into(), default: None }).node(),
+                        ObjectPatternPropKind::KeyValue(KeyValuePatternProp {
+                            key: PropNameKind::Id("d".into()),
+                            value: PatternKind::Assign(AssignPattern {
+                                target: Box::new(PatternKind::Array(ArrayPattern {
+                                    elements: vec![Some(PatternKind::Id("e".into()).node())],
+                                }).node()),
+                                default: Box::new(ExprKind::Primary(PrimaryExprKind::ArrayLiteral(
+                                    crate::ast::ArrayLit { elements: vec![] },
+                                )).node()),
+                            }).node(),
+                        }).node(),
+                        ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id: "f".into(), default: Some(num(2)) }).node(),
+                        ObjectPatternPropKind::Rest(Box::new(PatternKind::Id("g".into()).node())).node(),
+                    ],
+                }).node(),
+                PatternKind::Array(ArrayPattern {
+                    elements: vec![
+                        None,
+                        Some(PatternKind::Id("h".into()).node()),
+                        Some(PatternKind::Rest(Box::new(PatternKind::Id("i".into()).node())).node()),
+                    ],
+                }).node(),
+                PatternKind::Rest(Box::new(PatternKind::Id("j".into()).node())).node(),
+            ],
+            body: vec![StmtKind::Return(ReturnStmt { return_value: Some(Box::new(id("a"))) }).node()],
+            is_async: false,
+            is_generator: false,
+        };
+        assert_ast_eq!(stmt, StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::Function(expected))).node());
+    }
+
+    #[test]
+    fn test_yield_and_await_in_params() {
+        for source in [
+            "function* g(x = yield) {}",
+            "async function f(x = await 1) {}",
+            "class A { *m(a = yield) {} }",
+            "({ async m(a = await b) {} });",
+            "async (x = await 1) => 1;",
+            "async function f() { (x = await 1) => 1; }",
+            "function* g() { (x = yield) => 1; }",
+        ] {
+            let errs = Parser::init(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, YieldOrAwaitInParameters), "{}", source);
+        }
+        for source in ["async (await) => 1;", "async await => 1;", "async ([await]) => 1;", "async ({ await }) => 1;"] {
+            let errs = Parser::init_script(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(&errs[0].kind, ReservedWord(name) if name == "await"), "{}", source);
+        }
+        parse_module("function* g() { function* h(x = function*() { yield; }) {} yield; }");
+        parse_module("async function f() { async (x = async () => await 1) => 1; f(await x); async(await x); }");
+    }
+
+    #[test]
+    fn test_duplicate_params() {
+        let script = |source: &str| Parser::init_script(source).parse("test").errors;
+        assert!(script("function f(a, a) {} function g(a, b) { \"use strict\"; }").is_empty());
+
+        for (source, start) in [
+            ("\"use strict\"; function f(a, a) {}", 28),
+            ("function f(a, a) { \"use strict\"; }", 14),
+            ("function f(a, [a]) {}", 15),
+            ("(a, a) => 1;", 4),
+            ("async (a, { b: a }) => 1;", 15),
+            ("({ m(a, ...a) {} });", 11),
+            ("class A { set x([b, b]) {} }", 20),
+        ] {
+            let errs = script(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(&errs[0].kind, DuplicateParameter(_)), "{}", source);
+            assert_eq!(errs[0].span, Span::new(start, start + 1), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_arrow_functions() {
+        assert_ast_eq!(
+            parse_single_expr("x => x;"),
+            ExprKind::Arrow(ArrowFunction {
+                params: vec![PatternKind::Id("x".into()).node()],
+                body: ArrowBodyKind::Expr(Box::new(id("x"))),
+                is_async: false,
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("() => {};"),
+            ExprKind::Arrow(ArrowFunction { params: vec![], body: ArrowBodyKind::Block(vec![]), is_async: false }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("async (a, ...b) => a;"),
+            ExprKind::Arrow(ArrowFunction {
+                params: vec![PatternKind::Id("a".into()).node(), PatternKind::Rest(Box::new(PatternKind::Id("b".into()).node())).node()],
+                body: ArrowBodyKind::Expr(Box::new(id("a"))),
+                is_async: true,
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("async => async;"),
+            ExprKind::Arrow(ArrowFunction {
+                params: vec![PatternKind::Id("async".into()).node()],
+                body: ArrowBodyKind::Expr(Box::new(id("async"))),
+                is_async: false,
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("async(a);"),
+            ExprKind::Call(CallExpr {
+                callee: Box::new(id("async")),
+                args: vec![SpreadableKind::Expr(id("a"))],
+                optional: false,
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single_expr("a => b => (a);"),
+            ExprKind::Arrow(ArrowFunction {
+                params: vec![PatternKind::Id("a".into()).node()],
+                body: ArrowBodyKind::Expr(Box::new(ExprKind::Arrow(ArrowFunction {
+                    params: vec![PatternKind::Id("b".into()).node()],
+                    body: ArrowBodyKind::Expr(Box::new(ExprKind::Paren(ParenExpr { expr: Box::new(id("a")) }).node())),
+                    is_async: false,
+                }).node())),
+                is_async: false,
+            }).node()
+        );
+
+        // `async` followed by a line terminator is an identifier, and `=>` cannot start a new line.
+        let mut p = Parser::init("async\nx => x");
+        assert_eq!(p.parse("test").ast.body.len(), 2);
+        let mut p = Parser::init("async\nfunction f() {}");
+        assert_eq!(p.parse("test").ast.body.len(), 2);
+        let mut p = Parser::init("(a)\n=> a");
+        assert_eq!(p.parse("test").errors.len(), 1);
+
+        // Parameters are parsed as an expression first, so parentheses within regular expressions are not counted.
+        for source in ["(a = /[(]/) => a", "(a = /\\)/) => a", "(a = /\\(/) => a", "(a = /'/) => a", "({a = 1}, [b] = [/'/]) => a"] {
+            assert!(matches!(parse_single_expr(source).kind, ExprKind::Arrow(_)), "{}", source);
+        }
+        assert!(matches!(parse_single_expr("async (a = /[(]/) => a").kind, ExprKind::Arrow(ArrowFunction { is_async: true, .. })));
+
+        // Nothing can follow an arrow function, so a call on the next line is a separate statement.
+        assert_eq!(parse_module("let f = () => {}\n(a)").body.len(), 2);
+
+        for source in [
+            "((a)) => a", "(a.b) => a", "(...a, b) => a", "(...a,) => a", "()", "(a,)", "(...a)",
+            "a + (b) => b", "() => {} + 1", "() => {}.a", "({a = 1})",
+        ] {
+            assert!(error_count(source) > 0, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_methods() {
+        assert_ast_eq!(
+            parse_single_expr("({ *a() {}, async b() {}, async *c() {}, async: 1 });"),
+            ExprKind::Paren(ParenExpr {
+                expr: Box::new(ExprKind::Primary(PrimaryExprKind::ObjectLiteral(ObjectLit {
+                    props: vec![
+                        PropKind::Method(MethodProp {
+                            kind: MethodKind::Method,
+                            key: PropNameKind::Id("a".into()),
+                            value: empty_function(None, false, true),
+                        }).node(),
+                        PropKind::Method(MethodProp {
+                            kind: MethodKind::Method,
+                            key: PropNameKind::Id("b".into()),
+                            value: empty_function(None, true, false),
+                        }).node(),
+                        PropKind::Method(MethodProp {
+                            kind: MethodKind::Method,
+                            key: PropNameKind::Id("c".into()),
+                            value: empty_function(None, true, true),
+                        }).node(),
+                        PropKind::KeyValue(crate::ast::KeyValueProp { key: PropNameKind::Id("async".into()), value: num(1) }).node(),
+                    ],
+                })).node()),
+            }).node()
+        );
+    }
+}
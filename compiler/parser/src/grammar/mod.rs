@@ -1,3 +1,7 @@
 mod expression;
 mod statement;
-mod class;
\ No newline at end of file
+mod class;
+mod function;
+mod pattern;
+mod module;
+mod template;
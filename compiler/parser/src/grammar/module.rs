@@ -0,0 +1,535 @@
+use crate::ast::DeclKind::{Class, Hoistable, Lexical};
+use crate::ast::LexicalKind::{Const, Let};
+use crate::ast::{DeclKind, ExportAll, ExportDeclKind, ExportDefaultKind, ExportEntry, ExportSpecifier, Function, HoistableDeclKind, ImportAttribute, ImportDecl, ImportEntry, ImportNameKind, ImportSpecifier, ImportSpecifierKind, LexicalKind, ModuleSummary, NamedExport, Stmt, StmtKind, Utf16String, VarDeclarator};
+use crate::grammar::pattern::collect_bound_names;
+use crate::{ParseErrorKind, Parser};
+use crate::symbol::{sym, Symbol};
+use crate::token::{BraceKind, KeywordKind, LitKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
+
+impl Parser<'_> {
+
+    /// Section 16.2 [ModuleItem](https://tc39.es/ecma262/#prod-ModuleItem)
+    pub(crate) fn parse_module_item(&mut self) -> Option<Stmt> {
+        let start = self.start();
+        let kind = match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::Import) if self.is_import_decl_ahead() => StmtKind::Import(self.parse_import_decl()),
+            TokenKind::Keyword(KeywordKind::Export) => StmtKind::Export(self.parse_export_decl()),
+            _ => return self.parse_stmt(),
+        };
+        Some(Stmt::new(kind, self.finish(start)))
+    }
+
+    /// Checks if the `import` keyword starts a declaration rather than an `import()` call or `import.meta`.
+    pub(crate) fn is_import_decl_ahead(&self) -> bool {
+        !matches!(
+            self.peek_nth_kind(1),
+            TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)) | TokenKind::Punc(PuncKind::Dot)
+        )
+    }
+
+    /// Section 16.2.2 [Imports](https://tc39.es/ecma262/#sec-imports)
+    pub(crate) fn parse_import_decl(&mut self) -> ImportDecl {
+        self.expect(TokenKind::Keyword(KeywordKind::Import));
+
+        let mut specifiers = Vec::new();
+
+        if !matches!(self.peek_kind(), TokenKind::Lit(LitKind::String)) {
+            // A default import may be followed by either a namespace import or named imports.
+            let mut has_default = false;
+            if let TokenKind::Id(local) = self.peek_kind() {
+                self.check_identifier(self.peek());
+                self.check_strict_target(local, self.peek().span);
+                self.bump();
+                specifiers.push(ImportSpecifierKind::Default(local));
+                has_default = true;
+            }
+
+            if !has_default || self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication))) {
+                    self.expect(TokenKind::Id(sym::AS));
+                    specifiers.push(ImportSpecifierKind::Namespace(self.parse_binding_identifier()));
+                } else {
+                    self.parse_named_imports(&mut specifiers);
+                }
+            }
+
+            self.expect(TokenKind::Id(sym::FROM));
+        }
+
+        let source = self.parse_module_specifier();
+        let attributes = self.parse_import_attributes();
+
+        self.consume_semicolon();
+
+        ImportDecl { specifiers, source, attributes }
+    }
+
+    /// Section 16.2.2 [NamedImports](https://tc39.es/ecma262/#prod-NamedImports)
+    fn parse_named_imports(&mut self, specifiers: &mut Vec<ImportSpecifierKind>) {
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            let start = self.start();
+            let imported_span = self.peek().span;
+            let is_string = matches!(self.peek_kind(), TokenKind::Lit(LitKind::String));
+            let imported = self.parse_module_export_name();
+
+            let local = if self.eat(TokenKind::Id(sym::AS)) {
+                self.parse_binding_identifier()
+            } else {
+                // Without a local name, the imported name is bound directly so cannot be a string.
+                if is_string {
+                    self.push_error(self.unexpected());
+                }
+                let local = Symbol::intern(&imported);
+                self.check_strict_target(local, imported_span);
+                local
+            };
+
+            specifiers.push(ImportSpecifierKind::Named(ImportSpecifier { imported, local, span: self.finish(start) }));
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+    }
+
+    /// Section 16.2.2 [WithClause](https://tc39.es/ecma262/#prod-WithClause)
+    fn parse_import_attributes(&mut self) -> Vec<ImportAttribute> {
+        let mut attributes = Vec::new();
+
+        if !self.eat(TokenKind::Keyword(KeywordKind::With)) {
+            return attributes;
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            let start = self.start();
+            let key = self.parse_module_export_name();
+            self.expect(TokenKind::Punc(PuncKind::Colon));
+            let value = self.parse_module_specifier();
+
+            attributes.push(ImportAttribute { key, value, span: self.finish(start) });
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        attributes
+    }
+
+    /// Section 16.2.2 [ModuleExportName](https://tc39.es/ecma262/#prod-ModuleExportName)
+    fn parse_module_export_name(&mut self) -> String {
+        if self.at(TokenKind::Lit(LitKind::String)) {
+            let span = self.peek().span;
+            // Export names are matched between modules as Unicode text, so can not contain lone surrogates.
+            return match self.parse_string_value().to_utf8() {
+                Some(name) => name,
+                None => {
+                    self.push_error_at(ParseErrorKind::IllFormedExportName, span);
+                    String::new()
+                }
+            };
+        }
+        self.parse_identifier_name().to_string()
+    }
+
+    /// Section 16.2.2 [ModuleSpecifier](https://tc39.es/ecma262/#prod-ModuleSpecifier)
+    fn parse_module_specifier(&mut self) -> String {
+        if self.at(TokenKind::Lit(LitKind::String)) {
+            // A specifier with a lone surrogate is valid, but can not name any module a host would resolve anyway.
+            return self.parse_string_value().to_utf8_lossy();
+        }
+        self.error_and_recover(self.unexpected());
+        String::new()
+    }
+
+    /// Parses the string literal at the next token as its string value.
+    fn parse_string_value(&mut self) -> Utf16String {
+        let span = self.peek().span;
+        let value = self.tokens.borrow().string_lit(span).cooked;
+        self.bump();
+        value
+    }
+
+    /// Section 16.2.3 [Exports](https://tc39.es/ecma262/#sec-exports)
+    pub(crate) fn parse_export_decl(&mut self) -> ExportDeclKind {
+        self.expect(TokenKind::Keyword(KeywordKind::Export));
+
+        match self.peek_kind() {
+            TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)) => {
+                self.bump();
+
+                let mut exported = None;
+                if self.eat(TokenKind::Id(sym::AS)) {
+                    exported = Some(self.parse_module_export_name());
+                }
+
+                self.expect(TokenKind::Id(sym::FROM));
+                let source = self.parse_module_specifier();
+                let attributes = self.parse_import_attributes();
+
+                self.consume_semicolon();
+
+                ExportDeclKind::All(ExportAll { exported, source, attributes })
+            }
+            TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => ExportDeclKind::Named(self.parse_named_export()),
+            TokenKind::Keyword(KeywordKind::Default) => {
+                self.bump();
+                ExportDeclKind::Default(self.parse_export_default())
+            }
+            TokenKind::Keyword(KeywordKind::Var) => ExportDeclKind::Var(self.parse_var_stmt()),
+            TokenKind::Id(_) if self.at_id(sym::LET) => ExportDeclKind::Decl(Lexical(Let(self.parse_let_decl_stmt()))),
+            TokenKind::Keyword(KeywordKind::Const) => ExportDeclKind::Decl(Lexical(Const(self.parse_const_decl_stmt()))),
+            TokenKind::Keyword(KeywordKind::Class) => ExportDeclKind::Decl(Class(self.parse_class_stmt())),
+            TokenKind::Keyword(KeywordKind::Function) => ExportDeclKind::Decl(Hoistable(self.parse_function_decl())),
+            _ if self.is_async_function_ahead() => ExportDeclKind::Decl(Hoistable(self.parse_function_decl())),
+            _ => {
+                self.error_and_recover(self.unexpected());
+                ExportDeclKind::Named(NamedExport { specifiers: Vec::new(), source: None, attributes: Vec::new() })
+            }
+        }
+    }
+
+    /// Section 16.2.3 [NamedExports](https://tc39.es/ecma262/#prod-NamedExports)
+    fn parse_named_export(&mut self) -> NamedExport {
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        let mut specifiers = Vec::new();
+        let mut has_string_local = false;
+
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            let start = self.start();
+            has_string_local |= matches!(self.peek_kind(), TokenKind::Lit(LitKind::String));
+            let local = self.parse_module_export_name();
+
+            let mut exported = local.clone();
+            if self.eat(TokenKind::Id(sym::AS)) {
+                exported = self.parse_module_export_name();
+            }
+
+            specifiers.push(ExportSpecifier { local, exported, span: self.finish(start) });
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        let mut source = None;
+        let mut attributes = Vec::new();
+
+        if self.eat(TokenKind::Id(sym::FROM)) {
+            source = Some(self.parse_module_specifier());
+            attributes = self.parse_import_attributes();
+        } else if has_string_local {
+            // Only re-exports can refer to names that are not identifiers.
+            self.push_error(self.unexpected());
+        }
+
+        self.consume_semicolon();
+
+        NamedExport { specifiers, source, attributes }
+    }
+
+    /// Section 16.2.3 [ExportDeclaration](https://tc39.es/ecma262/#prod-ExportDeclaration), after `export default`.
+    fn parse_export_default(&mut self) -> ExportDefaultKind {
+        match self.peek_kind() {
+            TokenKind::Keyword(KeywordKind::Function) => ExportDefaultKind::Function(self.parse_default_function_decl()),
+            TokenKind::Keyword(KeywordKind::Class) => ExportDefaultKind::Class(self.parse_default_class_decl()),
+            _ if self.is_async_function_ahead() => ExportDefaultKind::Function(self.parse_default_function_decl()),
+            _ => {
+                let expr = self.with_in(true, Self::parse_assign_expr);
+                self.consume_semicolon();
+                ExportDefaultKind::Expr(expr)
+            }
+        }
+    }
+}
+
+impl ModuleSummary {
+    /// Collects the import and export entries of the top level statements of a module.
+    pub(crate) fn from_body(body: &[Stmt]) -> Self {
+        let mut summary = ModuleSummary::default();
+
+        for stmt in body {
+            match &stmt.kind {
+                StmtKind::Import(import) => summary.add_import(import),
+                StmtKind::Export(export) => summary.add_export(export),
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
+    fn add_request(&mut self, module_request: &str) {
+        if !self.requests.iter().any(|request| request == module_request) {
+            self.requests.push(module_request.to_string());
+        }
+    }
+
+    fn add_import(&mut self, import: &ImportDecl) {
+        self.add_request(&import.source);
+
+        for specifier in &import.specifiers {
+            let (import_name, local_name) = match specifier {
+                ImportSpecifierKind::Default(local) => (ImportNameKind::Name("default".into()), local),
+                ImportSpecifierKind::Namespace(local) => (ImportNameKind::Namespace, local),
+                ImportSpecifierKind::Named(named) => (ImportNameKind::Name(named.imported.clone()), &named.local),
+            };
+
+            self.imports.push(ImportEntry { module_request: import.source.clone(), import_name, local_name: local_name.to_string() });
+        }
+    }
+
+    fn add_export(&mut self, export: &ExportDeclKind) {
+        match export {
+            ExportDeclKind::Named(named) => {
+                if let Some(source) = &named.source {
+                    self.add_request(source);
+                }
+
+                for specifier in &named.specifiers {
+                    // Re-exported names are imported from the source module rather than bound locally.
+                    let entry = match &named.source {
+                        Some(source) => ExportEntry {
+                            export_name: Some(specifier.exported.clone()),
+                            module_request: Some(source.clone()),
+                            import_name: Some(ImportNameKind::Name(specifier.local.clone())),
+                            local_name: None,
+                        },
+                        None => local_export(&specifier.exported, &specifier.local),
+                    };
+                    self.exports.push(entry);
+                }
+            }
+            ExportDeclKind::All(all) => {
+                self.add_request(&all.source);
+
+                let import_name = match all.exported {
+                    Some(_) => ImportNameKind::Namespace,
+                    None => ImportNameKind::AllButDefault,
+                };
+
+                self.exports.push(ExportEntry {
+                    export_name: all.exported.clone(),
+                    module_request: Some(all.source.clone()),
+                    import_name: Some(import_name),
+                    local_name: None,
+                });
+            }
+            ExportDeclKind::Var(var) => self.add_declarator_exports(&var.declarators),
+            ExportDeclKind::Decl(decl) => match decl {
+                DeclKind::Hoistable(hoistable) => {
+                    if let Some(id) = &hoistable_function(hoistable).id {
+                        self.exports.push(local_export(id.as_str(), id.as_str()));
+                    }
+                }
+                DeclKind::Class(class) => {
+                    if let Some(id) = &class.id {
+                        self.exports.push(local_export(id.as_str(), id.as_str()));
+                    }
+                }
+                DeclKind::Lexical(LexicalKind::Let(decl)) => self.add_declarator_exports(&decl.declarators),
+                DeclKind::Lexical(LexicalKind::Const(decl)) => self.add_declarator_exports(&decl.declarators),
+            },
+            ExportDeclKind::Default(default) => {
+                let id = match default {
+                    ExportDefaultKind::Function(hoistable) => hoistable_function(hoistable).id,
+                    ExportDefaultKind::Class(class) => class.id,
+                    ExportDefaultKind::Expr(_) => None,
+                };
+                self.exports.push(local_export("default", id.map_or("*default*", |id| id.as_str())));
+            }
+        }
+    }
+
+    fn add_declarator_exports(&mut self, declarators: &[VarDeclarator]) {
+        let mut names = Vec::new();
+        for declarator in declarators {
+            collect_bound_names(&declarator.id, &mut names);
+        }
+
+        for (name, _) in names {
+            self.exports.push(local_export(name.as_str(), name.as_str()));
+        }
+    }
+}
+
+fn local_export(export_name: &str, local_name: &str) -> ExportEntry {
+    ExportEntry {
+        export_name: Some(export_name.to_string()),
+        module_request: None,
+        import_name: None,
+        local_name: Some(local_name.to_string()),
+    }
+}
+
+fn hoistable_function(hoistable: &HoistableDeclKind) -> &Function {
+    match hoistable {
+        HoistableDeclKind::Function(function)
+        | HoistableDeclKind::AsyncFunction(function)
+        | HoistableDeclKind::Generator(function)
+        | HoistableDeclKind::AsyncGenerator(function) => function,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{ExportAll, ExportDeclKind, ExportDefaultKind, ExportEntry, ExportSpecifier, Expr, ExprKind, ExprStmt, ImportAttribute, ImportCall, ImportDecl, ImportEntry, ImportNameKind, ImportSpecifier, ImportSpecifierKind, LitValue, NamedExport, PrimaryExprKind, StmtKind};
+    use crate::ParseErrorKind;
+    use crate::span::Span;
+    use crate::test_utils::{assert_ast_eq, parse_module, parse_single, IntoNode};
+    use super::Parser;
+
+    fn named(imported: &str, local: &str) -> ImportSpecifierKind {
+        ImportSpecifierKind::Named(ImportSpecifier { imported: imported.into(), local: local.into(), span: Span::default() })
+    }
+
+    fn local_export(export_name: &str, local_name: &str) -> ExportEntry {
+        ExportEntry { export_name: Some(export_name.into()), module_request: None, import_name: None, local_name: Some(local_name.into()) }
+    }
+
+    #[test]
+    fn test_imports() {
+        assert_ast_eq!(
+            parse_single("import \"polyfill\";"),
+            StmtKind::Import(ImportDecl { specifiers: vec![], source: "polyfill".into(), attributes: vec![] }).node()
+        );
+        assert_ast_eq!(
+            parse_single("import a, { b, c as d, default as e, \"f g\" as h } from \"m\";"),
+            StmtKind::Import(ImportDecl {
+                specifiers: vec![
+                    ImportSpecifierKind::Default("a".into()),
+                    named("b", "b"),
+                    named("c", "d"),
+                    named("default", "e"),
+                    named("f g", "h"),
+                ],
+                source: "m".into(),
+                attributes: vec![],
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single("import a, * as ns from \"m\""),
+            StmtKind::Import(ImportDecl {
+                specifiers: vec![ImportSpecifierKind::Default("a".into()), ImportSpecifierKind::Namespace("ns".into())],
+                source: "m".into(),
+                attributes: vec![],
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single("import data from \"./data.json\" with { type: \"json\" };"),
+            StmtKind::Import(ImportDecl {
+                specifiers: vec![ImportSpecifierKind::Default("data".into())],
+                source: "./data.json".into(),
+                attributes: vec![ImportAttribute { key: "type".into(), value: "json".into(), span: Span::default() }],
+            }).node()
+        );
+
+        let mut p = Parser::init("import { \"a b\" } from \"m\"");
+        assert_eq!(p.parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_exports() {
+        assert_ast_eq!(
+            parse_single("export { a, b as default } from \"m\";"),
+            StmtKind::Export(ExportDeclKind::Named(NamedExport {
+                specifiers: vec![
+                    ExportSpecifier { local: "a".into(), exported: "a".into(), span: Span::default() },
+                    ExportSpecifier { local: "b".into(), exported: "default".into(), span: Span::default() },
+                ],
+                source: Some("m".into()),
+                attributes: vec![],
+            })).node()
+        );
+        assert_ast_eq!(
+            parse_single("export * as ns from \"m\";"),
+            StmtKind::Export(ExportDeclKind::All(ExportAll { exported: Some("ns".into()), source: "m".into(), attributes: vec![] })).node()
+        );
+        assert_ast_eq!(
+            parse_single("export default 1;"),
+            StmtKind::Export(ExportDeclKind::Default(ExportDefaultKind::Expr(ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(1.0))).node()))).node()
+        );
+        assert!(matches!(
+            parse_single("export default function () {}").kind,
+            StmtKind::Export(ExportDeclKind::Default(ExportDefaultKind::Function(_)))
+        ));
+        assert!(matches!(parse_single("export async function f() {}").kind, StmtKind::Export(ExportDeclKind::Decl(_))));
+
+        // Module declarations are only allowed at the top level.
+        assert!(!Parser::init("{ export const a = 1; }").parse("test").errors.is_empty());
+        assert!(!Parser::init("if (a) import \"m\";").parse("test").errors.is_empty());
+
+        // String export names can not contain lone surrogates, unlike module specifiers.
+        for (source, start) in [("export { a as \"\\uD83D\" };", 14), ("import { \"\\uDE00\" as b } from \"m\";", 9)] {
+            let errors = Parser::init(source).parse("test").errors;
+            assert_eq!(errors.len(), 1, "{}", source);
+            assert!(matches!(errors[0].kind, ParseErrorKind::IllFormedExportName), "{}", source);
+            assert_eq!(errors[0].span, Span::new(start, start + 8), "{}", source);
+        }
+        assert!(Parser::init("export { a as \"\\uD83D\\uDE00\" } from \"\\uD83D\";").parse("test").errors.is_empty());
+    }
+
+    #[test]
+    fn test_import_call() {
+        assert!(matches!(
+            parse_single("import(\"m\", { with: { type: \"json\" } });").kind,
+            StmtKind::Expression(ExprStmt(Expr { kind: ExprKind::ImportCall(ImportCall { options: Some(_), .. }), .. }))
+        ));
+        assert_ast_eq!(
+            parse_single("import(a)"),
+            StmtKind::Expression(ExprStmt(ExprKind::ImportCall(ImportCall {
+                source: Box::new(ExprKind::Primary(PrimaryExprKind::Id("a".into())).node()),
+                options: None,
+            }).node())).node()
+        );
+    }
+
+    #[test]
+    fn test_module_summary() {
+        let module = parse_module(
+            "import a, { b as c } from \"x\";\n\
+             import * as ns from \"y\";\n\
+             export const { d, e: [f] } = a;\n\
+             export { c as g };\n\
+             export * from \"x\";\n\
+             export default class {}",
+        );
+        let summary = module.summary;
+
+        assert_eq!(summary.requests, vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(
+            summary.imports,
+            vec![
+                ImportEntry { module_request: "x".into(), import_name: ImportNameKind::Name("default".into()), local_name: "a".into() },
+                ImportEntry { module_request: "x".into(), import_name: ImportNameKind::Name("b".into()), local_name: "c".into() },
+                ImportEntry { module_request: "y".into(), import_name: ImportNameKind::Namespace, local_name: "ns".into() },
+            ]
+        );
+        assert_eq!(
+            summary.exports,
+            vec![
+                local_export("d", "d"),
+                local_export("f", "f"),
+                local_export("g", "c"),
+                ExportEntry {
+                    export_name: None,
+                    module_request: Some("x".into()),
+                    import_name: Some(ImportNameKind::AllButDefault),
+                    local_name: None,
+                },
+                local_export("default", "*default*"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,548 @@
+use crate::ast::{ArrayLit, ArrayPattern, AssignExpr, AssignPattern, Expr, ExprKind, KeyValuePatternProp, ObjectLit, ObjectPattern, ObjectPatternProp, ObjectPatternPropKind, ParenExpr, Pattern, PatternKind, PrimaryExprKind, PropKind, PropNameKind, ShorthandPatternProp, SpreadableKind};
+use crate::ParseErrorKind::{InvalidAssignmentTarget, UnexpectedToken};
+use crate::Parser;
+use crate::span::Span;
+use crate::symbol::{sym, Symbol};
+use crate::token::{BraceKind, BracketKind, OpKind, PuncKind, TokenKind};
+
+impl Parser<'_> {
+
+    /// Section 14.3.3 [BindingElement](https://tc39.es/ecma262/#prod-BindingElement)
+    pub(crate) fn parse_binding_element(&mut self) -> Pattern {
+        let start = self.start();
+        let target = self.parse_binding_target();
+
+        if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Assign))) {
+            let default = Box::new(self.parse_assign_expr());
+            return Pattern::new(PatternKind::Assign(AssignPattern { target: Box::new(target), default }), self.finish(start));
+        }
+
+        target
+    }
+
+    /// Parses a binding identifier or a nested destructuring pattern.
+    pub(crate) fn parse_binding_target(&mut self) -> Pattern {
+        let start = self.start();
+        let kind = match self.peek_kind() {
+            TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)) => {
+                self.nested(|p| PatternKind::Array(p.parse_array_binding_pattern()))
+            }
+            TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => {
+                self.nested(|p| PatternKind::Object(p.parse_object_binding_pattern()))
+            }
+            _ => Some(PatternKind::Id(self.parse_binding_identifier())),
+        };
+        // Like a missing binding identifier, a pattern nested too deeply is left empty.
+        Pattern::new(kind.unwrap_or(PatternKind::Id(sym::EMPTY)), self.finish(start))
+    }
+
+    /// Section 13.1 [BindingIdentifier](https://tc39.es/ecma262/#prod-BindingIdentifier)
+    pub(crate) fn parse_binding_identifier(&mut self) -> Symbol {
+        match self.peek_kind() {
+            TokenKind::Id(id) => {
+                self.check_identifier(self.peek());
+                self.check_strict_target(id, self.peek().span);
+                self.bump();
+                id
+            }
+            _ => {
+                self.error_and_recover(self.unexpected());
+                sym::EMPTY
+            }
+        }
+    }
+
+    /// Section 14.3.3 [BindingRestElement](https://tc39.es/ecma262/#prod-BindingRestElement)
+    pub(crate) fn parse_binding_rest(&mut self) -> Pattern {
+        let start = self.start();
+        self.expect(TokenKind::Punc(PuncKind::Op(OpKind::Spread)));
+        let target = Box::new(self.parse_binding_target());
+        Pattern::new(PatternKind::Rest(target), self.finish(start))
+    }
+
+    /// Section 14.3.3 [ArrayBindingPattern](https://tc39.es/ecma262/#prod-ArrayBindingPattern)
+    pub(crate) fn parse_array_binding_pattern(&mut self) -> ArrayPattern {
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)));
+
+        let mut elements = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right))) && !self.is_end() {
+            if self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                elements.push(None);
+                continue;
+            }
+
+            // The rest element must be the last element of the pattern.
+            if self.at(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+                elements.push(Some(self.parse_binding_rest()));
+                break;
+            }
+
+            elements.push(Some(self.parse_binding_element()));
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)));
+
+        ArrayPattern { elements }
+    }
+
+    /// Section 14.3.3 [ObjectBindingPattern](https://tc39.es/ecma262/#prod-ObjectBindingPattern)
+    pub(crate) fn parse_object_binding_pattern(&mut self) -> ObjectPattern {
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        let mut props = Vec::new();
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            let start = self.start();
+
+            // The rest property must be the last property of the pattern and can only bind an identifier.
+            if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Spread))) {
+                let id_start = self.start();
+                let id = PatternKind::Id(self.parse_binding_identifier());
+                let id = Pattern::new(id, self.finish(id_start));
+                props.push(ObjectPatternProp::new(ObjectPatternPropKind::Rest(Box::new(id)), self.finish(start)));
+                break;
+            }
+
+            let token = self.peek();
+            let key = self.parse_prop_name();
+
+            let prop = if self.eat(TokenKind::Punc(PuncKind::Colon)) {
+                let value = self.parse_binding_element();
+                ObjectPatternPropKind::KeyValue(KeyValuePatternProp { key, value })
+            } else if let PropNameKind::Id(id) = key {
+                self.check_shorthand(token);
+                self.check_strict_target(id, token.span);
+                let mut default = None;
+                if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Assign))) {
+                    default = Some(self.parse_assign_expr());
+                }
+                ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id, default })
+            } else {
+                self.error_and_recover(self.unexpected());
+                break;
+            };
+            props.push(ObjectPatternProp::new(prop, self.finish(start)));
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        ObjectPattern { props }
+    }
+
+    /// Reinterprets an already parsed expression as the target of an assignment, or the head of a for-in or for-of statement.
+    ///
+    /// Section 13.15.5 [Destructuring Assignment](https://tc39.es/ecma262/#sec-destructuring-assignment)
+    pub(crate) fn reinterpret_as_pattern(&mut self, expr: Expr) -> Pattern {
+        let span = expr.span;
+        match expr.kind {
+            ExprKind::Primary(PrimaryExprKind::ArrayLiteral(arr)) => {
+                if let Some(Some(SpreadableKind::Spread(rest))) = arr.elements.last() {
+                    self.report_rest_comma(rest.span, span);
+                }
+                Pattern::new(PatternKind::Array(self.reinterpret_array_lit(arr)), span)
+            }
+            ExprKind::Primary(PrimaryExprKind::ObjectLiteral(obj)) => {
+                if let Some(rest) = obj.props.last() && matches!(rest.kind, PropKind::Spread(_)) {
+                    self.report_rest_comma(rest.span, span);
+                }
+                Pattern::new(PatternKind::Object(self.reinterpret_object_lit(obj)), span)
+            }
+            kind => self.reinterpret_as_simple_target(Expr::new(kind, span)),
+        }
+    }
+
+    /// Reinterprets an expression as an identifier or member target, the only targets of compound assignments.
+    pub(crate) fn reinterpret_as_simple_target(&mut self, expr: Expr) -> Pattern {
+        let span = expr.span;
+        match expr.kind {
+            ExprKind::Primary(PrimaryExprKind::Id(id)) => {
+                self.check_strict_target(id, span);
+                Pattern::new(PatternKind::Id(id), span)
+            }
+            ExprKind::Member(member) if !member.optional && !is_optional_chain(&member.object) => {
+                Pattern::new(PatternKind::Member(member), span)
+            }
+            // Parentheses may only wrap simple targets, as in `(a) = b`.
+            ExprKind::Paren(ParenExpr { expr }) => self.reinterpret_as_simple_target(*expr),
+            _ => {
+                self.push_error_at(InvalidAssignmentTarget, span);
+                Pattern::new(PatternKind::Id(sym::EMPTY), span)
+            }
+        }
+    }
+
+    /// Reinterprets the contents of a parenthesized expression as the parameters of an arrow function, which can only
+    /// bind identifiers.
+    ///
+    /// Section 15.3.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-arrow-function-definitions-static-semantics-early-errors)
+    pub(crate) fn reinterpret_as_params(&mut self, items: Vec<SpreadableKind>) -> Vec<Pattern> {
+        // Unlike assignment targets, parameters cannot be parenthesized, as in `((a)) => a`.
+        for item in &items {
+            if let SpreadableKind::Expr(expr) | SpreadableKind::Spread(expr) = item
+                && matches!(expr.kind, ExprKind::Paren(_))
+            {
+                self.push_error_at(InvalidAssignmentTarget, expr.span);
+            }
+        }
+
+        let arr = ArrayLit { elements: items.into_iter().map(Some).collect() };
+        let params: Vec<Pattern> = self.reinterpret_array_lit(arr).elements.into_iter().flatten().collect();
+        for param in &params {
+            self.check_binding_pattern(param);
+        }
+        params
+    }
+
+    /// Reports any member targets in a pattern reinterpreted as a binding, as in `(a.b) => a`.
+    fn check_binding_pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::Id(_) => {}
+            PatternKind::Member(_) => self.push_error_at(InvalidAssignmentTarget, pattern.span),
+            PatternKind::Object(obj) => {
+                for prop in &obj.props {
+                    match &prop.kind {
+                        ObjectPatternPropKind::KeyValue(kv) => self.check_binding_pattern(&kv.value),
+                        ObjectPatternPropKind::Shorthand(_) => {}
+                        ObjectPatternPropKind::Rest(rest) => self.check_binding_pattern(rest),
+                    }
+                }
+            }
+            PatternKind::Array(arr) => {
+                for element in arr.elements.iter().flatten() {
+                    self.check_binding_pattern(element);
+                }
+            }
+            PatternKind::Rest(rest) => self.check_binding_pattern(rest),
+            PatternKind::Assign(assign) => self.check_binding_pattern(&assign.target),
+        }
+    }
+
+    /// Section 13.4.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-update-expressions-static-semantics-early-errors)
+    ///
+    /// Reports the operand of an update expression unless it is a simple target, as in `1++` or `a?.b++`.
+    pub(crate) fn check_update_target(&mut self, arg: &Expr) {
+        if !is_simple_target(arg) {
+            self.push_error_at(InvalidAssignmentTarget, arg.span);
+        }
+
+        let mut target = arg;
+        while let ExprKind::Paren(ParenExpr { expr }) = &target.kind {
+            target = expr;
+        }
+        if let ExprKind::Primary(PrimaryExprKind::Id(id)) = target.kind {
+            self.check_strict_target(id, target.span);
+        }
+    }
+
+    /// Reinterprets an array or object element, where an assignment becomes a default value.
+    fn reinterpret_as_element(&mut self, expr: Expr) -> Pattern {
+        let span = expr.span;
+        match expr.kind {
+            ExprKind::Assign(AssignExpr { op: OpKind::Assign, target, value }) => {
+                Pattern::new(PatternKind::Assign(AssignPattern { target, default: value }), span)
+            }
+            kind => self.reinterpret_as_pattern(Expr::new(kind, span)),
+        }
+    }
+
+    fn reinterpret_array_lit(&mut self, arr: ArrayLit) -> ArrayPattern {
+        let len = arr.elements.len();
+        let mut elements = Vec::with_capacity(len);
+
+        for (i, element) in arr.elements.into_iter().enumerate() {
+            let pattern = match element {
+                None => None,
+                Some(SpreadableKind::Expr(expr)) => Some(self.reinterpret_as_element(expr)),
+                Some(SpreadableKind::Spread(expr)) => {
+                    // The rest element must be last and cannot have a default value.
+                    if i + 1 != len || matches!(expr.kind, ExprKind::Assign(_)) {
+                        self.push_error_at(InvalidAssignmentTarget, expr.span);
+                    }
+                    let span = expr.span;
+                    Some(Pattern::new(PatternKind::Rest(Box::new(self.reinterpret_as_pattern(expr))), span))
+                }
+            };
+            elements.push(pattern);
+        }
+
+        ArrayPattern { elements }
+    }
+
+    fn reinterpret_object_lit(&mut self, obj: ObjectLit) -> ObjectPattern {
+        let len = obj.props.len();
+        let mut props = Vec::with_capacity(len);
+
+        for (i, prop) in obj.props.into_iter().enumerate() {
+            let span = prop.span;
+            let prop = match prop.kind {
+                PropKind::KeyValue(kv) => {
+                    let value = self.reinterpret_as_element(kv.value);
+                    ObjectPatternPropKind::KeyValue(KeyValuePatternProp { key: kv.key, value })
+                }
+                PropKind::Shorthand(id) => {
+                    self.check_strict_target(id, span);
+                    ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id, default: None })
+                }
+                PropKind::CoverInit(cover) => {
+                    self.check_strict_target(cover.id, span);
+                    ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id: cover.id, default: Some(*cover.init) })
+                }
+                PropKind::Spread(expr) => {
+                    // The rest property must be last and can only assign to a simple target.
+                    if i + 1 != len {
+                        self.push_error_at(InvalidAssignmentTarget, span);
+                    }
+                    ObjectPatternPropKind::Rest(Box::new(self.reinterpret_as_simple_target(expr)))
+                }
+                PropKind::Method(_) => {
+                    self.push_error_at(InvalidAssignmentTarget, span);
+                    continue;
+                }
+            };
+            props.push(ObjectPatternProp::new(prop, span));
+        }
+
+        ObjectPattern { props }
+    }
+
+    /// Reports a comma after the rest element at the end of a literal reinterpreted as a pattern, as recorded by
+    /// [Parser::parse_array_lit] and [Parser::parse_object_lit].
+    fn report_rest_comma(&mut self, rest: Span, literal: Span) {
+        let comma = self.rest_commas.iter().position(|comma| rest.end <= comma.start && comma.end <= literal.end);
+        if let Some(i) = comma {
+            let comma = self.rest_commas.swap_remove(i);
+            self.push_error_at(UnexpectedToken(TokenKind::Punc(PuncKind::Comma), Vec::new()), comma);
+        }
+    }
+
+    /// Reports the `=` of any `{ a = b }` properties parsed since `start` which were not reinterpreted as assignment
+    /// patterns.
+    pub(crate) fn report_cover_inits(&mut self, start: usize) {
+        for span in self.cover_inits.split_off(start) {
+            self.push_error_at(UnexpectedToken(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), Vec::new()), span);
+        }
+    }
+}
+
+/// Collects the identifiers bound by a pattern, in source order, along with the span of the pattern or property that
+/// binds each.
+///
+/// Section 8.2.1 [BoundNames](https://tc39.es/ecma262/#sec-static-semantics-boundnames)
+pub(crate) fn collect_bound_names(pattern: &Pattern, names: &mut Vec<(Symbol, Span)>) {
+    match &pattern.kind {
+        PatternKind::Id(id) => names.push((*id, pattern.span)),
+        PatternKind::Object(obj) => {
+            for prop in &obj.props {
+                match &prop.kind {
+                    ObjectPatternPropKind::KeyValue(kv) => collect_bound_names(&kv.value, names),
+                    ObjectPatternPropKind::Shorthand(shorthand) => names.push((shorthand.id, prop.span)),
+                    ObjectPatternPropKind::Rest(rest) => collect_bound_names(rest, names),
+                }
+            }
+        }
+        PatternKind::Array(arr) => {
+            for element in arr.elements.iter().flatten() {
+                collect_bound_names(element, names);
+            }
+        }
+        PatternKind::Rest(rest) => collect_bound_names(rest, names),
+        PatternKind::Assign(assign) => collect_bound_names(&assign.target, names),
+        // Member targets only appear in assignment expressions, which bind nothing.
+        PatternKind::Member(_) => {}
+    }
+}
+
+/// Checks if the expression is an identifier or member target, which [Parser::reinterpret_as_simple_target] accepts.
+fn is_simple_target(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Primary(PrimaryExprKind::Id(_)) => true,
+        ExprKind::Member(member) => !member.optional && !is_optional_chain(&member.object),
+        ExprKind::Paren(ParenExpr { expr }) => is_simple_target(expr),
+        _ => false,
+    }
+}
+
+/// Checks if the expression is part of an optional chain such as `a?.b.c`, which cannot be assigned to.
+fn is_optional_chain(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Member(member) => member.optional || is_optional_chain(&member.object),
+        ExprKind::Call(call) => call.optional || is_optional_chain(&call.callee),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{ArrayLit, ArrayPattern, AssignExpr, AssignPattern, ConstDecl, DeclKind, Expr, ExprKind, ExprStmt, KeyValuePatternProp, LexicalKind, LitValue, MemberExpr, MemberPropKind, ObjectPattern, ObjectPatternProp, ObjectPatternPropKind, ParenExpr, Pattern, PatternKind, PrimaryExprKind, PropNameKind, ShorthandPatternProp, SpreadableKind, Stmt, StmtKind, VarDeclarator};
+    use crate::token::{OpKind, PuncKind, TokenKind};
+    use crate::span::Span;
+    use crate::test_utils::{assert_ast_eq, error_count, id, parse_single, pat, IntoNode};
+    use super::{InvalidAssignmentTarget, Parser, UnexpectedToken};
+    use crate::ParseErrorKind::StrictEvalOrArguments;
+
+    fn assign(op: OpKind, target: Pattern, value: Expr) -> Stmt {
+        StmtKind::Expression(ExprStmt(ExprKind::Assign(AssignExpr { op, target: Box::new(target), value: Box::new(value) }).node())).node()
+    }
+
+    fn shorthand(name: &str, default: Option<Expr>) -> ObjectPatternProp {
+        ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id: name.into(), default }).node()
+    }
+
+    #[test]
+    fn test_binding_pattern() {
+        assert_ast_eq!(
+            parse_single("const {a, b: [c, ...d] = []} = obj;"),
+            StmtKind::Decl(DeclKind::Lexical(LexicalKind::Const(ConstDecl {
+                declarators: vec![VarDeclarator {
+                    id: PatternKind::Object(ObjectPattern {
+                        props: vec![
+                            shorthand("a", None),
+                            ObjectPatternPropKind::KeyValue(KeyValuePatternProp {
+                                key: PropNameKind::Id("b".into()),
+                                value: PatternKind::Assign(AssignPattern {
+                                    target: Box::new(PatternKind::Array(ArrayPattern {
+                                        elements: vec![Some(pat("c")), Some(PatternKind::Rest(Box::new(pat("d"))).node())],
+                                    }).node()),
+                                    default: Box::new(ExprKind::Primary(PrimaryExprKind::ArrayLiteral(ArrayLit { elements: vec![] })).node()),
+                                }).node(),
+                            }).node(),
+                        ],
+                    }).node(),
+                    init: Some(id("obj")),
+                    span: Span::default(),
+                }],
+            }))).node()
+        );
+    }
+
+    #[test]
+    fn test_assignment() {
+        assert_ast_eq!(
+            parse_single("a = b = c"),
+            assign(OpKind::Assign, pat("a"), ExprKind::Assign(AssignExpr {
+                op: OpKind::Assign,
+                target: Box::new(pat("b")),
+                value: Box::new(id("c")),
+            }).node())
+        );
+        assert_ast_eq!(
+            parse_single("a.b += 1"),
+            assign(
+                OpKind::AdditonAssign,
+                PatternKind::Member(MemberExpr { object: Box::new(id("a")), prop: MemberPropKind::Id("b".into()), optional: false }).node(),
+                ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(1.0))).node(),
+            )
+        );
+        assert_ast_eq!(parse_single("(a) = b"), assign(OpKind::Assign, pat("a"), id("b")));
+
+        assert_eq!(error_count("a + b = c"), 1);
+        assert_eq!(error_count("a?.b = c"), 1);
+        assert_eq!(error_count("[a] += b"), 1);
+        assert_eq!(error_count("([a]) = b"), 1);
+    }
+
+    #[test]
+    fn test_destructuring_assignment() {
+        assert_ast_eq!(
+            parse_single("[x, y] = [y, x]"),
+            assign(
+                OpKind::Assign,
+                PatternKind::Array(ArrayPattern { elements: vec![Some(pat("x")), Some(pat("y"))] }).node(),
+                ExprKind::Primary(PrimaryExprKind::ArrayLiteral(ArrayLit {
+                    elements: vec![Some(SpreadableKind::Expr(id("y"))), Some(SpreadableKind::Expr(id("x")))],
+                })).node(),
+            )
+        );
+        assert_ast_eq!(
+            parse_single("({a = 1, b: [c = 2], ...d} = obj)"),
+            StmtKind::Expression(ExprStmt(ExprKind::Paren(ParenExpr {
+                expr: Box::new(ExprKind::Assign(AssignExpr {
+                    op: OpKind::Assign,
+                    target: Box::new(PatternKind::Object(ObjectPattern {
+                        props: vec![
+                            shorthand("a", Some(ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(1.0))).node())),
+                            ObjectPatternPropKind::KeyValue(KeyValuePatternProp {
+                                key: PropNameKind::Id("b".into()),
+                                value: PatternKind::Array(ArrayPattern {
+                                    elements: vec![Some(PatternKind::Assign(AssignPattern {
+                                        target: Box::new(pat("c")),
+                                        default: Box::new(ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(2.0))).node()),
+                                    }).node())],
+                                }).node(),
+                            }).node(),
+                            ObjectPatternPropKind::Rest(Box::new(pat("d"))).node(),
+                        ],
+                    }).node()),
+                    value: Box::new(id("obj")),
+                }).node()),
+            }).node())).node()
+        );
+
+        // Initializers in object literals are only valid once reinterpreted as a pattern.
+        assert_eq!(error_count("({a = 1})"), 1);
+        assert_eq!(error_count("[{a = 1}]"), 1);
+        assert_eq!(error_count("f({a = 1})"), 1);
+        assert_eq!(error_count("[...a, b] = c"), 1);
+        assert_eq!(error_count("({a() {}} = b)"), 1);
+
+        // Unlike in a literal, a comma cannot follow the rest element of a pattern.
+        assert_eq!(error_count("({...a,} = b)"), 1);
+        assert_eq!(error_count("[...a,] = b"), 1);
+        assert_eq!(error_count("[[...a,]] = b"), 1);
+        assert_eq!(error_count("x = {...a,}, [...b,]"), 0);
+    }
+
+    #[test]
+    fn test_cover_init_span() {
+        // The error is at the `=` of the property, not where the literal turned out not to be a pattern.
+        for (source, start) in [("({a = 1})", 4), ("f({b, a = 1});", 8), ("x = [{a = 1}, {b = 2}].c", 8)] {
+            let errors = Parser::init(source).parse("test").errors;
+            assert!(matches!(errors[0].kind, UnexpectedToken(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), _)), "{}", source);
+            assert_eq!(errors[0].span, Span::new(start, start + 1), "{}", source);
+        }
+        assert_eq!(error_count("x = [{a = 1}, {b = 2}].c"), 2);
+
+        let errors = Parser::init("({...a,} = b)").parse("test").errors;
+        assert_eq!(errors[0].span, Span::new(6, 7));
+    }
+
+    #[test]
+    fn test_invalid_target_span() {
+        let mut p = Parser::init("[a, b + c] = d");
+        let errors = p.parse("test").errors;
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, InvalidAssignmentTarget));
+        assert_eq!(errors[0].span, Span::new(4, 9));
+    }
+
+    #[test]
+    fn test_strict_eval_and_arguments() {
+        for (source, start, name) in [
+            ("eval = 1;", 0, "eval"),
+            ("arguments++;", 0, "arguments"),
+            ("--(eval);", 3, "eval"),
+            ("[a, arguments] = b;", 4, "arguments"),
+            ("({ eval } = a);", 3, "eval"),
+            ("(eval) => 1;", 1, "eval"),
+            ("function f(arguments) {}", 11, "arguments"),
+            ("let { a: eval } = b;", 9, "eval"),
+            ("import { eval } from \"a\";", 9, "eval"),
+        ] {
+            let errors = Parser::init(source).parse("test").errors;
+            assert_eq!(errors.len(), 1, "{}", source);
+            assert!(matches!(&errors[0].kind, StrictEvalOrArguments(found) if found == name), "{}", source);
+            assert_eq!(errors[0].span, Span::new(start, start + name.len()), "{}", source);
+        }
+
+        // Outside of strict mode code they are ordinary names, and in any code they can be referenced or be labels.
+        assert!(Parser::init_script("eval = 1; arguments++; function eval(arguments) {}").parse("test").errors.is_empty());
+        assert!(Parser::init("eval(a); arguments[0] = 1; eval: ;").parse("test").errors.is_empty());
+    }
+}
@@ -1,20 +1,34 @@
-use crate::ast::DeclKind::{Class, Lexical};
+use crate::ast::DeclKind::{Class, Hoistable, Lexical};
 use crate::ast::LexicalKind::{Const, Let};
-use crate::ast::{BlockStmt, BreakStmt, BreakableStmtKind, ConstDecl, ContinueStmt, DebugStmt, EmptyStmt, ExprKind, ExprStmt, IfStmt, LetDecl, ReturnStmt, StmtKind, SwitchStmt, ThrowStmt, TryStmt, WithStmt};
-use crate::ast::StmtKind::Expression;
+use crate::ast::{BlockStmt, BreakStmt, BreakableStmtKind, CatchClause, ConstDecl, ContinueStmt, DebugStmt, DoWhileStmt, EmptyStmt, ExprStmt, ForHeadKind, ForInStmt, ForInitKind, ForOfStmt, ForStmt, IfStmt, IterStmtKind, LabelledStmt, LetDecl, Pattern, PatternKind, ReturnStmt, Stmt, StmtKind, SwitchCase, SwitchStmt, ThrowStmt, TryStmt, VarDeclarator, VarStmt, WhileStmt, WithStmt};
+use crate::grammar::pattern::collect_bound_names;
 use crate::ParseErrorKind::UnexpectedToken;
-use crate::Parser;
-use crate::token::{BraceKind, KeywordKind, OpKind, PuncKind, TokenKind};
+use crate::{Label, ParseErrorKind, Parser};
+use crate::symbol::{sym, Symbol};
+use crate::token::{BraceKind, BracketKind, KeywordKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
 use crate::token::TokenKind::Keyword;
 
-impl Parser {
+impl Parser<'_> {
 
     /// Section 14 [Statement](https://tc39.es/ecma262/#sec-ecmascript-language-statements-and-declarations)
-    pub(crate) fn parse_stmt(&mut self) -> Option<StmtKind> {
+    pub(crate) fn parse_stmt(&mut self) -> Option<Stmt> {
+        let start = self.start();
+        let kind = self.nested(Self::parse_stmt_kind)??;
+        Some(Stmt::new(kind, self.finish(start)))
+    }
+
+    fn parse_stmt_kind(&mut self) -> Option<StmtKind> {
 
         match self.peek().kind {
             TokenKind::Eof => None,
-            TokenKind::Id(id) => todo!(),
+            TokenKind::Id(_) if self.is_async_function_ahead() => {
+                Some(StmtKind::Decl(Hoistable(self.parse_function_decl())))
+            }
+            TokenKind::Id(_) if self.is_let_decl_ahead() => Some(StmtKind::Decl(Lexical(Let(self.parse_let_decl_stmt())))),
+            TokenKind::Id(_) if self.peek_nth_kind(1) == TokenKind::Punc(PuncKind::Colon) => {
+                Some(StmtKind::Labelled(self.parse_labelled_stmt()))
+            }
+            TokenKind::Id(_) => Some(StmtKind::Expression(self.parse_expr_stmt())),
             TokenKind::Keyword(k) => {
                 match k {
                     KeywordKind::Break => Some(StmtKind::Break(self.parse_break_stmt())),
@@ -22,20 +36,34 @@ impl Parser {
                     KeywordKind::Const => Some(StmtKind::Decl(Lexical(Const(self.parse_const_decl_stmt())))),
                     KeywordKind::Continue => Some(StmtKind::Continue(self.parse_continue_stmt())),
                     KeywordKind::Debugger => Some(StmtKind::Debugger(self.parse_debugger_stmt())),
-                    KeywordKind::Let => Some(StmtKind::Decl(Lexical(Let(self.parse_let_decl_stmt())))),
+                    KeywordKind::Do => Some(StmtKind::Breakable(BreakableStmtKind::Iter(self.parse_do_while_stmt()))),
+                    KeywordKind::For => Some(StmtKind::Breakable(BreakableStmtKind::Iter(self.parse_for_stmt()))),
+                    KeywordKind::Function => Some(StmtKind::Decl(Hoistable(self.parse_function_decl()))),
                     KeywordKind::If => Some(StmtKind::If(self.parse_if_stmt())),
                     KeywordKind::Return => Some(StmtKind::Return(self.parse_return_stmt())),
                     KeywordKind::Switch => Some(StmtKind::Breakable(BreakableStmtKind::Switch(self.parse_switch_stmt()))),
                     KeywordKind::This => Some(StmtKind::Expression(self.parse_expr_stmt())),
                     KeywordKind::Throw => Some(StmtKind::Throw(self.parse_throw_stmt())),
+                    KeywordKind::Try => Some(StmtKind::Try(self.parse_try_stmt())),
+                    KeywordKind::While => Some(StmtKind::Breakable(BreakableStmtKind::Iter(self.parse_while_stmt()))),
+                    // Import and export declarations are only allowed at the top level of a module.
+                    KeywordKind::Export => {
+                        self.error_and_recover(self.unexpected());
+                        None
+                    }
+                    KeywordKind::Import if self.is_import_decl_ahead() => {
+                        self.error_and_recover(self.unexpected());
+                        None
+                    }
+                    KeywordKind::Var => Some(StmtKind::Variable(self.parse_var_stmt())),
+                    KeywordKind::With => Some(StmtKind::With(self.parse_with_stmt())),
                     _ => Some(StmtKind::Expression(self.parse_expr_stmt())),
                 }
             }
-            TokenKind::LineTerminator(_) => {
-                self.bump();
-                None
+            // A private name starts an expression in `#x in obj`, and is reported as unexpected otherwise.
+            TokenKind::Lit(_) | TokenKind::Template(_) | TokenKind::PrivateId(_) => {
+                Some(StmtKind::Expression(self.parse_expr_stmt()))
             }
-            TokenKind::Lit(_) => Some(StmtKind::Expression(self.parse_expr_stmt())),
             TokenKind::Punc(punc) => {
                 match punc {
                     PuncKind::SemiColon => Some(StmtKind::Empty(self.parse_empty_stmt())),
@@ -43,27 +71,79 @@ impl Parser {
                     _ => Some(StmtKind::Expression(self.parse_expr_stmt())),
                 }
             }
-            TokenKind::Unicode(_) => {
-                self.bump();
-                None
-            }
+            // The token is skipped by the enclosing statement list once it resynchronizes.
             _ => {
-                self.bump();
+                self.error_and_recover(self.unexpected());
                 None
-            },
+            }
         }
     }
 
-    pub(crate) fn parse_stmt_list(&mut self) -> StmtKind {
-        match self.peek().kind {
-            TokenKind::Keyword(k) => match k {
-                KeywordKind::Const => StmtKind::Decl(Lexical(Const(self.parse_const_decl_stmt()))),
-                KeywordKind::Let => StmtKind::Decl(Lexical(Let(self.parse_let_decl_stmt()))),
-                KeywordKind::If => StmtKind::If(self.parse_if_stmt()),
-                _ => todo!()
+    /// Parses an item of a statement list with the provided parse function, such as a statement in a block. After a
+    /// syntax error, skips to where parsing can resume and returns what was parsed of the statement, or an
+    /// [StmtKind::Error] placeholder if nothing was.
+    pub(crate) fn parse_stmt_list_item(&mut self, parse: fn(&mut Self) -> Option<Stmt>) -> Option<Stmt> {
+        let start = self.start();
+        let pos = self.pos();
+        let cover_inits = self.cover_inits.len();
+
+        let stmt = parse(self);
+        if !self.recovering {
+            return stmt;
+        }
+
+        let stmt = stmt.unwrap_or_else(|| Stmt::new(StmtKind::Error, self.finish(start)));
+        self.cover_inits.truncate(cover_inits);
+        self.synchronize(self.pos() != pos);
+        Some(stmt)
+    }
+
+    /// Ends recovery from a syntax error by skipping tokens until after a `;`, or before a `}` closing the enclosing
+    /// block or a keyword starting the next statement. At least one token is skipped unless `progressed` is set, so
+    /// that a token which can not start a statement is never parsed again.
+    pub(crate) fn synchronize(&mut self, progressed: bool) {
+        self.recovering = false;
+        self.expected.clear();
+
+        let mut progressed = progressed;
+        let mut depth = 0;
+        loop {
+            match self.peek_kind() {
+                TokenKind::Eof => return,
+                TokenKind::Punc(PuncKind::SemiColon) if depth == 0 => {
+                    self.bump();
+                    return;
+                }
+                TokenKind::Punc(PuncKind::Brace(BraceKind::Right)) if depth == 0 => {
+                    if !progressed {
+                        self.bump();
+                    }
+                    return;
+                }
+                Keyword(k) if depth == 0 && progressed && is_stmt_keyword(k) => return,
+                TokenKind::Id(_) if depth == 0 && progressed && self.is_let_decl_ahead() => return,
+                TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => depth += 1,
+                TokenKind::Punc(PuncKind::Brace(BraceKind::Right)) => depth -= 1,
+                _ => {}
+            }
+            self.bump();
+            // A lexer error in the skipped tokens is reported, but does not stop the skipping.
+            self.recovering = false;
+            progressed = true;
+        }
+    }
+
+    /// Parses the single statement forming the body of a compound statement such as `if`, `while` or `for`.
+    pub(crate) fn parse_sub_stmt(&mut self) -> Stmt {
+        loop {
+            if self.is_end() {
+                self.error_and_recover(self.unexpected());
+                return Stmt::new(StmtKind::Empty(EmptyStmt), self.finish(self.start()));
+            }
+
+            if let Some(stmt) = self.parse_stmt() {
+                return stmt;
             }
-            TokenKind::Punc(PuncKind::Brace(BraceKind::Left)) => StmtKind::Block(self.parse_block_stmt()),
-            _ => todo!()
         }
     }
 
@@ -74,8 +154,10 @@ impl Parser {
 
         let mut stmts = Vec::new();
 
-        while self.peek().kind == TokenKind::Punc(PuncKind::Brace(BraceKind::Right)) {
-            stmts.push(self.parse_stmt_list());
+        while !self.at(TokenKind::Punc(PuncKind::Brace(BraceKind::Right))) && !self.is_end() {
+            if let Some(stmt) = self.parse_stmt_list_item(Self::parse_stmt) {
+                stmts.push(stmt);
+            }
         }
 
         self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
@@ -83,50 +165,67 @@ impl Parser {
         BlockStmt { stmts}
     }
 
+    /// Checks if `let` starts a lexical declaration. Outside of strict mode code, `let` is also an identifier, as in
+    /// `let = 1`, unless followed by a binding.
+    pub(crate) fn is_let_decl_ahead(&self) -> bool {
+        self.at_id(sym::LET)
+            && (self.strict
+                || matches!(
+                    self.peek_nth_kind(1),
+                    TokenKind::Id(_) | TokenKind::Punc(PuncKind::Bracket(BracketKind::Left) | PuncKind::Brace(BraceKind::Left))
+                ))
+    }
+
     /// Section 14.3.1 [Let and Const Declarations](https://tc39.es/ecma262/#sec-let-and-const-declarations)
     pub(crate) fn parse_let_decl_stmt(&mut self) -> LetDecl {
+        self.expect(TokenKind::Id(sym::LET));
 
-        self.expect(Keyword(KeywordKind::Let));
-
-        let mut id = String::new();
-        match self.next_kind() {
-            TokenKind::Id(val) => {id = val }
-            t => self.set_fatal_error(UnexpectedToken(t))
+        let declarators = self.parse_var_declarators(false);
+        for declarator in &declarators {
+            self.check_lexical_binding(&declarator.id);
         }
 
-        self.expect(TokenKind::Punc(PuncKind::Op(OpKind::Assign)));
-
-        let let_decl = LetDecl {
-            identifier: id,
-            expression: self.parse_expr()
-        };
+        self.consume_semicolon();
 
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
-
-        let_decl
+        LetDecl { declarators }
     }
 
     /// Section 14.3.1 [Let and Const Declarations](https://tc39.es/ecma262/#sec-let-and-const-declarations)
     pub(crate) fn parse_const_decl_stmt(&mut self) -> ConstDecl {
-
         self.expect(Keyword(KeywordKind::Const));
 
-        let mut id = String::new();
-        match self.next().kind {
-            TokenKind::Id(val) => {id = val }
-            t => self.push_error(UnexpectedToken(t))
+        let declarators = self.parse_var_declarators(true);
+        for declarator in &declarators {
+            self.check_lexical_binding(&declarator.id);
         }
 
-        self.expect(TokenKind::Punc(PuncKind::Op(OpKind::Assign)));
+        self.consume_semicolon();
 
-        let const_decl = ConstDecl {
-            identifier: id,
-            expression: self.parse_expr()
-        };
+        ConstDecl { declarators }
+    }
 
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+    /// Section 14.3.1.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-let-and-const-declarations-static-semantics-early-errors)
+    ///
+    /// Reports `let` bound by a let or const declaration, which it can not be even where it is an identifier.
+    fn check_lexical_binding(&mut self, id: &Pattern) {
+        let mut names = Vec::new();
+        collect_bound_names(id, &mut names);
+        for (name, span) in names {
+            if name == sym::LET {
+                self.push_error_at(ParseErrorKind::LexicallyBoundLet, span);
+            }
+        }
+    }
+
+    /// Section 14.3.2 [Variable Statement](https://tc39.es/ecma262/#sec-variable-statement)
+    pub(crate) fn parse_var_stmt(&mut self) -> VarStmt {
+        self.expect(Keyword(KeywordKind::Var));
+
+        let declarators = self.parse_var_declarators(false);
+
+        self.consume_semicolon();
 
-        const_decl
+        VarStmt { declarators }
     }
 
     /// Section 14.4 [Empty Statement](https://tc39.es/ecma262/#sec-empty-statement)
@@ -138,7 +237,7 @@ impl Parser {
     /// Section 14.5 [Expression Statement](https://tc39.es/ecma262/#sec-expression-statement)
     pub(crate) fn parse_expr_stmt(&mut self) -> ExprStmt {
         let expr = self.parse_expr();
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+        self.consume_semicolon();
         ExprStmt(expr)
     }
 
@@ -149,47 +248,277 @@ impl Parser {
 
         let cond = self.parse_paren_expr();
 
-        let body = Box::new(self.parse_stmt_list());
+        let body = Box::new(self.parse_sub_stmt());
 
         let mut alternative = None;
         if self.eat(Keyword(KeywordKind::Else)) {
-            alternative = Some(Box::new(self.parse_stmt_list()));
+            alternative = Some(Box::new(self.parse_sub_stmt()));
         }
 
         IfStmt { cond, body, alternative }
     }
 
+    /// Section 14.7.2 [The do-while Statement](https://tc39.es/ecma262/#sec-do-while-statement)
+    pub(crate) fn parse_do_while_stmt(&mut self) -> IterStmtKind {
+        self.expect(Keyword(KeywordKind::Do));
+
+        let body = Box::new(self.parse_loop_body());
+
+        self.expect(Keyword(KeywordKind::While));
+
+        let cond = self.parse_paren_expr();
+
+        // A semicolon is always inserted after a do-while statement if missing.
+        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+
+        IterStmtKind::DoWhile(DoWhileStmt { body, cond })
+    }
+
+    /// Section 14.7.3 [The while Statement](https://tc39.es/ecma262/#sec-while-statement)
+    pub(crate) fn parse_while_stmt(&mut self) -> IterStmtKind {
+        self.expect(Keyword(KeywordKind::While));
+
+        let cond = self.parse_paren_expr();
+
+        let body = Box::new(self.parse_loop_body());
+
+        IterStmtKind::While(WhileStmt { cond, body })
+    }
+
+    /// Section 14.7.4 [The for Statement](https://tc39.es/ecma262/#sec-for-statement) and
+    /// Section 14.7.5 [The for-in, for-of, and for-await-of Statements](https://tc39.es/ecma262/#sec-for-in-and-for-of-statements)
+    pub(crate) fn parse_for_stmt(&mut self) -> IterStmtKind {
+        self.expect(Keyword(KeywordKind::For));
+
+        let is_await = self.allow_await && self.eat(TokenKind::Id(sym::AWAIT));
+
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)));
+
+        let mut init = None;
+
+        type DeclKinds = (fn(Pattern) -> ForHeadKind, fn(Vec<VarDeclarator>) -> ForInitKind);
+        let is_const = self.peek_kind() == Keyword(KeywordKind::Const);
+        let is_lexical = is_const || self.is_let_decl_ahead();
+        let decl: Option<DeclKinds> = match self.peek_kind() {
+            Keyword(KeywordKind::Var) => Some((ForHeadKind::Var, ForInitKind::Var)),
+            Keyword(KeywordKind::Const) => Some((ForHeadKind::Const, ForInitKind::Const)),
+            TokenKind::Id(_) if self.is_let_decl_ahead() => Some((ForHeadKind::Let, ForInitKind::Let)),
+            _ => None,
+        };
+
+        match self.peek_kind() {
+            TokenKind::Punc(PuncKind::SemiColon) => {}
+            _ if let Some((head, init_kind)) = decl => {
+                self.bump();
+
+                let id = self.parse_binding_target();
+
+                // A single binding without an initializer may be the head of a for-in or for-of statement.
+                if self.at_for_in_of() {
+                    if is_lexical {
+                        self.check_lexical_binding(&id);
+                    }
+                    return self.parse_for_in_of_rest(head(id), is_await);
+                }
+
+                let declarators = self.with_in(false, |p| p.parse_var_declarators_from(id, is_const));
+                if is_lexical {
+                    for declarator in &declarators {
+                        self.check_lexical_binding(&declarator.id);
+                    }
+                }
+                init = Some(init_kind(declarators));
+            }
+            _ => {
+                let cover_inits = self.cover_inits.len();
+                let expr = self.with_in(false, Self::parse_expr);
+
+                if self.at_for_in_of() {
+                    let left = self.reinterpret_as_pattern(expr);
+                    self.cover_inits.truncate(cover_inits);
+                    return self.parse_for_in_of_rest(ForHeadKind::Pattern(left), is_await);
+                }
+
+                self.report_cover_inits(cover_inits);
+
+                init = Some(ForInitKind::Expr(expr));
+            }
+        }
+
+        // Only for-of statements can be awaited.
+        if is_await {
+            self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::SemiColon));
+
+        let mut test = None;
+        if !self.at(TokenKind::Punc(PuncKind::SemiColon)) {
+            test = Some(self.parse_expr());
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::SemiColon));
+
+        let mut update = None;
+        if !self.at(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right))) {
+            update = Some(self.parse_expr());
+        }
+
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+
+        let body = Box::new(self.parse_loop_body());
+
+        IterStmtKind::For(ForStmt { init, test, update, body })
+    }
+
+    /// Parses the remainder of a for-in or for-of statement after its left side.
+    fn parse_for_in_of_rest(&mut self, left: ForHeadKind, is_await: bool) -> IterStmtKind {
+        if self.eat(Keyword(KeywordKind::In)) {
+            // Only for-of statements can be awaited.
+            if is_await {
+                self.push_error(UnexpectedToken(Keyword(KeywordKind::In), Vec::new()));
+            }
+
+            let right = self.parse_expr();
+            self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+            let body = Box::new(self.parse_loop_body());
+
+            return IterStmtKind::ForIn(ForInStmt { left, right, body });
+        }
+
+        self.expect(TokenKind::Id(sym::OF));
+
+        let right = self.parse_assign_expr();
+        self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+        let body = Box::new(self.parse_loop_body());
+
+        IterStmtKind::ForOf(ForOfStmt { left, right, body, is_await })
+    }
+
+    /// Parses the body of a loop, which `break` and `continue` without a label apply to.
+    fn parse_loop_body(&mut self) -> Stmt {
+        self.loop_depth += 1;
+        let body = self.parse_sub_stmt();
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn at_for_in_of(&self) -> bool {
+        matches!(self.peek_kind(), Keyword(KeywordKind::In)) || self.at_id(sym::OF)
+    }
+
+    /// Parses a comma separated list of declarators, each a binding with an optional initializer.
+    pub(crate) fn parse_var_declarators(&mut self, is_const: bool) -> Vec<VarDeclarator> {
+        let id = self.parse_binding_target();
+        self.parse_var_declarators_from(id, is_const)
+    }
+
+    /// Parses a comma separated list of declarators, continuing from the already parsed binding of the first.
+    /// Destructuring patterns and the bindings of const declarations must be initialized, except as the head of a
+    /// for-in or for-of statement, which is parsed before getting here.
+    pub(crate) fn parse_var_declarators_from(&mut self, id: Pattern, is_const: bool) -> Vec<VarDeclarator> {
+        let mut declarators = Vec::new();
+        let mut id = id;
+        loop {
+            let start = id.span.start;
+
+            let mut init = None;
+            if self.eat(TokenKind::Punc(PuncKind::Op(OpKind::Assign))) {
+                init = Some(self.parse_assign_expr());
+            } else if is_const || !matches!(id.kind, PatternKind::Id(_)) {
+                self.push_error(self.unexpected());
+            }
+
+            declarators.push(VarDeclarator { id, init, span: self.finish(start) });
+
+            if !self.eat(TokenKind::Punc(PuncKind::Comma)) {
+                return declarators;
+            }
+
+            id = self.parse_binding_target();
+        }
+    }
+
     /// Section 14.8 [Continue Statement](https://tc39.es/ecma262/#sec-continue-statement)
     pub(crate) fn parse_continue_stmt(&mut self) -> ContinueStmt {
+        let keyword = self.peek().span;
         self.expect(Keyword(KeywordKind::Continue));
 
-        // TODO: Check for label
-        let label = None;
+        let span = self.peek().span;
+        let label = self.parse_label_ref();
+        match label {
+            Some(name) => match self.labels.iter().rfind(|label| label.name == name) {
+                Some(label) if label.is_loop => {}
+                Some(_) => self.push_error_at(ParseErrorKind::IllegalContinue, span),
+                None => self.push_error_at(ParseErrorKind::UndefinedLabel(name.to_string()), span),
+            },
+            None if self.loop_depth == 0 => self.push_error_at(ParseErrorKind::IllegalContinue, keyword),
+            None => {}
+        }
 
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+        self.consume_semicolon();
 
         ContinueStmt { label }
     }
 
     /// Section 14.9 [Break Statement](https://tc39.es/ecma262/#sec-break-statement)
     pub(crate) fn parse_break_stmt(&mut self) -> BreakStmt {
+        let keyword = self.peek().span;
         self.expect(Keyword(KeywordKind::Break));
 
-        // TODO: Check for label
-        let label = None;
+        let span = self.peek().span;
+        let label = self.parse_label_ref();
+        match label {
+            Some(name) if !self.labels.iter().any(|label| label.name == name) => {
+                self.push_error_at(ParseErrorKind::UndefinedLabel(name.to_string()), span);
+            }
+            None if self.loop_depth == 0 && self.switch_depth == 0 => {
+                self.push_error_at(ParseErrorKind::IllegalBreak, keyword);
+            }
+            _ => {}
+        }
 
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+        self.consume_semicolon();
 
         BreakStmt { label }
     }
 
+    /// The optional label of a `break` or `continue`, which must be on the same line as the keyword.
+    fn parse_label_ref(&mut self) -> Option<Symbol> {
+        match self.peek_kind() {
+            TokenKind::Id(label) if !self.has_newline_before() => {
+                self.check_identifier(self.peek());
+                self.bump();
+                Some(label)
+            }
+            _ => None,
+        }
+    }
+
     /// Section 14.10 [Return Statement](https://tc39.es/ecma262/#sec-return-statement)
     pub(crate) fn parse_return_stmt(&mut self) -> ReturnStmt {
+        // Return statements are only allowed within functions.
+        if !self.allow_return {
+            self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+        }
         self.expect(Keyword(KeywordKind::Return));
 
-        // TODO: Check for void return as well.
+        // The return value must start on the same line as the `return`.
+        let has_value = !self.has_newline_before()
+            && !matches!(
+                self.peek_kind(),
+                TokenKind::Punc(PuncKind::SemiColon) | TokenKind::Punc(PuncKind::Brace(BraceKind::Right)) | TokenKind::Eof
+            );
+
+        if !has_value {
+            self.consume_semicolon();
+            return ReturnStmt { return_value: None };
+        }
+
         let expr = self.parse_expr();
 
+        self.consume_semicolon();
+
         ReturnStmt {
             return_value: Some(Box::new(expr))
         }
@@ -197,9 +526,13 @@ impl Parser {
 
     /// Section 14.11 [With Statement (LEGACY)](https://tc39.es/ecma262/#sec-with-statement)
     pub(crate) fn parse_with_stmt(&mut self) -> WithStmt {
+        // With statements are not allowed in strict mode code, including modules.
+        if self.strict {
+            self.push_error(ParseErrorKind::StrictWith);
+        }
         self.expect(Keyword(KeywordKind::With));
         let with_expr = self.parse_paren_expr();
-        let with_block = Box::new(self.parse_stmt_list());
+        let with_block = Box::new(self.parse_sub_stmt());
         WithStmt {
             with_expr,
             with_block,
@@ -210,13 +543,65 @@ impl Parser {
     pub(crate) fn parse_switch_stmt(&mut self) -> SwitchStmt {
         self.expect(Keyword(KeywordKind::Switch));
 
-        todo!()
+        let switch_expr = self.parse_paren_expr();
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)));
+
+        let mut cases = Vec::new();
+        let mut has_default = false;
+        self.switch_depth += 1;
+
+        loop {
+            let start = self.start();
+            let test = match self.peek_kind() {
+                Keyword(KeywordKind::Case) => {
+                    self.bump();
+                    Some(self.parse_expr())
+                }
+                Keyword(KeywordKind::Default) => {
+                    // Only a single default case is allowed.
+                    if has_default {
+                        self.push_error(UnexpectedToken(self.peek_kind(), Vec::new()));
+                    }
+                    has_default = true;
+                    self.bump();
+                    None
+                }
+                _ => break,
+            };
+
+            self.expect(TokenKind::Punc(PuncKind::Colon));
+
+            let mut body = Vec::new();
+            while !matches!(
+                self.peek_kind(),
+                Keyword(KeywordKind::Case) | Keyword(KeywordKind::Default) | TokenKind::Punc(PuncKind::Brace(BraceKind::Right))
+            ) && !self.is_end() {
+                if let Some(stmt) = self.parse_stmt_list_item(Self::parse_stmt) {
+                    body.push(stmt);
+                }
+            }
+
+            cases.push(SwitchCase { test, body, span: self.finish(start) });
+        }
+        self.switch_depth -= 1;
+
+        self.expect(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+
+        SwitchStmt { switch_expr, cases }
     }
 
     /// Section 14.14 [Throw Statement](https://tc39.es/ecma262/#sec-throw-statement)
     pub(crate) fn parse_throw_stmt(&mut self) -> ThrowStmt {
         self.expect(Keyword(KeywordKind::Throw));
+
+        // Unlike `return`, a line terminator after `throw` is an error rather than ending the statement.
+        if self.has_newline_before() {
+            self.error_and_recover(self.unexpected());
+        }
+
         let throws_expr = self.parse_expr();
+        self.consume_semicolon();
         ThrowStmt {
             throws_expr,
         }
@@ -230,8 +615,8 @@ impl Parser {
 
         let mut catch_block = None;
 
-        if self.eat(Keyword(KeywordKind::Catch)) {
-            // TODO: Handle catch
+        if self.at(Keyword(KeywordKind::Catch)) {
+            catch_block = Some(Box::new(self.parse_catch_clause()));
         }
 
         let mut finally_block = None;
@@ -240,6 +625,11 @@ impl Parser {
             finally_block = Some(Box::new(self.parse_block_stmt()));
         }
 
+        // A try statement requires at least one of a catch or finally block.
+        if catch_block.is_none() && finally_block.is_none() {
+            self.error_and_recover(self.unexpected());
+        }
+
 
         TryStmt {
             try_block,
@@ -248,33 +638,563 @@ impl Parser {
         }
     }
 
+    /// Section 14.15 [Catch](https://tc39.es/ecma262/#prod-Catch)
+    pub(crate) fn parse_catch_clause(&mut self) -> CatchClause {
+        let start = self.start();
+        self.expect(Keyword(KeywordKind::Catch));
+
+        let mut catch_param = None;
+
+        if self.eat(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))) {
+            catch_param = Some(self.parse_binding_target());
+            self.expect(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)));
+        }
+
+        let catch_block = Box::new(self.parse_block_stmt());
+
+        CatchClause { catch_param, catch_block, span: self.finish(start) }
+    }
+
+    /// Section 14.13 [Labelled Statements](https://tc39.es/ecma262/#sec-labelled-statements)
+    pub(crate) fn parse_labelled_stmt(&mut self) -> LabelledStmt {
+        let chain = self.labels.len();
+        self.parse_chained_labelled_stmt(chain)
+    }
+
+    /// Parses a labelled statement whose label is part of the chain of labels starting at `chain` in the label stack,
+    /// all of which label the same statement, as in `a: b: while (x) {}`.
+    fn parse_chained_labelled_stmt(&mut self, chain: usize) -> LabelledStmt {
+        // Unlike a binding, a label may be `eval` or `arguments` even in strict mode code.
+        self.check_identifier(self.peek());
+        let label = match self.peek_kind() {
+            TokenKind::Id(label) => label,
+            _ => sym::EMPTY,
+        };
+        if self.labels.iter().any(|enclosing| enclosing.name == label) {
+            self.push_error(ParseErrorKind::DuplicateLabel(label.to_string()));
+        }
+        self.bump();
+
+        self.expect(TokenKind::Punc(PuncKind::Colon));
+
+        self.labels.push(Label { name: label, is_loop: false });
+        let body = match self.peek_kind() {
+            TokenKind::Id(_) if self.peek_nth_kind(1) == TokenKind::Punc(PuncKind::Colon) => {
+                let start = self.start();
+                match self.nested(|p| p.parse_chained_labelled_stmt(chain)) {
+                    Some(stmt) => Stmt::new(StmtKind::Labelled(stmt), self.finish(start)),
+                    None => Stmt::new(StmtKind::Empty(EmptyStmt), self.finish(start)),
+                }
+            }
+            kind => {
+                if matches!(kind, Keyword(KeywordKind::Do | KeywordKind::For | KeywordKind::While)) {
+                    for label in &mut self.labels[chain..] {
+                        label.is_loop = true;
+                    }
+                }
+                self.parse_sub_stmt()
+            }
+        };
+        self.labels.pop();
+
+        LabelledStmt { label, body: Box::new(body) }
+    }
+
     /// Section 14.16 [Debugger Statement](https://tc39.es/ecma262/#sec-debugger-statement)
     pub(crate) fn parse_debugger_stmt(&mut self) -> DebugStmt {
         self.expect(Keyword(KeywordKind::Debugger));
 
-        self.eat(TokenKind::Punc(PuncKind::SemiColon));
+        self.consume_semicolon();
 
         DebugStmt{}
     }
 }
 
+/// Checks if a keyword starts a statement, making it a point to resume parsing after a syntax error.
+fn is_stmt_keyword(keyword: KeywordKind) -> bool {
+    matches!(
+        keyword,
+        KeywordKind::Break
+            | KeywordKind::Class
+            | KeywordKind::Const
+            | KeywordKind::Continue
+            | KeywordKind::Debugger
+            | KeywordKind::Do
+            | KeywordKind::Export
+            | KeywordKind::For
+            | KeywordKind::Function
+            | KeywordKind::If
+            | KeywordKind::Import
+            | KeywordKind::Return
+            | KeywordKind::Switch
+            | KeywordKind::Throw
+            | KeywordKind::Try
+            | KeywordKind::Var
+            | KeywordKind::While
+            | KeywordKind::With
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::ast::{ArrayPattern, AssignExpr, BinaryExpr, BlockStmt, BreakStmt, BreakableStmtKind, CatchClause, ConstDecl, ContinueStmt, DeclKind, DoWhileStmt, Expr, ExprKind, ExprStmt, ForHeadKind, ForInStmt, ForInitKind, ForOfStmt, ForStmt, HoistableDeclKind, IfStmt, IterStmtKind, LabelledStmt, LetDecl, LexicalKind, MemberExpr, MemberPropKind, ObjectPattern, ObjectPatternPropKind, OperatorKind, ParenExpr, PatternKind, ReturnStmt, ShorthandPatternProp, Stmt, StmtKind, SwitchCase, SwitchStmt, TryStmt, UpdateExpr, VarDeclarator, VarStmt, WhileStmt, WithStmt};
+    use crate::token::{BracketKind, KeywordKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
+    use crate::span::Span;
+    use crate::ParseErrorKind::{MissingSemicolon, UnexpectedToken};
+    use crate::ParseErrorKind;
+    use crate::test_utils::{assert_ast_eq, error_count, id, num, parse_module, parse_single, IntoNode};
     use super::Parser;
 
+    fn iter(stmt: IterStmtKind) -> Stmt {
+        StmtKind::Breakable(BreakableStmtKind::Iter(stmt)).node()
+    }
+
+    fn empty_block() -> Box<Stmt> {
+        Box::new(StmtKind::Block(BlockStmt { stmts: vec![] }).node())
+    }
+
     #[test]
     fn test_decl() {
         let module = "Test";
-        let mut parser = Parser::init("let five = 5;\n let six = 6\n let added = five + six").unwrap();
-        let res = parser.parse(module.into());
-        assert!(res.is_ok());
+        let mut parser = Parser::init("let five = 5;\n let six = 6\n let added = five + six");
+        let res = parser.parse(module);
+        assert!(res.errors.is_empty());
+    }
+
+    #[test]
+    fn test_var_decls() {
+        assert_ast_eq!(
+            parse_single("var x"),
+            StmtKind::Variable(VarStmt { declarators: vec![VarDeclarator { id: PatternKind::Id("x".into()).node(), init: None, span: Span::default() }] }).node()
+        );
+        assert_ast_eq!(
+            parse_single("let a, b = 2;"),
+            StmtKind::Decl(DeclKind::Lexical(LexicalKind::Let(LetDecl {
+                declarators: vec![
+                    VarDeclarator { id: PatternKind::Id("a".into()).node(), init: None, span: Span::default() },
+                    VarDeclarator { id: PatternKind::Id("b".into()).node(), init: Some(num(2)), span: Span::default() },
+                ],
+            }))).node()
+        );
+        assert_ast_eq!(
+            parse_single("const [a] = xs, c = 3;"),
+            StmtKind::Decl(DeclKind::Lexical(LexicalKind::Const(ConstDecl {
+                declarators: vec![
+                    VarDeclarator { id: PatternKind::Array(ArrayPattern { elements: vec![Some(PatternKind::Id("a".into()).node())] }).node(), init: Some(id("xs")), span: Span::default() },
+                    VarDeclarator { id: PatternKind::Id("c".into()).node(), init: Some(num(3)), span: Span::default() },
+                ],
+            }))).node()
+        );
+
+        let mut p = Parser::init("const a = 1, b;");
+        assert_eq!(p.parse("test").errors.len(), 1);
     }
 
     #[test]
     fn test_if() {
         let module = "Test";
-        let mut parser = Parser::init("let y = 5;\nif (y == 5) {\nreturn;\n}").unwrap();
-        let res = parser.parse(module.into());
-        assert!(res.is_ok());
+        let mut parser = Parser::init("let y = 5;\nif (y == 5) {\ny = 6;\n}");
+        let res = parser.parse(module);
+        assert!(res.errors.is_empty());
+    }
+
+    #[test]
+    fn test_while() {
+        assert_ast_eq!(parse_single("while (a) {}"), iter(IterStmtKind::While(WhileStmt { cond: id("a"), body: empty_block() })));
+        assert_ast_eq!(
+            parse_single("do a; while (b);"),
+            iter(IterStmtKind::DoWhile(DoWhileStmt { body: Box::new(StmtKind::Expression(ExprStmt(id("a"))).node()), cond: id("b") }))
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_for() {
+        assert_ast_eq!(
+            parse_single("for (let i = 0, j; i < n; i++) {}"),
+            iter(IterStmtKind::For(ForStmt {
+                init: Some(ForInitKind::Let(vec![
+                    VarDeclarator { id: PatternKind::Id("i".into()).node(), init: Some(num(0)), span: Span::default() },
+                    VarDeclarator { id: PatternKind::Id("j".into()).node(), init: None, span: Span::default() },
+                ])),
+                test: Some(ExprKind::Binary(BinaryExpr {
+                    op: OperatorKind::Punc(OpKind::LessThan),
+                    lhs: Box::new(id("i")),
+                    rhs: Box::new(id("n")),
+                }).node()),
+                update: Some(ExprKind::Update(UpdateExpr { op: OpKind::Increment, prefix: false, arg: Box::new(id("i")) }).node()),
+                body: empty_block(),
+            }))
+        );
+        assert_ast_eq!(
+            parse_single("for (;;) {}"),
+            iter(IterStmtKind::For(ForStmt { init: None, test: None, update: None, body: empty_block() }))
+        );
+        assert_ast_eq!(
+            parse_single("for ((a in b);;) {}"),
+            iter(IterStmtKind::For(ForStmt {
+                init: Some(ForInitKind::Expr(ExprKind::Paren(ParenExpr {
+                    expr: Box::new(ExprKind::Binary(BinaryExpr {
+                        op: OperatorKind::Keyword(KeywordKind::In),
+                        lhs: Box::new(id("a")),
+                        rhs: Box::new(id("b")),
+                    }).node()),
+                }).node())),
+                test: None,
+                update: None,
+                body: empty_block(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_for_in_of() {
+        assert_ast_eq!(
+            parse_single("for (var k in o) {}"),
+            iter(IterStmtKind::ForIn(ForInStmt {
+                left: ForHeadKind::Var(PatternKind::Id("k".into()).node()),
+                right: id("o"),
+                body: empty_block(),
+            }))
+        );
+        assert_ast_eq!(
+            parse_single("for (a.b in o) {}"),
+            iter(IterStmtKind::ForIn(ForInStmt {
+                left: ForHeadKind::Pattern(PatternKind::Member(MemberExpr {
+                    object: Box::new(id("a")),
+                    prop: MemberPropKind::Id("b".into()),
+                    optional: false,
+                }).node()),
+                right: id("o"),
+                body: empty_block(),
+            }))
+        );
+        assert_ast_eq!(
+            parse_single("for (const [k, v] of entries) {}"),
+            iter(IterStmtKind::ForOf(ForOfStmt {
+                left: ForHeadKind::Const(PatternKind::Array(ArrayPattern {
+                    elements: vec![Some(PatternKind::Id("k".into()).node()), Some(PatternKind::Id("v".into()).node())],
+                }).node()),
+                right: id("entries"),
+                body: empty_block(),
+                is_await: false,
+            }))
+        );
+        assert_ast_eq!(
+            parse_single("for await (x of xs) {}"),
+            iter(IterStmtKind::ForOf(ForOfStmt {
+                left: ForHeadKind::Pattern(PatternKind::Id("x".into()).node()),
+                right: id("xs"),
+                body: empty_block(),
+                is_await: true,
+            }))
+        );
+
+        let mut p = Parser::init("for await (x in xs) {}");
+        assert_eq!(p.parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_switch() {
+        assert_ast_eq!(
+            parse_single("switch (x) {\ncase 1:\nbreak;\ndefault:\ncase 2: y; z;\n}"),
+            StmtKind::Breakable(BreakableStmtKind::Switch(SwitchStmt {
+                switch_expr: id("x"),
+                cases: vec![
+                    SwitchCase { test: Some(num(1)), body: vec![StmtKind::Break(BreakStmt { label: None }).node()], span: Span::default() },
+                    SwitchCase { test: None, body: vec![], span: Span::default() },
+                    SwitchCase {
+                        test: Some(num(2)),
+                        body: vec![StmtKind::Expression(ExprStmt(id("y"))).node(), StmtKind::Expression(ExprStmt(id("z"))).node()],
+                        span: Span::default(),
+                    },
+                ],
+            })).node()
+        );
+
+        let mut p = Parser::init("switch (x) { default: default: }");
+        assert_eq!(p.parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_try() {
+        assert_ast_eq!(
+            parse_single("try {\n} catch {\n} finally {}"),
+            StmtKind::Try(TryStmt {
+                try_block: Box::new(BlockStmt { stmts: vec![] }),
+                catch_block: Some(Box::new(CatchClause { catch_param: None, catch_block: Box::new(BlockStmt { stmts: vec![] }), span: Span::default() })),
+                finally_block: Some(Box::new(BlockStmt { stmts: vec![] })),
+            }).node()
+        );
+        assert_ast_eq!(
+            parse_single("try {} catch ({ message }) {}"),
+            StmtKind::Try(TryStmt {
+                try_block: Box::new(BlockStmt { stmts: vec![] }),
+                catch_block: Some(Box::new(CatchClause {
+                    catch_param: Some(PatternKind::Object(ObjectPattern {
+                        props: vec![ObjectPatternPropKind::Shorthand(ShorthandPatternProp { id: "message".into(), default: None }).node()],
+                    }).node()),
+                    catch_block: Box::new(BlockStmt { stmts: vec![] }),
+                    span: Span::default(),
+                })),
+                finally_block: None,
+            }).node()
+        );
+
+        assert!(!Parser::init("try {}").parse("test").errors.is_empty());
+    }
+
+    #[test]
+    fn test_with() {
+        let res = Parser::init_script("with (o) x;").parse("test");
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_ast_eq!(
+            res.ast.body,
+            vec![StmtKind::With(WithStmt {
+                with_expr: id("o"),
+                with_block: Box::new(StmtKind::Expression(ExprStmt(id("x"))).node()),
+            }).node()]
+        );
+
+        for source in ["\"use strict\"; with (o) x;", "function f() { \"use strict\"; with (o) x; }"] {
+            let errs = Parser::init_script(source).parse("test").errors;
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::StrictWith), "{}", source);
+        }
+        assert!(matches!(Parser::init("with (o) x;").parse("test").errors[0].kind, ParseErrorKind::StrictWith));
+    }
+
+    #[test]
+    fn test_labelled() {
+        assert_ast_eq!(
+            parse_single("outer: while (x) { continue outer; break outer; }"),
+            StmtKind::Labelled(LabelledStmt {
+                label: "outer".into(),
+                body: Box::new(iter(IterStmtKind::While(WhileStmt {
+                    cond: id("x"),
+                    body: Box::new(StmtKind::Block(BlockStmt {
+                        stmts: vec![
+                            StmtKind::Continue(ContinueStmt { label: Some("outer".into()) }).node(),
+                            StmtKind::Break(BreakStmt { label: Some("outer".into()) }).node(),
+                        ],
+                    }).node()),
+                }))),
+            }).node()
+        );
+    }
+
+    fn expr_stmt(expr: Expr) -> Stmt {
+        StmtKind::Expression(ExprStmt(expr)).node()
+    }
+
+    /// Parses statements as the body of a function, where `return` is allowed.
+    fn parse_function_body(source: &str) -> Vec<Stmt> {
+        match parse_single(&format!("function f() {{{}}}", source)).kind {
+            StmtKind::Decl(DeclKind::Hoistable(HoistableDeclKind::Function(function))) => function.body,
+            stmt => panic!("Expected function declaration, found {:?}", stmt),
+        }
+    }
+
+    #[test]
+    fn test_asi() {
+        assert_ast_eq!(
+            parse_function_body("return\nx"),
+            vec![StmtKind::Return(ReturnStmt { return_value: None }).node(), expr_stmt(id("x"))]
+        );
+        assert_ast_eq!(
+            parse_module("x\n++y").body,
+            vec![
+                expr_stmt(id("x")),
+                expr_stmt(ExprKind::Update(UpdateExpr { op: OpKind::Increment, prefix: true, arg: Box::new(id("y")) }).node()),
+            ]
+        );
+        assert_ast_eq!(
+            parse_module("while (x) { break\nfoo }").body,
+            vec![iter(IterStmtKind::While(WhileStmt {
+                cond: id("x"),
+                body: Box::new(StmtKind::Block(BlockStmt {
+                    stmts: vec![StmtKind::Break(BreakStmt { label: None }).node(), expr_stmt(id("foo"))],
+                }).node()),
+            }))]
+        );
+        assert_eq!(parse_module("let a = 1\nlet b = 2\n").body.len(), 2);
+        assert_eq!(parse_module("if (a) b\nelse c").body.len(), 1);
+        assert_eq!(parse_module("do {} while (a) b").body.len(), 2);
+        assert_eq!(parse_module("{ a } b").body.len(), 2);
+        // No semicolon is inserted when the next line continues the expression.
+        assert_eq!(parse_module("a\n(b)\n.c").body.len(), 1);
+        // A multi-line comment containing a line terminator separates lines as one would.
+        assert_eq!(parse_module("a /*\n*/ b").body.len(), 2);
+        assert_ast_eq!(parse_function_body("return /*\n*/ x"), parse_function_body("return\nx"));
+        assert_eq!(parse_module("a // b\nc").body.len(), 2);
+
+        for source in ["a b", "let a = 1 let b = 2", "if (a) b else c", "throw\nx", "for (a\nb) {}", "a /* */ b"] {
+            assert!(!Parser::init(source).parse("test").errors.is_empty(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_early_errors() {
+        for source in [
+            "return;", "{ return 1 }", "class A { static { return } }", "const a;", "for (const a; ;) {}",
+            "let [a];", "var {a}, b;", "for (let [a]; ;) {}", "for (var a, {b}; ;) {}",
+        ] {
+            assert_eq!(error_count(source), 1, "{}", source);
+        }
+        for source in ["function f() { return; }", "() => { return }", "for (const a of b) {}", "for (let [a] in b) {}"] {
+            assert_eq!(error_count(source), 0, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_contextual_keywords() {
+        let script = |source: &str| Parser::init_script(source).parse("test").errors;
+
+        let module = |source: &str| Parser::init(source).parse("test").errors;
+        assert!(script("let yield = 1; var let = 2; let = 3; var static, await;").is_empty());
+        assert!(script("obj.static; obj.let; obj.if; ({ get: 1, set: 2, async: 3, of: 4 });").is_empty());
+        assert!(script("function* g() { yield 1 } async function f() { await x; for await (x of y); }").is_empty());
+        assert!(module("for (x of y); await x; class C { static x; static { } get y() {} }").is_empty());
+        assert!(script("let\nx = 1").is_empty());
+
+        let cases = [
+            (module("let yield = 1;"), "yield"),
+            (module("function f() { var await; }"), "await"),
+            (script("function* g() { var yield; }"), "yield"),
+            (script("async function f() { var await; }"), "await"),
+            (script("\"use strict\"; var let;"), "let"),
+            (script("function f() { \"use strict\"; var static; } var static;"), "static"),
+            (script("class C { m() { var implements; } }"), "implements"),
+        ];
+        for (errs, word) in cases {
+            assert_eq!(errs.len(), 1, "{}", word);
+            assert!(matches!(&errs[0].kind, ParseErrorKind::ReservedWord(name) if name == word), "{}", word);
+        }
+
+        // Let and const declarations can not bind `let`, even where it is an identifier.
+        for source in ["const let = 1;", "let [let] = 1;", "for (let let of x) {}", "let\nlet = 1;", "for (const { let } in x);"] {
+            let errs = script(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(errs[0].kind, ParseErrorKind::LexicallyBoundLet), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_break_and_continue_targets() {
+        let errors = |source: &str| Parser::init(source).parse("test").errors;
+        assert!(errors("while (a) break; for (;;) continue; switch (a) { case 1: break; } a: { break a; }").is_empty());
+        assert!(errors("a: b: while (x) { c: { continue a; } } a: do { break a; } while (x); a: ; a: ;").is_empty());
+
+        let cases = [
+            ("if (a) break;", "break"),
+            ("switch (a) { default: continue; }", "continue"),
+            ("a: { while (x) continue a; }", "continue"),
+            ("while (x) { () => { break; }; }", "break"),
+        ];
+        for (source, keyword) in cases {
+            let errs = errors(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            match keyword {
+                "break" => assert!(matches!(errs[0].kind, ParseErrorKind::IllegalBreak), "{}", source),
+                _ => assert!(matches!(errs[0].kind, ParseErrorKind::IllegalContinue), "{}", source),
+            }
+        }
+
+        for source in ["while (x) break a;", "a: while (x) { function f() { continue a; } }"] {
+            let errs = errors(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(&errs[0].kind, ParseErrorKind::UndefinedLabel(name) if name == "a"), "{}", source);
+        }
+
+        for source in ["a: a: ;", "a: while (x) { a: ; }"] {
+            let errs = errors(source);
+            assert_eq!(errs.len(), 1, "{}", source);
+            assert!(matches!(&errs[0].kind, ParseErrorKind::DuplicateLabel(name) if name == "a"), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let mut p = Parser::init("let a = );\nb = 1 c;\nif (x) { y = ]; z }\nclass A { get x = 1; y() {} }\nd;");
+        let res = p.parse("test");
+
+        let found: Vec<(TokenKind, Vec<TokenKind>)> = res.errors.iter()
+            .map(|err| match &err.kind {
+                UnexpectedToken(found, expected) => (*found, expected.clone()),
+                MissingSemicolon(found) => (*found, vec![TokenKind::Punc(PuncKind::SemiColon)]),
+                kind => panic!("Unexpected error {:?}", kind),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)), vec![]),
+                (TokenKind::Id("c".into()), vec![TokenKind::Punc(PuncKind::SemiColon)]),
+                (TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)), vec![]),
+                (TokenKind::Punc(PuncKind::Op(OpKind::Assign)), vec![TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left))]),
+            ]
+        );
+        assert_eq!(res.errors[0].span, Span::new(8, 9));
+
+        // Statements are kept as far as they were parsed, with placeholders for what could not be.
+        assert_ast_eq!(
+            res.ast.body[0],
+            StmtKind::Decl(DeclKind::Lexical(LexicalKind::Let(LetDecl {
+                declarators: vec![VarDeclarator { id: PatternKind::Id("a".into()).node(), init: Some(ExprKind::Error.node()), span: Span::default() }],
+            }))).node()
+        );
+        assert_ast_eq!(
+            res.ast.body[2],
+            StmtKind::If(IfStmt {
+                cond: id("x"),
+                body: Box::new(StmtKind::Block(BlockStmt {
+                    stmts: vec![
+                        StmtKind::Expression(ExprStmt(ExprKind::Assign(AssignExpr {
+                            op: OpKind::Assign,
+                            target: Box::new(PatternKind::Id("y".into()).node()),
+                            value: Box::new(ExprKind::Error.node()),
+                        }).node())).node(),
+                        StmtKind::Expression(ExprStmt(id("z"))).node(),
+                    ],
+                }).node()),
+                alternative: None,
+            }).node()
+        );
+        let StmtKind::Decl(DeclKind::Class(class)) = &res.ast.body[3].kind else { panic!("Expected class") };
+        assert_eq!(class.body.len(), 2);
+        assert_ast_eq!(res.ast.body[4], StmtKind::Expression(ExprStmt(id("d"))).node());
+        assert_eq!(res.ast.body.len(), 5);
+
+        // A token which can not start a statement is skipped, rather than parsed again.
+        let res = Parser::init("} ) a;").parse("test");
+        assert_eq!(res.errors.len(), 2);
+        let error_stmt = StmtKind::Expression(ExprStmt(ExprKind::Error.node())).node();
+        assert_ast_eq!(res.ast.body, vec![error_stmt.clone(), error_stmt]);
+
+        // Tokens which start no statement or expression are reported where they are.
+        for (source, token, start) in [
+            ("@", TokenKind::Unicode('@'), 0),
+            ("a;\n€ b;", TokenKind::Unicode('€'), 3),
+            ("#x;", TokenKind::PrivateId("x".into()), 0),
+        ] {
+            let res = Parser::init(source).parse("test");
+            assert_eq!(res.errors.len(), 1, "{}", source);
+            assert!(matches!(&res.errors[0].kind, UnexpectedToken(found, _) if *found == token), "{}", source);
+            assert_eq!(res.errors[0].span.start, start, "{}", source);
+        }
+
+        let res = Parser::init("{ export const a = 1; b }").parse("test");
+        assert_eq!(res.errors.len(), 1);
+        assert_ast_eq!(
+            res.ast.body,
+            vec![StmtKind::Block(BlockStmt {
+                stmts: vec![
+                    StmtKind::Error.node(),
+                    StmtKind::Decl(DeclKind::Lexical(LexicalKind::Const(ConstDecl {
+                        declarators: vec![VarDeclarator { id: PatternKind::Id("a".into()).node(), init: Some(num(1)), span: Span::default() }],
+                    }))).node(),
+                    StmtKind::Expression(ExprStmt(id("b"))).node(),
+                ],
+            }).node()]
+        );
+    }
+}
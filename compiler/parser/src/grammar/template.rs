@@ -0,0 +1,138 @@
+use crate::ast::TemplateLit;
+use crate::ParseErrorKind::InvalidTemplateEscape;
+use crate::Parser;
+use crate::token::{BraceKind, PuncKind, TemplateKind, TokenKind};
+
+impl Parser<'_> {
+    /// Section 13.2.8 [Template Literals](https://tc39.es/ecma262/#sec-template-literals)
+    ///
+    /// Only tagged templates may contain invalid escape sequences, which leave their characters without a cooked value.
+    pub(crate) fn parse_template_lit(&mut self, tagged: bool) -> TemplateLit {
+        let mut template = TemplateLit { quasis: Vec::new(), exprs: Vec::new() };
+        let mut head = true;
+
+        loop {
+            let token = self.peek();
+            let kind = match token.kind {
+                TokenKind::Template(kind @ (TemplateKind::NoSubstitution | TemplateKind::Head)) if head => kind,
+                TokenKind::Template(kind @ (TemplateKind::Middle | TemplateKind::Tail)) if !head => kind,
+                _ => {
+                    // The `}` ending a substitution is lexed as part of the template characters following it.
+                    self.expect_peek(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)));
+                    return template;
+                }
+            };
+            self.bump();
+
+            let element = self.tokens.borrow().template_element(kind, token.span);
+            if element.cooked.is_none() && !tagged {
+                self.push_error_at(InvalidTemplateEscape, token.span);
+            }
+            template.quasis.push(element);
+
+            let tail = matches!(kind, TemplateKind::NoSubstitution | TemplateKind::Tail);
+            if tail {
+                return template;
+            }
+
+            template.exprs.push(self.with_in(true, Self::parse_expr));
+            head = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{CallExpr, Expr, ExprKind, MemberExpr, MemberPropKind, PrimaryExprKind, TaggedTemplateExpr, TemplateElement, TemplateLit};
+    use crate::span::Span;
+    use crate::test_utils::{assert_ast_eq, id, parse_single_expr, IntoNode};
+    use crate::{LexerErrorKind, ParseErrorKind};
+    use super::Parser;
+
+    fn quasi(cooked: Option<&str>, raw: &str) -> TemplateElement {
+        TemplateElement { cooked: cooked.map(Into::into), raw: raw.into(), span: Span::default() }
+    }
+
+    fn template(quasis: Vec<TemplateElement>, exprs: Vec<Expr>) -> Expr {
+        ExprKind::Primary(PrimaryExprKind::TemplateLiteral(TemplateLit { quasis, exprs })).node()
+    }
+
+    fn tagged(tag: Expr, quasis: Vec<TemplateElement>, exprs: Vec<Expr>) -> Expr {
+        ExprKind::TaggedTemplate(TaggedTemplateExpr { tag: Box::new(tag), quasi: TemplateLit { quasis, exprs } }).node()
+    }
+
+    #[test]
+    fn test_template_lit() {
+        assert_ast_eq!(parse_single_expr("`abc`"), template(vec![quasi(Some("abc"), "abc")], vec![]));
+        assert_ast_eq!(parse_single_expr("``"), template(vec![quasi(Some(""), "")], vec![]));
+
+        assert_ast_eq!(
+            parse_single_expr("`a${b}c${d}`"),
+            template(vec![quasi(Some("a"), "a"), quasi(Some("c"), "c"), quasi(Some(""), "")], vec![id("b"), id("d")])
+        );
+
+        // Braces within substitutions, and templates nested within them.
+        let inner = template(vec![quasi(Some("c"), "c"), quasi(Some(""), "")], vec![id("d")]);
+        let object = parse_single_expr("({b: `c${d}`})");
+        let ExprKind::Paren(paren) = object.kind else { panic!() };
+        assert_ast_eq!(
+            parse_single_expr("`a${ {b: `c${d}`} }e`"),
+            template(vec![quasi(Some("a"), "a"), quasi(Some("e"), "e")], vec![*paren.expr])
+        );
+        assert_ast_eq!(
+            parse_single_expr("`${`c${d}`}`"),
+            template(vec![quasi(Some(""), ""), quasi(Some(""), "")], vec![inner])
+        );
+
+        // Escapes are cooked, and line terminators are normalized in both values.
+        assert_ast_eq!(
+            parse_single_expr("`\\x41\\u{42}\\`\\${\r\n\\\n`"),
+            template(vec![quasi(Some("AB`${\n"), "\\x41\\u{42}\\`\\${\n\\\n")], vec![])
+        );
+    }
+
+    #[test]
+    fn test_template_spans() {
+        let expr = parse_single_expr("`ab${c}de`");
+        assert_eq!(expr.span, Span::new(0, 10));
+        let ExprKind::Primary(PrimaryExprKind::TemplateLiteral(template)) = expr.kind else { panic!() };
+        assert_eq!(template.quasis[0].span, Span::new(1, 3));
+        assert_eq!(template.exprs[0].span, Span::new(5, 6));
+        assert_eq!(template.quasis[1].span, Span::new(7, 9));
+    }
+
+    #[test]
+    fn test_tagged_template() {
+        assert_ast_eq!(parse_single_expr("tag`a${b}`"), tagged(id("tag"), vec![quasi(Some("a"), "a"), quasi(Some(""), "")], vec![id("b")]));
+
+        // Invalid escapes are allowed in tagged templates, without a cooked value.
+        assert_ast_eq!(parse_single_expr("tag`\\unicode\\01`"), tagged(id("tag"), vec![quasi(None, "\\unicode\\01")], vec![]));
+
+        // Tags are member expressions, which calls and member accesses can follow.
+        let member = ExprKind::Member(MemberExpr { object: Box::new(id("a")), prop: MemberPropKind::Id("b".into()), optional: false }).node();
+        let call = ExprKind::Call(CallExpr { callee: Box::new(tagged(member, vec![quasi(Some("x"), "x")], vec![])), args: vec![], optional: false });
+        assert_ast_eq!(parse_single_expr("a.b`x`()"), call.node());
+
+        let inner = tagged(id("a"), vec![quasi(Some("x"), "x")], vec![]);
+        assert_ast_eq!(parse_single_expr("a`x`\n`y`"), tagged(inner, vec![quasi(Some("y"), "y")], vec![]));
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let errors = |source: &str| Parser::init(source).parse("test").errors;
+
+        let errs = errors("`a\\unicode`");
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(errs[0].kind, ParseErrorKind::InvalidTemplateEscape));
+        assert_eq!(errs[0].span, Span::new(0, 11));
+
+        assert!(matches!(errors("`a${b c}`")[0].kind, ParseErrorKind::UnexpectedToken(..)));
+        assert!(matches!(errors("a?.b`c`")[0].kind, ParseErrorKind::UnexpectedToken(..)));
+        assert!(errors("function* f() { `${yield}` }").is_empty());
+
+        let errs = errors("`abc${d}e");
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(errs[0].kind, ParseErrorKind::Lexer(LexerErrorKind::UnterminatedTemplate)));
+        assert_eq!(errs[0].span, Span::new(7, 9));
+    }
+}
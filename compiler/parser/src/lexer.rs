@@ -1,370 +1,1012 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use unicode_id::UnicodeID;
+use super::diagnostic::Diagnostic;
 use super::reader::Reader;
+use super::span::Span;
+use super::ast::{LitValue, RegExLit, StringLit, TemplateElement, Utf16String};
+use super::symbol::Symbol;
 
-use super::token::{is_removable, map_keyword, BooleanKind, BraceKind, BracketKind, LineTerminatorKind, LitKind, OpKind, ParenthesesKind, PuncKind, Token, TokenKind, WhiteSpaceKind};
+use super::token::{is_removable, map_keyword, BooleanKind, BraceKind, BracketKind, Comment, CommentKind, LineTerminatorKind, LitKind, OpKind, ParenthesesKind, PuncKind, TemplateKind, Token, TokenKind, WhiteSpaceKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LexerErrorKind {
     InvalidToken,
+    /// A malformed numeric literal, such as `0x`, `1__000` or `3in`.
+    InvalidNumericLiteral,
+    /// A legacy octal literal such as `017`, or a decimal literal with a leading zero such as `08`, in strict mode code.
+    LegacyOctalLiteral,
+    /// A string literal without a closing quote on the same line.
+    UnterminatedString,
+    /// A malformed hexadecimal or Unicode escape sequence, such as `\x4` or `\u{110000}`.
+    InvalidEscape,
+    /// A legacy octal escape sequence such as `\01`, or `\8` or `\9`, in strict mode code.
+    LegacyOctalEscape,
+    /// A template literal without a closing backtick.
+    UnterminatedTemplate,
+    /// A regular expression literal without a closing `/` on the same line.
+    UnterminatedRegEx,
+    /// Regular expression flags other than `dgimsuyv`, repeated flags, or both `u` and `v`.
+    InvalidRegExFlags,
+    /// A multi-line comment without a closing `*/`.
+    UnterminatedComment,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LexerError {
-    pub pos: usize,
+    pub span: Span,
     pub error: LexerErrorKind,
 }
 
+impl LexerErrorKind {
+    /// The stable error code, as described by `hermesc explain`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerErrorKind::InvalidToken => "E0001",
+            LexerErrorKind::InvalidNumericLiteral => "E0002",
+            LexerErrorKind::LegacyOctalLiteral => "E0003",
+            LexerErrorKind::UnterminatedString => "E0004",
+            LexerErrorKind::InvalidEscape => "E0005",
+            LexerErrorKind::LegacyOctalEscape => "E0006",
+            LexerErrorKind::UnterminatedTemplate => "E0007",
+            LexerErrorKind::UnterminatedRegEx => "E0008",
+            LexerErrorKind::InvalidRegExFlags => "E0009",
+            LexerErrorKind::UnterminatedComment => "E0010",
+        }
+    }
+}
+
+impl LexerError {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self.error {
+            LexerErrorKind::InvalidToken => Diagnostic::error("invalid token")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "not recognized as a token"),
+            LexerErrorKind::InvalidNumericLiteral => Diagnostic::error("invalid numeric literal")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "not a valid number"),
+            LexerErrorKind::LegacyOctalLiteral => Diagnostic::error("legacy octal literals are not allowed in strict mode")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "leading zero")
+                .with_help("use the `0o` prefix for octal numbers, or remove the leading zeros of decimal numbers"),
+            LexerErrorKind::UnterminatedString => Diagnostic::error("unterminated string literal")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "missing closing quote")
+                .with_help("strings can not span lines, use `\\n` for a line break or `\\` at the end of the line to continue it"),
+            LexerErrorKind::InvalidEscape => Diagnostic::error("invalid escape sequence")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "invalid escape")
+                .with_help("`\\x` takes two hexadecimal digits, and `\\u` takes four, or a code point up to 10FFFF in braces"),
+            LexerErrorKind::LegacyOctalEscape => Diagnostic::error("octal escape sequences are not allowed in strict mode")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "octal escape")
+                .with_help("use a hexadecimal escape such as `\\x01` instead"),
+            LexerErrorKind::UnterminatedTemplate => Diagnostic::error("unterminated template literal")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "missing closing backtick"),
+            LexerErrorKind::UnterminatedRegEx => Diagnostic::error("unterminated regular expression literal")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "missing closing `/`")
+                .with_help("escape a `/` in the pattern as `\\/`"),
+            LexerErrorKind::InvalidRegExFlags => Diagnostic::error("invalid regular expression flags")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "invalid flags")
+                .with_help("the flags are `d`, `g`, `i`, `m`, `s`, `u`, `v` and `y`, each used at most once, and `u` and `v` can not be combined"),
+            LexerErrorKind::UnterminatedComment => Diagnostic::error("unterminated multi-line comment")
+                .with_code(self.error.code())
+                .with_primary_label(self.span, "missing closing `*/`"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    reader: RefCell<Reader<'a>>,
+    /// Whether the source is lexed with the Module goal symbol, which does not allow HTML-like comments.
+    module: bool,
+    /// For each `{` or `${` that is yet to be closed, whether it opened a template substitution, so the `}` closing
+    /// it continues the template.
+    braces: RefCell<Vec<bool>>,
+    /// Whether only whitespace and comments precede the next token on its line, where `-->` opens a comment.
+    line_start: Cell<bool>,
+    /// Whether a line terminator has been lexed since the last token.
+    preceded_by_newline: bool,
+    /// The comments lexed, which are not kept in the token stream.
+    comments: RefCell<BTreeMap<Span, Comment>>,
+    /// The legacy octal literal or first legacy octal escape sequence in the token being lexed, if any, which the token
+    /// records for the parser.
+    legacy_octal: Cell<Option<Span>>,
+}
+
+/// The state of the lexer between two tokens, which it can be rewound to in order to lex the source from there again.
 #[derive(Debug, Clone)]
-pub struct Lexer {
-    reader: Rc<RefCell<Reader<char>>>,
+pub struct LexerCheckpoint {
+    pos: usize,
+    braces: Vec<bool>,
+    line_start: bool,
+    preceded_by_newline: bool,
 }
 
-impl Lexer {
-    pub fn init(source: &str) -> Self {
-        Lexer { reader: Rc::new(RefCell::new(Reader::init(source.chars().collect::<Vec<char>>()))) }
+impl<'a> Lexer<'a> {
+    pub fn init(source: &'a str) -> Self {
+        Lexer {
+            reader: RefCell::new(Reader::init(source)),
+            module: false,
+            braces: RefCell::new(Vec::new()),
+            line_start: Cell::new(true),
+            preceded_by_newline: false,
+            comments: RefCell::new(BTreeMap::new()),
+            legacy_octal: Cell::new(None),
+        }
     }
 
-    pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerError> {
-        let mut tokens = Vec::new();
+    /// Lexes the source with the Module goal symbol, where `<!--` and `-->` are operators rather than comments.
+    pub fn module(mut self) -> Self {
+        self.module = true;
+        self
+    }
 
+    /// Lexes the next token, which is [TokenKind::Eof] from the end of the source onward. Line terminators are not
+    /// tokens, but recorded on the token following them, as are multi-line comments containing line terminators.
+    pub fn next_token(&mut self) -> Result<Token, LexerError> {
         loop {
-            match self.lex() {
-                Ok(token) => {
-                    if !is_removable(&token.kind) {
-                        tokens.push(token.clone());
-                    }
-                    if token.kind == TokenKind::Eof {
-                        break;
+            let mut token = self.lex()?;
+            match token.kind {
+                TokenKind::LineTerminator(_) => {
+                    self.preceded_by_newline = true;
+                    self.line_start.set(true);
+                    continue;
+                }
+                TokenKind::Comment(kind) => {
+                    let comment = Comment::new(kind, self.reader.borrow().range(token.span.start, token.span.end));
+                    if kind == CommentKind::MultiLine && comment.text.contains(['\n', '\r', '\u{2028}', '\u{2029}']) {
+                        self.preceded_by_newline = true;
+                        self.line_start.set(true);
                     }
+                    self.comments.borrow_mut().insert(token.span, comment);
+                    continue;
                 }
-                Err(err) => return Err(err),
+                kind if is_removable(&kind) => continue,
+                _ => {}
+            }
+            self.line_start.set(false);
+            token.preceded_by_newline = std::mem::take(&mut self.preceded_by_newline);
+            return Ok(token);
+        }
+    }
+
+    /// Lexes the rest of the source, up to and including the [TokenKind::Eof] token.
+    #[cfg(test)]
+    pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            let eof = token.kind == TokenKind::Eof;
+            tokens.push(token);
+            if eof {
+                return Ok(tokens);
             }
         }
+    }
+
+    /// Takes the comments lexed so far, keyed by span.
+    pub fn take_comments(&self) -> BTreeMap<Span, Comment> {
+        std::mem::take(&mut self.comments.borrow_mut())
+    }
+
+    /// The current state of the lexer, after the last token lexed.
+    pub fn checkpoint(&self) -> LexerCheckpoint {
+        LexerCheckpoint {
+            pos: self.reader.borrow().get_pos(),
+            braces: self.braces.borrow().clone(),
+            line_start: self.line_start.get(),
+            preceded_by_newline: self.preceded_by_newline,
+        }
+    }
+
+    /// Rewinds the lexer to a checkpoint, forgetting the comments lexed since.
+    pub fn rewind(&mut self, checkpoint: LexerCheckpoint) {
+        self.comments.borrow_mut().split_off(&Span::new(checkpoint.pos, checkpoint.pos));
+        self.reader.borrow_mut().set_pos(checkpoint.pos);
+        *self.braces.borrow_mut() = checkpoint.braces;
+        self.line_start.set(checkpoint.line_start);
+        self.preceded_by_newline = checkpoint.preceded_by_newline;
+    }
+
+    /// Section 12 [InputElementRegExp](https://tc39.es/ecma262/#prod-InputElementRegExp)
+    ///
+    /// The source is lexed with the InputElementDiv goal, where `/` is division. Lexes the provided `/` or `/=` token
+    /// again as a regular expression literal, given the checkpoint taken right after it. As `/` neither opens nor
+    /// closes braces, the lexer is then in the state it was in before the token.
+    pub fn rescan_regex(&mut self, slash: &Token, checkpoint: LexerCheckpoint) -> Result<Token, LexerError> {
+        self.rewind(checkpoint);
+
+        let mut reader = self.reader.borrow_mut();
+        reader.set_pos(slash.span.start + 1);
+        let kind = self.lex_regex(&mut reader, slash.span.start)?;
+        let mut token = Token::new(kind, Span::new(slash.span.start, reader.get_pos()));
+        token.preceded_by_newline = slash.preceded_by_newline;
+        Ok(token)
+    }
+
+    /// The value of a literal token of the provided kind and span, read from its source text.
+    pub fn lit_value(&self, kind: LitKind, span: Span) -> LitValue {
+        let source = self.reader.borrow().range(span.start, span.end);
+        match kind {
+            LitKind::BigInt => LitValue::BigInt(big_int_value(source)),
+            LitKind::Bool(value) => LitValue::Bool(value),
+            LitKind::Null => LitValue::Null,
+            LitKind::Num(value) => LitValue::Num(value),
+            LitKind::String => LitValue::String(self.string_lit(span)),
+            LitKind::RegEx => unreachable!("regular expression literals are read with regex_lit"),
+        }
+    }
+
+    /// The value of a string literal token with the provided span.
+    pub fn string_lit(&self, span: Span) -> StringLit {
+        let source = self.reader.borrow().range(span.start + 1, span.end - 1);
+        StringLit { cooked: cook(source, false).unwrap_or_default(), raw: source.to_string() }
+    }
+
+    /// The pattern and flags of a regular expression literal token with the provided span.
+    pub fn regex_lit(&self, span: Span) -> RegExLit {
+        let source = self.reader.borrow().range(span.start, span.end);
+        // The flags cannot contain a `/`, so the last one ends the pattern.
+        let end = source.rfind('/').unwrap_or_default();
+        RegExLit { pattern: source[1..end].to_string(), flags: source[end + 1..].to_string() }
+    }
 
-        Ok(tokens)
+    /// The characters of a template token of the provided kind and span, read from their source text.
+    pub fn template_element(&self, kind: TemplateKind, span: Span) -> TemplateElement {
+        let (open, close) = kind.delimiters();
+        let span = Span::new(span.start + open, span.end - close);
+        let source = self.reader.borrow().range(span.start, span.end);
+        let raw = if source.contains('\r') { source.replace("\r\n", "\n").replace('\r', "\n") } else { source.to_string() };
+        TemplateElement { cooked: cook(source, true), raw, span }
     }
 
     fn lex(&self) -> Result<Token, LexerError> {
+        self.legacy_octal.set(None);
+        let start = self.reader.borrow().get_pos();
+        let kind = self.lex_kind()?;
+        let end = self.reader.borrow().get_pos();
+        let mut token = Token::new(kind, Span::new(start, end));
+        token.escaped = matches!(token.kind, TokenKind::Id(_)) && self.reader.borrow().range(start, end).contains('\\');
+        token.legacy_octal = self.legacy_octal.take();
+        Ok(token)
+    }
+
+    /// Records a legacy octal literal or escape sequence in the token being lexed, keeping the first one.
+    fn record_legacy_octal(&self, span: Span) {
+        if self.legacy_octal.get().is_none() {
+            self.legacy_octal.set(Some(span));
+        }
+    }
+
+    fn lex_kind(&self) -> Result<TokenKind, LexerError> {
         let mut reader = self.reader.borrow_mut();
         let pos = reader.get_pos();
         match reader.next_single() {
             Some(first) => {
                 if first.is_digit(10) {
-                    return Ok(self.lex_numeric(&mut reader, first));
+                    return self.lex_numeric(&mut reader, first, pos);
                 }
 
-                if first.is_alphabetic() {
-                    return Ok(self.lex_identifier(&mut reader, first));
+                if is_identifier_start(first) || (first == '\\' && reader.peek_single() == Some('u')) {
+                    return self.lex_identifier(&mut reader, first);
+                }
+
+                if let Some(white_space) = white_space_kind(first) {
+                    return Ok(TokenKind::WhiteSpace(white_space));
                 }
 
                 match first {
-                    '"' => self.lex_string_literal(&mut reader),
-                    '\t' => Ok(Token::new(TokenKind::WhiteSpace(WhiteSpaceKind::HorizontalTabulation), pos)),
-                    '\n' => Ok(Token::new(TokenKind::LineTerminator(LineTerminatorKind::LineFeed), pos)),
-                    '\r' => Ok(Token::new(TokenKind::LineTerminator(LineTerminatorKind::CarriageReturn), pos)),
-                    ' ' => Ok(Token::new(TokenKind::WhiteSpace(WhiteSpaceKind::Space), pos)),
-                    '(' => Ok(Token::new(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)), pos)),
-                    ')' => Ok(Token::new(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)), pos)),
-                    '=' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '=' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::StrictEquality)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Equal)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Equal)), pos)),
-                                }
-                            }
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), pos)),
-                    },
-                    '*' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '*' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::ExponentialAssign)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Exponential)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Exponential)), pos)),
-                                }
-                            }
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::MultiplicationAssign)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Multiplication)), pos)),
-                    },
-                    '&' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '&' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::AndAssign)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::And)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::And)), pos)),
-                                }
-                            }
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitAndAssign)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitAnd)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitAnd)), pos)),
-                    },
-                    '|' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '|' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::OrAssign)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Or)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Or)), pos)),
-                                }
-                            }
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitOrAssign)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitOr)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::BitOr)), pos)),
-                    },
-                    '^' => Ok(self.lex_assignable_operator(&mut reader, OpKind::BitXor, OpKind::BitXorAssign)),
-                    '+' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '+' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Increment)), pos)),
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::AdditonAssign)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Addition)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Addition)), pos)),
-                    },
-                    '-' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '-' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Decrement)), pos)),
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::SubtractionAssign)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Subtraction)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Subtraction)), pos)),
-                    },
-                    '/' => Ok(self.lex_assignable_operator(&mut reader, OpKind::Division, OpKind::DivisionAssign)),
-                    '%' => Ok(self.lex_assignable_operator(&mut reader, OpKind::Mod, OpKind::ModAssign)),
-                    '>' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '>' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '>' => {
-                                            reader.bump();
-                                            match reader.peek_single() {
-                                                Some(fourth) => match fourth {
-                                                    '=' => {
-                                                        reader.bump();
-                                                        Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::UnsignedRightShiftAssign)), pos))
-                                                    }
-                                                    _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::ZeroFillRightShift)), pos)),
-                                                },
-                                                None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::ZeroFillRightShift)), pos)),
-                                            }
-                                        }
-                                        '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::RightShiftAssign)), pos)),
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::RightShift)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::RightShift)), pos)),
-                                }
-                            }
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::GreaterThanEqual)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::GreaterThan)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::GreaterThan)), pos)),
-                    },
-                    '<' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '<' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LeftShiftAssign)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LeftShift)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LeftShift)), pos)),
-                                }
-                            }
-                            '=' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LessThanEqual)), pos)),
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LessThan)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::LessThan)), pos)),
-                    },
-                    '!' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '=' => {
-                                reader.bump();
-                                Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::NotEqual)), pos))
-                            }
-                            _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Not)), pos)),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::Not)), pos)),
-                    },
-                    '?' => match reader.peek_single() {
-                        Some(second) => match second {
-                            '?' => {
-                                reader.bump();
-                                match reader.peek_single() {
-                                    Some(third) => match third {
-                                        '=' => {
-                                            reader.bump();
-                                            Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::NullishCoalescingAssign)), pos))
-                                        }
-                                        _ => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::NullishCoalescing)), pos)),
-                                    },
-                                    None => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::OptionalChain)), pos)),
-                                }
-                            }
-                            '.' => Ok(Token::new(TokenKind::Punc(PuncKind::Op(OpKind::OptionalChain)), pos)),
-                            _ => Err(LexerError{pos, error: LexerErrorKind::InvalidToken}),
-                        },
-                        None => Ok(Token::new(TokenKind::Punc(PuncKind::Question), pos))
+                    '"' | '\'' => self.lex_string_literal(&mut reader, first),
+                    '#' if pos == 0 && reader.peek_single() == Some('!') => {
+                        reader.bump();
+                        lex_single_line_comment(&mut reader);
+                        Ok(TokenKind::Comment(CommentKind::Hashbang))
+                    }
+                    '#' => self.lex_private_identifier(&mut reader),
+                    '\n' => Ok(TokenKind::LineTerminator(LineTerminatorKind::LineFeed)),
+                    '\r' => Ok(TokenKind::LineTerminator(LineTerminatorKind::CarriageReturn)),
+                    '\u{2028}' => Ok(TokenKind::LineTerminator(LineTerminatorKind::LineSeparator)),
+                    '\u{2029}' => Ok(TokenKind::LineTerminator(LineTerminatorKind::ParagraphSeparator)),
+                    '-' if !self.module && self.line_start.get() && reader.starts_with("->") => {
+                        reader.skip(2);
+                        lex_single_line_comment(&mut reader);
+                        Ok(TokenKind::Comment(CommentKind::HtmlClose))
+                    }
+                    '/' if reader.peek_single() == Some('/') => {
+                        reader.bump();
+                        lex_single_line_comment(&mut reader);
+                        Ok(TokenKind::Comment(CommentKind::SingleLine))
+                    }
+                    '/' if reader.peek_single() == Some('*') => {
+                        reader.bump();
+                        self.lex_multi_line_comment(&mut reader, pos)
+                    }
+                    '/' => Ok(TokenKind::Punc(PuncKind::Op(lex_assignable_operator(&mut reader, OpKind::Division, OpKind::DivisionAssign)))),
+                    '<' if !self.module && reader.starts_with("!--") => {
+                        reader.skip(3);
+                        lex_single_line_comment(&mut reader);
+                        Ok(TokenKind::Comment(CommentKind::HtmlOpen))
+                    }
+                    '.' if matches!(reader.peek_single(), Some('0'..='9')) => self.lex_numeric(&mut reader, first, pos),
+                    '{' => {
+                        self.braces.borrow_mut().push(false);
+                        Ok(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)))
+                    }
+                    '}' => {
+                        let substitution = self.braces.borrow_mut().pop() == Some(true);
+                        if substitution {
+                            return self.lex_template(&mut reader, false);
+                        }
+                        Ok(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)))
+                    }
+                    '`' => self.lex_template(&mut reader, true),
+                    _ => match lex_punctuator(&mut reader, first) {
+                        Some(punc) => Ok(TokenKind::Punc(punc)),
+                        None => Ok(TokenKind::Unicode(first)),
                     },
-                    '.' => Ok(Token::new(TokenKind::Punc(PuncKind::Dot), pos)),
-                    ';' => Ok(Token::new(TokenKind::Punc(PuncKind::SemiColon), pos)),
-                    ',' => Ok(Token::new(TokenKind::Punc(PuncKind::Comma), pos)),
-                    ':' => Ok(Token::new(TokenKind::Punc(PuncKind::Colon), pos)),
-                    '[' => Ok(Token::new(TokenKind::Punc(PuncKind::Bracket(BracketKind::Left)), pos)),
-                    ']' => Ok(Token::new(TokenKind::Punc(PuncKind::Bracket(BracketKind::Right)), pos)),
-                    '{' => Ok(Token::new(TokenKind::Punc(PuncKind::Brace(BraceKind::Left)), pos)),
-                    '}' => Ok(Token::new(TokenKind::Punc(PuncKind::Brace(BraceKind::Right)), pos)),
-                    _ => Ok(Token::new(TokenKind::Unicode(first.to_string()), pos)),
                 }
             }
-            None => Ok(Token::new(TokenKind::Eof, pos)),
+            None => Ok(TokenKind::Eof),
         }
     }
 
-    /// Handles alphabetic tokens encapsulated by
-    fn lex_string_literal(&self, reader: &mut Reader<char>) -> Result<Token, LexerError> {
-        let mut word = String::new();
-        let pos = reader.get_pos();
+    /// Section 12.4 [Comments](https://tc39.es/ecma262/#sec-comments)
+    ///
+    /// Given the opening `/*`, lexes the rest of a multi-line comment.
+    fn lex_multi_line_comment(&self, reader: &mut Reader<'a>, start: usize) -> Result<TokenKind, LexerError> {
         loop {
-            match reader.peek_single() {
-                Some(peek) => {
-                    if peek.is_alphabetic() {
-                        reader.bump();
-                        word.push(peek);
-                    } else if peek == '"' {
-                        reader.bump();
-                        return Ok(Token::new(TokenKind::Lit(LitKind::String(word)), pos));
-                    }
+            match reader.next_single() {
+                Some('*') if reader.peek_single() == Some('/') => {
+                    reader.bump();
+                    return Ok(TokenKind::Comment(CommentKind::MultiLine));
                 }
+                Some(_) => {}
                 None => {
-                    return Err(LexerError{pos, error: LexerErrorKind::InvalidToken});
+                    return Err(LexerError { span: Span::new(start, reader.get_pos()), error: LexerErrorKind::UnterminatedComment });
                 }
             }
         }
     }
 
-    /// Handles all alphabetic tokens not encapsulated by quotations (non-string literals)
-    fn lex_identifier(&self, reader: &mut Reader<char>, char: char) -> Token {
-        let mut word = char.to_string();
-        let pos = reader.get_pos();
+    /// Section 12.9.4 [String Literals](https://tc39.es/ecma262/#sec-literals-string-literals)
+    ///
+    /// Given the opening quote, lexes the rest of the string literal. Its value is only cooked once the parser reads it.
+    fn lex_string_literal(&self, reader: &mut Reader<'a>, quote: char) -> Result<TokenKind, LexerError> {
+        // The span starts at the opening quote, which has already been consumed.
+        let start = reader.get_pos() - 1;
+        // The code units of the escape sequences, which are only lexed to be validated.
+        let mut escaped = Vec::new();
+
         loop {
             match reader.peek_single() {
-                Some(peek) => {
-                    if peek.is_alphabetic() {
-                        word.push(peek);
-                        reader.bump();
-                    } else {
-                        return if let Some(keyword) = map_keyword(&word) {
-                            Token::new(TokenKind::Keyword(keyword), pos)
-                        } else if word == "true" {
-                            Token::new(TokenKind::Lit(LitKind::Bool(BooleanKind::True)), pos)
-                        } else if word == "false" {
-                            Token::new(TokenKind::Lit(LitKind::Bool(BooleanKind::False)), pos)
-                        } else {
-                            Token::new(TokenKind::Id(word), pos)
-                        }
-                    }
+                Some(peek) if peek == quote => break,
+                // Strings cannot span multiple lines, other than U+2028 and U+2029 which are allowed unescaped.
+                Some('\n' | '\r') | None => {
+                    return Err(LexerError { span: Span::new(start, reader.get_pos()), error: LexerErrorKind::UnterminatedString });
                 }
-                None => {
-                    return if let Some(keyword) = map_keyword(&word) {
-                        Token::new(TokenKind::Keyword(keyword), pos)
-                    } else if word == "true" {
-                        Token::new(TokenKind::Lit(LitKind::Bool(BooleanKind::True)), pos)
-                    } else if word == "false" {
-                        Token::new(TokenKind::Lit(LitKind::Bool(BooleanKind::False)), pos)
-                    } else {
-                        Token::new(TokenKind::Id(word), pos)
+                Some('\\') => {
+                    let escape = reader.get_pos();
+                    reader.bump();
+                    match lex_escape(reader, &mut escaped, false) {
+                        Ok(legacy_octal) => {
+                            if legacy_octal {
+                                self.record_legacy_octal(Span::new(escape, reader.get_pos()));
+                            }
+                        }
+                        Err(error) => {
+                            let start = if error == LexerErrorKind::UnterminatedString { start } else { escape };
+                            return Err(LexerError { span: Span::new(start, reader.get_pos()), error });
+                        }
                     }
                 }
+                Some(_) => reader.bump(),
             }
         }
+
+        reader.bump();
+        Ok(TokenKind::Lit(LitKind::String))
     }
 
-    /// Given a numeric character, parses the rest of the numeric and determines numeric variant.
-    /// TODO: Need to check for decimals and non-decimal number types.
-    fn lex_numeric(&self, reader: &mut Reader<char>, char: char) -> Token {
-        let mut val = char.to_string();
-        let pos = reader.get_pos();
+    /// Section 12.9.5 [Regular Expression Literals](https://tc39.es/ecma262/#sec-literals-regular-expression-literals)
+    ///
+    /// Given the opening `/`, lexes the pattern and flags of a regular expression literal.
+    fn lex_regex(&self, reader: &mut Reader<'a>, start: usize) -> Result<TokenKind, LexerError> {
+        let unterminated = |reader: &Reader| {
+            LexerError { span: Span::new(start, reader.get_pos()), error: LexerErrorKind::UnterminatedRegEx }
+        };
+
+        // A `/` within a class, such as `[/]`, does not end the pattern.
+        let mut in_class = false;
         loop {
             match reader.peek_single() {
+                None | Some('\n' | '\r' | '\u{2028}' | '\u{2029}') => return Err(unterminated(reader)),
+                Some('/') if !in_class => break,
                 Some(peek) => {
-                    if peek.is_digit(10) {
-                        val.push(peek);
-                        reader.bump();
-                    } else {
-                        return Token::new(TokenKind::Lit(LitKind::Num(val.parse().unwrap())), pos);
+                    reader.bump();
+                    match peek {
+                        '\\' => match reader.peek_single() {
+                            None | Some('\n' | '\r' | '\u{2028}' | '\u{2029}') => return Err(unterminated(reader)),
+                            Some(_) => reader.bump(),
+                        },
+                        '[' => in_class = true,
+                        ']' => in_class = false,
+                        _ => {}
                     }
                 }
-                None => return Token::new(TokenKind::Lit(LitKind::Num(val.parse().unwrap())), pos),
             }
         }
+        reader.bump();
+
+        let flags_start = reader.get_pos();
+        while let Some(peek) = reader.peek_single() && is_identifier_part(peek) {
+            reader.bump();
+        }
+        let flags = reader.range(flags_start, reader.get_pos());
+
+        let valid = flags.char_indices().all(|(i, flag)| "dgimsuyv".contains(flag) && !flags[..i].contains(flag))
+            && !(flags.contains('u') && flags.contains('v'));
+        if !valid {
+            return Err(LexerError { span: Span::new(flags_start, reader.get_pos()), error: LexerErrorKind::InvalidRegExFlags });
+        }
+
+        Ok(TokenKind::Lit(LitKind::RegEx))
     }
 
-    fn lex_assignable_operator(&self, reader: &mut Reader<char>, operator: OpKind, assign: OpKind) -> Token {
-        let pos = reader.get_pos();
+    /// Section 12.9.6 [Template Literal Lexical Components](https://tc39.es/ecma262/#sec-template-literal-lexical-components)
+    ///
+    /// Lexes the characters of a template up to and including the `` ` `` ending it or the `${` starting a substitution,
+    /// given the `` ` `` starting the template, or the `}` ending the previous substitution.
+    fn lex_template(&self, reader: &mut Reader<'a>, head: bool) -> Result<TokenKind, LexerError> {
+        let start = reader.get_pos() - 1;
+        // As for string literals, escape sequences are only lexed to be validated. Templates may contain invalid escape
+        // sequences when tagged, which leave them without a cooked value, so only the end of the template is found.
+        let mut escaped = Vec::new();
+
+        let substitution = loop {
+            match reader.peek_single() {
+                None => {
+                    return Err(LexerError { span: Span::new(start, reader.get_pos()), error: LexerErrorKind::UnterminatedTemplate });
+                }
+                Some('`') => break false,
+                Some('$') if reader.starts_with("${") => break true,
+                Some('\\') => {
+                    reader.bump();
+                    if let Err(LexerErrorKind::UnterminatedString) = lex_escape(reader, &mut escaped, true) {
+                        let span = Span::new(start, reader.get_pos());
+                        return Err(LexerError { span, error: LexerErrorKind::UnterminatedTemplate });
+                    }
+                }
+                Some(_) => reader.bump(),
+            }
+        };
+
+        if substitution {
+            reader.skip(2);
+            self.braces.borrow_mut().push(true);
+        } else {
+            reader.bump();
+        }
+
+        Ok(TokenKind::Template(match (head, substitution) {
+            (true, false) => TemplateKind::NoSubstitution,
+            (true, true) => TemplateKind::Head,
+            (false, true) => TemplateKind::Middle,
+            (false, false) => TemplateKind::Tail,
+        }))
+    }
+
+    /// Section 12.7 [Names and Keywords](https://tc39.es/ecma262/#sec-names-and-keywords)
+    ///
+    /// Given the first char of an identifier name, lexes an identifier, keyword or literal. Names containing escape
+    /// sequences are always identifiers, even if they spell a keyword.
+    fn lex_identifier(&self, reader: &mut Reader<'a>, first: char) -> Result<TokenKind, LexerError> {
+        let name = self.lex_identifier_name(reader, first)?;
+        if let Cow::Owned(name) = name {
+            return Ok(TokenKind::Id(Symbol::intern(&name)));
+        }
+
+        Ok(if let Some(keyword) = map_keyword(&name) {
+            TokenKind::Keyword(keyword)
+        } else if name == "true" {
+            TokenKind::Lit(LitKind::Bool(BooleanKind::True))
+        } else if name == "false" {
+            TokenKind::Lit(LitKind::Bool(BooleanKind::False))
+        } else if name == "null" {
+            TokenKind::Lit(LitKind::Null)
+        } else {
+            TokenKind::Id(Symbol::intern(&name))
+        })
+    }
+
+    /// Handles private names of class members, which are identifier names prefixed with `#`.
+    fn lex_private_identifier(&self, reader: &mut Reader<'a>) -> Result<TokenKind, LexerError> {
         match reader.peek_single() {
-            Some(second) => match second {
-                '=' => {
+            Some(peek) if is_identifier_start(peek) || is_escape_ahead(reader) => {
+                reader.bump();
+                Ok(TokenKind::PrivateId(Symbol::intern(&self.lex_identifier_name(reader, peek)?)))
+            }
+            _ => Ok(TokenKind::Unicode('#')),
+        }
+    }
+
+    /// Section 12.7 [IdentifierName](https://tc39.es/ecma262/#prod-IdentifierName)
+    ///
+    /// Given the first char, which is an identifier start char or the `\` of a `\u` escape sequence, lexes the rest of
+    /// an identifier name. Each escape sequence must encode a char allowed where it appears. Names without escape
+    /// sequences are borrowed from the source.
+    fn lex_identifier_name(&self, reader: &mut Reader<'a>, first: char) -> Result<Cow<'a, str>, LexerError> {
+        let start = reader.get_pos() - first.len_utf8();
+        if first != '\\' {
+            while let Some(peek) = reader.peek_single() && is_identifier_part(peek) {
+                reader.bump();
+            }
+            if !is_escape_ahead(reader) {
+                return Ok(Cow::Borrowed(reader.range(start, reader.get_pos())));
+            }
+            // Lexed again char by char, which only names containing escape sequences need.
+            reader.set_pos(start + first.len_utf8());
+        }
+
+        let mut name = String::new();
+        let mut next = Some(first);
+
+        while let Some(char) = next {
+            if char == '\\' {
+                let start = reader.get_pos() - 1;
+                reader.bump();
+                match lex_unicode_escape(reader).and_then(char::from_u32) {
+                    Some(escaped) if name.is_empty() && is_identifier_start(escaped) => name.push(escaped),
+                    Some(escaped) if !name.is_empty() && is_identifier_part(escaped) => name.push(escaped),
+                    _ => return Err(LexerError { span: Span::new(start, reader.get_pos()), error: LexerErrorKind::InvalidEscape }),
+                }
+            } else {
+                name.push(char);
+            }
+
+            next = match reader.peek_single() {
+                Some(peek) if is_identifier_part(peek) || is_escape_ahead(reader) => {
+                    reader.bump();
+                    Some(peek)
+                }
+                _ => None,
+            };
+        }
+
+        Ok(Cow::Owned(name))
+    }
+
+    /// Section 12.9.3 [Numeric Literals](https://tc39.es/ecma262/#sec-literals-numeric-literals)
+    ///
+    /// Given the first character of a numeric literal, which is a digit or a `.` followed by a digit, lexes the rest
+    /// of the literal to its Number or BigInt value.
+    fn lex_numeric(&self, reader: &mut Reader<'a>, first: char, start: usize) -> Result<TokenKind, LexerError> {
+        let lit = self.lex_numeric_value(reader, first, start);
+
+        // The source character immediately following a numeric literal must not be an identifier start or a digit, as
+        // in `3in` or `1_`.
+        let followed = matches!(reader.peek_single(), Some(peek) if is_identifier_part(peek)) || is_escape_ahead(reader);
+
+        match lit {
+            Ok(lit) if !followed => Ok(TokenKind::Lit(lit)),
+            lit => {
+                while let Some(peek) = reader.peek_single() && is_identifier_part(peek) {
                     reader.bump();
-                    Token::new(TokenKind::Punc(PuncKind::Op(assign)), pos)
                 }
-                _ => Token::new(TokenKind::Punc(PuncKind::Op(operator)), pos),
+                let error = lit.err().unwrap_or(LexerErrorKind::InvalidNumericLiteral);
+                Err(LexerError { span: Span::new(start, reader.get_pos()), error })
+            }
+        }
+    }
+
+    fn lex_numeric_value(&self, reader: &mut Reader<'a>, first: char, start: usize) -> Result<LitKind, LexerErrorKind> {
+        let invalid = LexerErrorKind::InvalidNumericLiteral;
+
+        // The literal without separators, as understood by `f64::from_str`.
+        let mut literal = first.to_string();
+        // Only integers without a leading zero, other than `0` itself, can have a BigInt suffix.
+        let mut big_int = true;
+
+        match first {
+            '.' => literal += &lex_digits(reader, 10, false).ok_or(invalid.clone())?,
+            '0' => match reader.peek_single() {
+                // NonDecimalIntegerLiteral, such as `0x1F`, `0o17` or `0b1010`.
+                Some(prefix @ ('x' | 'X' | 'o' | 'O' | 'b' | 'B')) => {
+                    reader.bump();
+                    let radix = match prefix.to_ascii_lowercase() {
+                        'x' => 16,
+                        'o' => 8,
+                        _ => 2,
+                    };
+                    let digits = lex_digits(reader, radix, false).ok_or(invalid.clone())?;
+                    if digits.is_empty() {
+                        return Err(invalid);
+                    }
+                    if reader.peek_single() == Some('n') {
+                        reader.bump();
+                        return Ok(LitKind::BigInt);
+                    }
+                    return to_decimal(&digits, radix).parse().map(LitKind::Num).map_err(|_| invalid);
+                }
+                // Section B.1.1 LegacyOctalIntegerLiteral, such as `017`, and NonOctalDecimalIntegerLiteral, such as
+                // `08`, which can not contain separators.
+                Some('0'..='9') => {
+                    let mut digits = String::new();
+                    while let Some(peek @ '0'..='9') = reader.peek_single() {
+                        digits.push(peek);
+                        reader.bump();
+                    }
+                    self.record_legacy_octal(Span::new(start, reader.get_pos()));
+                    if digits.chars().all(|digit| digit.is_digit(8)) {
+                        return to_decimal(&digits, 8).parse().map(LitKind::Num).map_err(|_| invalid);
+                    }
+                    literal += &digits;
+                    big_int = false;
+                }
+                _ => {}
             },
-            None => Token::new(TokenKind::Punc(PuncKind::Op(operator)), pos),
+            _ => literal += &lex_digits(reader, 10, true).ok_or(invalid.clone())?,
+        }
+
+        if first != '.' && reader.peek_single() == Some('.') {
+            reader.bump();
+            literal.push('.');
+            literal += &lex_digits(reader, 10, false).ok_or(invalid.clone())?;
+            big_int = false;
+        }
+
+        if let Some('e' | 'E') = reader.peek_single() {
+            reader.bump();
+            literal.push('e');
+            if let Some(sign @ ('+' | '-')) = reader.peek_single() {
+                reader.bump();
+                literal.push(sign);
+            }
+            let exponent = lex_digits(reader, 10, false).ok_or(invalid.clone())?;
+            if exponent.is_empty() {
+                return Err(invalid);
+            }
+            literal += &exponent;
+            big_int = false;
+        }
+
+        if first == '.' {
+            big_int = false;
+        }
+
+        if big_int && reader.peek_single() == Some('n') {
+            reader.bump();
+            return Ok(LitKind::BigInt);
+        }
+
+        // Parsing is correctly rounded to the nearest f64, with ties to even, as the spec requires.
+        literal.parse().map(LitKind::Num).map_err(|_| invalid)
+    }
+}
+
+/// Section 12.8 [Punctuators](https://tc39.es/ecma262/#sec-punctuators)
+///
+/// Given the first character, lexes the longest punctuator it starts. `/`, `{` and `}` are left to the caller as they
+/// also start comments and regular expressions or close template substitutions.
+fn lex_punctuator(reader: &mut Reader, first: char) -> Option<PuncKind> {
+    let op = match first {
+        '(' => return Some(PuncKind::Parentheses(ParenthesesKind::Left)),
+        ')' => return Some(PuncKind::Parentheses(ParenthesesKind::Right)),
+        '[' => return Some(PuncKind::Bracket(BracketKind::Left)),
+        ']' => return Some(PuncKind::Bracket(BracketKind::Right)),
+        ';' => return Some(PuncKind::SemiColon),
+        ',' => return Some(PuncKind::Comma),
+        ':' => return Some(PuncKind::Colon),
+        '.' if reader.starts_with("..") => {
+            reader.skip(2);
+            OpKind::Spread
+        }
+        '.' => return Some(PuncKind::Dot),
+        '=' if reader.eat('>') => OpKind::Arrow,
+        '=' if reader.eat('=') => if reader.eat('=') { OpKind::StrictEquality } else { OpKind::Equal },
+        '=' => OpKind::Assign,
+        '!' if reader.eat('=') => if reader.eat('=') { OpKind::StrictInequality } else { OpKind::NotEqual },
+        '!' => OpKind::Not,
+        '+' if reader.eat('+') => OpKind::Increment,
+        '+' => lex_assignable_operator(reader, OpKind::Addition, OpKind::AdditonAssign),
+        '-' if reader.eat('-') => OpKind::Decrement,
+        '-' => lex_assignable_operator(reader, OpKind::Subtraction, OpKind::SubtractionAssign),
+        '*' if reader.eat('*') => lex_assignable_operator(reader, OpKind::Exponential, OpKind::ExponentialAssign),
+        '*' => lex_assignable_operator(reader, OpKind::Multiplication, OpKind::MultiplicationAssign),
+        '%' => lex_assignable_operator(reader, OpKind::Mod, OpKind::ModAssign),
+        '&' if reader.eat('&') => lex_assignable_operator(reader, OpKind::And, OpKind::AndAssign),
+        '&' => lex_assignable_operator(reader, OpKind::BitAnd, OpKind::BitAndAssign),
+        '|' if reader.eat('|') => lex_assignable_operator(reader, OpKind::Or, OpKind::OrAssign),
+        '|' => lex_assignable_operator(reader, OpKind::BitOr, OpKind::BitOrAssign),
+        '^' => lex_assignable_operator(reader, OpKind::BitXor, OpKind::BitXorAssign),
+        '~' => OpKind::BitNot,
+        '<' if reader.eat('<') => lex_assignable_operator(reader, OpKind::LeftShift, OpKind::LeftShiftAssign),
+        '<' => lex_assignable_operator(reader, OpKind::LessThan, OpKind::LessThanEqual),
+        '>' if reader.starts_with(">>") => {
+            reader.skip(2);
+            lex_assignable_operator(reader, OpKind::ZeroFillRightShift, OpKind::UnsignedRightShiftAssign)
+        }
+        '>' if reader.eat('>') => lex_assignable_operator(reader, OpKind::RightShift, OpKind::RightShiftAssign),
+        '>' => lex_assignable_operator(reader, OpKind::GreaterThan, OpKind::GreaterThanEqual),
+        '?' if reader.eat('?') => lex_assignable_operator(reader, OpKind::NullishCoalescing, OpKind::NullishCoalescingAssign),
+        // `a?.5:b` is a conditional expression, not an optional chain.
+        '?' if reader.peek_single() == Some('.') && !matches!(reader.peek_nth(1), Some('0'..='9')) => {
+            reader.bump();
+            OpKind::OptionalChain
+        }
+        '?' => return Some(PuncKind::Question),
+        _ => return None,
+    };
+    Some(PuncKind::Op(op))
+}
+
+/// Lexes `assign` if the operator is followed by `=`, and `operator` otherwise.
+fn lex_assignable_operator(reader: &mut Reader, operator: OpKind, assign: OpKind) -> OpKind {
+    if reader.eat('=') {
+        assign
+    } else {
+        operator
+    }
+}
+
+/// Section 12.2 [White Space](https://tc39.es/ecma262/#sec-white-space)
+fn white_space_kind(char: char) -> Option<WhiteSpaceKind> {
+    match char {
+        ' ' => Some(WhiteSpaceKind::Space),
+        '\t' => Some(WhiteSpaceKind::HorizontalTabulation),
+        '\u{000B}' => Some(WhiteSpaceKind::VerticalTabulation),
+        '\u{000C}' => Some(WhiteSpaceKind::FormFeed),
+        '\u{00A0}' => Some(WhiteSpaceKind::NoBreakSpace),
+        '\u{FEFF}' => Some(WhiteSpaceKind::ZeroWidthNoBreakSpace),
+        // The other chars of the Space_Separator category.
+        '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => {
+            Some(WhiteSpaceKind::SpaceSeparator(char))
+        }
+        _ => None,
+    }
+}
+
+/// Section 12.7 [IdentifierStartChar](https://tc39.es/ecma262/#prod-IdentifierStartChar)
+fn is_identifier_start(char: char) -> bool {
+    char == '$' || char == '_' || UnicodeID::is_id_start(char)
+}
+
+/// Section 12.7 [IdentifierPartChar](https://tc39.es/ecma262/#prod-IdentifierPartChar), which includes the zero width
+/// non-joiner and joiner.
+fn is_identifier_part(char: char) -> bool {
+    char == '$' || char == '\u{200C}' || char == '\u{200D}' || UnicodeID::is_id_continue(char)
+}
+
+/// Whether a `\u` escape sequence is next, which may continue an identifier name.
+fn is_escape_ahead(reader: &Reader) -> bool {
+    reader.starts_with("\\u")
+}
+
+/// Section 12.4 [Comments](https://tc39.es/ecma262/#sec-comments)
+///
+/// Given the opening delimiter, lexes the rest of a comment up to the end of the line.
+fn lex_single_line_comment(reader: &mut Reader) {
+    while let Some(peek) = reader.peek_single() && !matches!(peek, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+        reader.bump();
+    }
+}
+
+/// Section 12.9.3 [NumericLiteralSeparator](https://tc39.es/ecma262/#prod-NumericLiteralSeparator)
+///
+/// Lexes a run of digits in the provided radix, which may contain single `_` separators between digits. `continued` is
+/// whether the run continues a digit that was already lexed. Returns the digits without separators, or [None] if a
+/// separator is misplaced.
+fn lex_digits(reader: &mut Reader, radix: u32, continued: bool) -> Option<String> {
+    let mut digits = String::new();
+    let mut after_digit = continued;
+    let mut after_separator = false;
+    while let Some(peek) = reader.peek_single() {
+        if peek == '_' {
+            if !after_digit {
+                return None;
+            }
+            after_digit = false;
+            after_separator = true;
+        } else if peek.is_digit(radix) {
+            digits.push(peek);
+            after_digit = true;
+            after_separator = false;
+        } else {
+            break;
+        }
+        reader.bump();
+    }
+    (!after_separator).then_some(digits)
+}
+
+/// Lexes exactly `count` hexadecimal digits to their value.
+fn lex_hex_digits(reader: &mut Reader, count: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..count {
+        let digit = reader.peek_single()?.to_digit(16)?;
+        reader.bump();
+        value = value * 16 + digit;
+    }
+    Some(value)
+}
+
+/// Section 12.9.4 [UnicodeEscapeSequence](https://tc39.es/ecma262/#prod-UnicodeEscapeSequence)
+///
+/// Lexes the code point of a Unicode escape sequence following `\u`, which is either four hexadecimal digits or a code
+/// point up to U+10FFFF in braces, such as `\u{1F600}`.
+fn lex_unicode_escape(reader: &mut Reader) -> Option<u32> {
+    if reader.peek_single() != Some('{') {
+        return lex_hex_digits(reader, 4);
+    }
+    reader.bump();
+
+    let mut value: u32 = 0;
+    let mut empty = true;
+    while let Some(digit) = reader.peek_single()?.to_digit(16) {
+        reader.bump();
+        value = value.saturating_mul(16).saturating_add(digit);
+        empty = false;
+    }
+
+    if reader.peek_single()? != '}' {
+        return None;
+    }
+    reader.bump();
+    (!empty && value <= 0x10FFFF).then_some(value)
+}
+
+/// Section 12.9.4 [EscapeSequence](https://tc39.es/ecma262/#prod-EscapeSequence)
+///
+/// Lexes the escape sequence following a `\`, adding the code units it represents to `cooked`, and returns whether it
+/// is a legacy octal escape sequence. Templates do not allow those even outside of strict mode code.
+fn lex_escape(reader: &mut Reader, cooked: &mut Vec<u16>, template: bool) -> Result<bool, LexerErrorKind> {
+    let Some(escape) = reader.next_single() else {
+        return Err(LexerErrorKind::UnterminatedString);
+    };
+
+    let unit = match escape {
+        // LineContinuation, which adds nothing to the value.
+        '\n' | '\u{2028}' | '\u{2029}' => return Ok(false),
+        '\r' => {
+            reader.eat('\n');
+            return Ok(false);
+        }
+        'b' => 0x08,
+        'f' => 0x0C,
+        'n' => 0x0A,
+        'r' => 0x0D,
+        't' => 0x09,
+        'v' => 0x0B,
+        '0' if !matches!(reader.peek_single(), Some('0'..='9')) => 0,
+        // Section B.1.2 LegacyOctalEscapeSequence, which is at most 0o377.
+        '0'..='7' => {
+            if template {
+                return Err(LexerErrorKind::LegacyOctalEscape);
+            }
+            let mut value = escape.to_digit(8).unwrap_or_default();
+            let max_digits = if value <= 3 { 3 } else { 2 };
+            for _ in 1..max_digits {
+                match reader.peek_single().and_then(|peek| peek.to_digit(8)) {
+                    Some(digit) => {
+                        value = value * 8 + digit;
+                        reader.bump();
+                    }
+                    None => break,
+                }
+            }
+            cooked.push(value as u16);
+            return Ok(true);
+        }
+        // NonOctalDecimalEscapeSequence, which is the digit itself.
+        '8' | '9' => {
+            if template {
+                return Err(LexerErrorKind::LegacyOctalEscape);
+            }
+            cooked.push(escape as u16);
+            return Ok(true);
+        }
+        'x' => lex_hex_digits(reader, 2).ok_or(LexerErrorKind::InvalidEscape)? as u16,
+        'u' => {
+            let code_point = lex_unicode_escape(reader).ok_or(LexerErrorKind::InvalidEscape)?;
+            match char::from_u32(code_point) {
+                Some(char) => cooked.extend(char.encode_utf16(&mut [0; 2]).iter()),
+                // A surrogate, which may pair with one that follows.
+                None => cooked.push(code_point as u16),
+            }
+            return Ok(false);
         }
+        // NonEscapeCharacter, which is the character itself.
+        _ => {
+            cooked.extend(escape.encode_utf16(&mut [0; 2]).iter());
+            return Ok(false);
+        }
+    };
+    cooked.push(unit);
+    Ok(false)
+}
+
+/// The string value of the characters of a string literal or template, given their source text between the
+/// delimiters, which the lexer has already lexed without errors. [None] if a template contains an invalid escape
+/// sequence.
+fn cook(source: &str, template: bool) -> Option<Utf16String> {
+    if !source.contains(['\\', '\r']) {
+        return Some(source.into());
     }
+
+    // UTF-16 code units, as escaped surrogates may only form a character together, or not at all.
+    let mut cooked = Vec::with_capacity(source.len());
+    let mut reader = Reader::init(source);
+    while let Some(char) = reader.next_single() {
+        match char {
+            '\\' => {
+                lex_escape(&mut reader, &mut cooked, template).ok()?;
+            }
+            // Line terminator sequences in templates are normalized to a line feed.
+            '\r' => {
+                reader.eat('\n');
+                cooked.push(0x0A);
+            }
+            _ => cooked.extend(char.encode_utf16(&mut [0; 2]).iter()),
+        }
+    }
+    Some(Utf16String(cooked))
+}
+
+/// The decimal digits of the value of a BigInt literal, given its source text.
+fn big_int_value(source: &str) -> String {
+    let literal = source.strip_suffix('n').unwrap_or(source);
+    let (digits, radix) = match literal.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => (&literal[2..], 16),
+        Some("0o") => (&literal[2..], 8),
+        Some("0b") => (&literal[2..], 2),
+        _ => (literal, 10),
+    };
+    to_decimal(digits, radix)
+}
+
+/// Converts digits in the provided radix to decimal digits, which is the form BigInt values are kept in, and which
+/// `f64::from_str` rounds correctly however many digits there are.
+fn to_decimal(digits: &str, radix: u32) -> String {
+    const BASE: u64 = 1_000_000_000;
+
+    // Little endian limbs of 9 decimal digits each.
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits.chars().filter_map(|digit| digit.to_digit(radix)) {
+        let mut carry = digit as u64;
+        for limb in limbs.iter_mut() {
+            let value = *limb * radix as u64 + carry;
+            *limb = value % BASE;
+            carry = value / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    limbs.iter().rev().enumerate()
+        .map(|(i, limb)| if i == 0 { limb.to_string() } else { format!("{:09}", limb) })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::token::{BooleanKind, BracketKind, KeywordKind, LitKind, OpKind, ParenthesesKind, PuncKind, TokenKind};
+    use crate::ast::{LitValue, RegExLit, StringLit, Utf16String};
+    use crate::token::{BooleanKind, BraceKind, BracketKind, Comment, CommentKind, KeywordKind, LitKind, OpKind, ParenthesesKind, PuncKind, TemplateKind, Token, TokenKind};
 
-    use super::Lexer;
+    use crate::span::Span;
+    use super::{Lexer, LexerError, LexerErrorKind};
 
     #[test]
     fn test_tokenize() {
         let mut lexer = Lexer::init("testing 123");
         let res = lexer.tokenize().unwrap();
         assert_eq!(TokenKind::Id("testing".into()), res[0].kind);
-        assert_eq!(TokenKind::Lit(LitKind::Num(123)), res[1].kind);
+        assert_eq!(TokenKind::Lit(LitKind::Num(123.0)), res[1].kind);
     }
 
     #[test]
@@ -376,6 +1018,14 @@ mod tests {
         let mut lexer = Lexer::init("");
         let res = lexer.tokenize().unwrap();
         assert_eq!(TokenKind::Eof, res[0].kind);
+
+        // A byte order mark, vertical tab, form feed and any Space_Separator char are white space too.
+        let mut lexer = Lexer::init("\u{FEFF}a\u{B}\u{C}\u{A0}b\u{3000}\u{2003}c");
+        let res = lexer.tokenize().unwrap();
+        let kinds: Vec<_> = res.iter().map(|token| token.kind).collect();
+        assert_eq!(kinds, vec![TokenKind::Id("a".into()), TokenKind::Id("b".into()), TokenKind::Id("c".into()), TokenKind::Eof]);
+        assert_eq!(res[0].span, Span::new(3, 4));
+        assert!(!res[1].preceded_by_newline);
     }
 
     #[test]
@@ -400,71 +1050,548 @@ mod tests {
     fn test_string_literal() {
         let mut lexer = Lexer::init("\"true\"");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Lit(LitKind::String("true".into())), res[0].kind);
+        assert_eq!(TokenKind::Lit(LitKind::String), res[0].kind);
+        assert_eq!(lexer.lit_value(LitKind::String, res[0].span), string("true", "true"));
+
+        let strings = [
+            (r#""hello world 123""#, "hello world 123"),
+            (r#"'single "quoted"'"#, "single \"quoted\""),
+            (r#""it's""#, "it's"),
+            (r#"''"#, ""),
+            (r#""\n\t\r\b\f\v\0""#, "\n\t\r\u{8}\u{c}\u{b}\0"),
+            (r#""\'\"\\""#, "'\"\\"),
+            (r#""\x41\u0042\u{43}\u{0001F600}""#, "ABC😀"),
+            (r#""\uD83D\uDE00""#, "😀"),
+            (r#""\a\c\é""#, "acé"),
+            ("\"a\\\nb\\\r\nc\\\u{2028}d\"", "abcd"),
+            ("\"a\u{2028}b\"", "a\u{2028}b"),
+            (r#""\101\7\08\400\8""#, "A\u{7}\u{0}8\u{20}08"),
+        ];
+        for (source, cooked) in strings {
+            let mut lexer = Lexer::init(source);
+            let res = lexer.tokenize().unwrap();
+            let raw = &source[1..source.len() - 1];
+            assert_eq!(TokenKind::Lit(LitKind::String), res[0].kind, "{}", source);
+            assert_eq!(lexer.lit_value(LitKind::String, res[0].span), string(cooked, raw), "{}", source);
+            assert_eq!(TokenKind::Eof, res[1].kind, "{}", source);
+        }
+
+        let invalid = [
+            (r#""abc"#, LexerErrorKind::UnterminatedString, Span::new(0, 4)),
+            ("'abc\n'", LexerErrorKind::UnterminatedString, Span::new(0, 4)),
+            (r#""abc'"#, LexerErrorKind::UnterminatedString, Span::new(0, 5)),
+            (r#""abc\"#, LexerErrorKind::UnterminatedString, Span::new(0, 5)),
+            (r#""a\x4g""#, LexerErrorKind::InvalidEscape, Span::new(2, 5)),
+            (r#""a\u12""#, LexerErrorKind::InvalidEscape, Span::new(2, 6)),
+            (r#""a\u{}""#, LexerErrorKind::InvalidEscape, Span::new(2, 6)),
+            (r#""a\u{110000}""#, LexerErrorKind::InvalidEscape, Span::new(2, 12)),
+            (r#""a\u{41""#, LexerErrorKind::InvalidEscape, Span::new(2, 7)),
+        ];
+        for (source, error, span) in invalid {
+            let err = Lexer::init(source).tokenize().unwrap_err();
+            assert_eq!((err.error, err.span), (error, span), "{}", source);
+        }
+
+        // Legacy octal escapes are only recorded, as whether they are allowed depends on the code being strict.
+        for (source, end) in [(r#""\01\02""#, 4), (r#""\7""#, 3), (r#""\08""#, 3), (r#""\8""#, 3), (r#""\9""#, 3)] {
+            let res = Lexer::init(source).tokenize().unwrap();
+            assert_eq!(res[0].legacy_octal, Some(Span::new(1, end)), "{}", source);
+        }
+        let mut lexer = Lexer::init(r#""\0""#);
+        let res = lexer.tokenize().unwrap();
+        assert_eq!(lexer.lit_value(LitKind::String, res[0].span), string("\0", "\\0"));
+        assert_eq!(res[0].legacy_octal, None);
+
+        // Lone surrogates are kept as they are, rather than replaced.
+        let mut lexer = Lexer::init(r#""a\uD83D\uDE00\uD83D""#);
+        let res = lexer.tokenize().unwrap();
+        let LitValue::String(lit) = lexer.lit_value(LitKind::String, res[0].span) else { panic!("Expected string") };
+        assert_eq!(lit.cooked, Utf16String(vec![0x61, 0xD83D, 0xDE00, 0xD83D]));
+        assert!(!lit.cooked.is_well_formed());
+        assert_eq!(format!("{:?}", lit.cooked), r#""a😀\u{d83d}""#);
+        assert_eq!(serde_json::to_string(&lit.cooked).unwrap(), "[97,55357,56832,55357]");
+        assert_eq!(serde_json::to_string(&Utf16String::from("a😀")).unwrap(), r#""a😀""#);
+    }
+
+    #[test]
+    fn test_template() {
+        // The cooked and raw characters of each template token, read back from the source.
+        let elements = |lexer: &Lexer, tokens: &[Token]| -> Vec<(Option<Utf16String>, String)> {
+            tokens.iter().filter_map(|token| match token.kind {
+                TokenKind::Template(kind) => {
+                    let element = lexer.template_element(kind, token.span);
+                    Some((element.cooked, element.raw))
+                }
+                _ => None,
+            }).collect()
+        };
+        let chars = |cooked: Option<&str>, raw: &str| (cooked.map(Into::into), raw.into());
+
+        let mut lexer = Lexer::init("`a${ {b} }c${d}\\u`");
+        let res = lexer.tokenize().unwrap();
+        let kinds = res.iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![
+            TokenKind::Template(TemplateKind::Head),
+            TokenKind::Punc(PuncKind::Brace(BraceKind::Left)),
+            TokenKind::Id("b".into()),
+            TokenKind::Punc(PuncKind::Brace(BraceKind::Right)),
+            TokenKind::Template(TemplateKind::Middle),
+            TokenKind::Id("d".into()),
+            TokenKind::Template(TemplateKind::Tail),
+            TokenKind::Eof,
+        ]);
+        assert_eq!(elements(&lexer, &res), vec![chars(Some("a"), "a"), chars(Some("c"), "c"), chars(None, "\\u")]);
+        assert_eq!(lexer.template_element(TemplateKind::Middle, res[4].span).span, Span::new(10, 11));
+
+        let mut lexer = Lexer::init("`a\r\nb\rc$d{e}`");
+        let res = lexer.tokenize().unwrap();
+        assert_eq!(res[0].kind, TokenKind::Template(TemplateKind::NoSubstitution));
+        assert_eq!(elements(&lexer, &res), vec![chars(Some("a\nb\nc$d{e}"), "a\nb\nc$d{e}")]);
+
+        // Octal escapes are not allowed in templates, even outside of strict mode code.
+        let mut lexer = Lexer::init("`\\0\\01`");
+        let res = lexer.tokenize().unwrap();
+        assert_eq!(elements(&lexer, &res), vec![chars(None, "\\0\\01")]);
+
+        let mut lexer = Lexer::init("`\\uDE00`");
+        let res = lexer.tokenize().unwrap();
+        assert_eq!(elements(&lexer, &res), vec![(Some(Utf16String(vec![0xDE00])), "\\uDE00".into())]);
+
+        for source in ["`abc", "`a${b}c", "`a\\"] {
+            let err = Lexer::init(source).tokenize().unwrap_err();
+            assert_eq!(err.error, LexerErrorKind::UnterminatedTemplate, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_rescan_regex() {
+        let regex = |pattern: &str, flags: &str| RegExLit { pattern: pattern.into(), flags: flags.into() };
+
+        // Lexes up to the first `/` or `/=` token, and then again from it as a regular expression literal.
+        fn rescan(source: &str) -> Result<(Lexer<'_>, Vec<Token>), LexerError> {
+            let mut lexer = Lexer::init(source);
+            loop {
+                let token = lexer.next_token()?;
+                if let TokenKind::Punc(PuncKind::Op(OpKind::Division | OpKind::DivisionAssign)) = token.kind {
+                    let regex = lexer.rescan_regex(&token, lexer.checkpoint())?;
+                    let mut tokens = vec![regex];
+                    tokens.extend(lexer.tokenize()?);
+                    return Ok((lexer, tokens));
+                }
+            }
+        }
+
+        let (lexer, res) = rescan("x = /[/]\\//gi.y").unwrap();
+        assert_eq!(res[0].kind, TokenKind::Lit(LitKind::RegEx));
+        assert_eq!(res[0].span, Span::new(4, 13));
+        assert_eq!(lexer.regex_lit(res[0].span), regex("[/]\\/", "gi"));
+        assert_eq!(res[1].kind, TokenKind::Punc(PuncKind::Dot));
+        assert_eq!(res[3].kind, TokenKind::Eof);
+
+        // A `}` in the pattern does not continue the template it is substituted in.
+        let (lexer, res) = rescan("`${/}/}`").unwrap();
+        let kinds = res.iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![TokenKind::Lit(LitKind::RegEx), TokenKind::Template(TemplateKind::Tail), TokenKind::Eof]);
+        assert_eq!(lexer.regex_lit(res[0].span), regex("}", ""));
+        assert_eq!(lexer.template_element(TemplateKind::Tail, res[1].span).raw, "");
+
+        // Comments lexed past the `/` are forgotten, and lexed again if they follow the literal.
+        let mut lexer = Lexer::init("/a/*b*/ /* c */");
+        let slash = lexer.next_token().unwrap();
+        lexer.next_token().unwrap();
+        lexer.rescan_regex(&slash, lexer.checkpoint()).unwrap();
+        lexer.tokenize().unwrap();
+        let comments = lexer.take_comments().into_values().collect::<Vec<_>>();
+        assert_eq!(comments, vec![comment(CommentKind::MultiLine, " c ")]);
+
+        for (source, error, span) in [
+            ("/abc", LexerErrorKind::UnterminatedRegEx, Span::new(0, 4)),
+            ("/a\nb/", LexerErrorKind::UnterminatedRegEx, Span::new(0, 2)),
+            ("/[/\n]/", LexerErrorKind::UnterminatedRegEx, Span::new(0, 3)),
+            ("/a\\", LexerErrorKind::UnterminatedRegEx, Span::new(0, 3)),
+            ("/a/gg", LexerErrorKind::InvalidRegExFlags, Span::new(3, 5)),
+            ("/a/x", LexerErrorKind::InvalidRegExFlags, Span::new(3, 4)),
+            ("/a/uv", LexerErrorKind::InvalidRegExFlags, Span::new(3, 5)),
+        ] {
+            let err = rescan(source).unwrap_err();
+            assert_eq!((err.error, err.span), (error, span), "{}", source);
+        }
+        let (lexer, res) = rescan("/a/dgimsy").unwrap();
+        assert_eq!(lexer.regex_lit(res[0].span), regex("a", "dgimsy"));
+    }
+
+    #[test]
+    fn test_comments() {
+        let mut lexer = Lexer::init("#!/usr/bin/env node\na // b\n/* c\n*/ d /* e */ <!-- f\n --> g\nh-->i");
+        let res = lexer.tokenize().unwrap();
+        let tokens = res.iter().map(|token| (token.kind, token.preceded_by_newline)).collect::<Vec<_>>();
+        assert_eq!(tokens, vec![
+            (TokenKind::Id("a".into()), true),
+            (TokenKind::Id("d".into()), true),
+            (TokenKind::Id("h".into()), true),
+            (TokenKind::Punc(PuncKind::Op(OpKind::Decrement)), false),
+            (TokenKind::Punc(PuncKind::Op(OpKind::GreaterThan)), false),
+            (TokenKind::Id("i".into()), false),
+            (TokenKind::Eof, false),
+        ]);
+
+        let comments = lexer.take_comments().into_iter().collect::<Vec<_>>();
+        assert_eq!(comments, vec![
+            (Span::new(0, 19), comment(CommentKind::Hashbang, "/usr/bin/env node")),
+            (Span::new(22, 26), comment(CommentKind::SingleLine, " b")),
+            (Span::new(27, 34), comment(CommentKind::MultiLine, " c\n")),
+            (Span::new(37, 44), comment(CommentKind::MultiLine, " e ")),
+            (Span::new(45, 51), comment(CommentKind::HtmlOpen, " f")),
+            (Span::new(53, 58), comment(CommentKind::HtmlClose, " g")),
+        ]);
+
+        // A hashbang comment may only start the source, and `-->` only opens a comment at the start of a line.
+        let res = Lexer::init(" #!a").tokenize().unwrap();
+        assert_eq!(res[0].kind, TokenKind::Unicode('#'));
+        let res = Lexer::init("/* a */ --> b").tokenize().unwrap();
+        assert_eq!(res[0].kind, TokenKind::Eof);
+
+        // HTML-like comments are only comments in scripts.
+        let res = Lexer::init("a <!-- b
+--> c").module().tokenize().unwrap();
+        let kinds = res.into_iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![
+            TokenKind::Id("a".into()),
+            TokenKind::Punc(PuncKind::Op(OpKind::LessThan)),
+            TokenKind::Punc(PuncKind::Op(OpKind::Not)),
+            TokenKind::Punc(PuncKind::Op(OpKind::Decrement)),
+            TokenKind::Id("b".into()),
+            TokenKind::Punc(PuncKind::Op(OpKind::Decrement)),
+            TokenKind::Punc(PuncKind::Op(OpKind::GreaterThan)),
+            TokenKind::Id("c".into()),
+            TokenKind::Eof,
+        ]);
+
+        let err = Lexer::init("a /* b").tokenize().unwrap_err();
+        assert_eq!(err.error, LexerErrorKind::UnterminatedComment);
+        assert_eq!(err.span, Span::new(2, 6));
+    }
+
+    fn comment(kind: CommentKind, text: &str) -> Comment {
+        Comment { kind, text: text.into() }
+    }
+
+    fn string(cooked: &str, raw: &str) -> LitValue {
+        LitValue::String(StringLit { cooked: cooked.into(), raw: raw.into() })
     }
 
     #[test]
     fn test_string_identifier() {
         let mut lexer = Lexer::init("let test = new Tokenizer(\"debugger\");");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Keyword(KeywordKind::Let), res[0].kind);
+        assert_eq!(TokenKind::Id("let".into()), res[0].kind);
         assert_eq!(TokenKind::Id("test".into()), res[1].kind);
         assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), res[2].kind);
         assert_eq!(TokenKind::Keyword(KeywordKind::New), res[3].kind);
         assert_eq!(TokenKind::Id("Tokenizer".into()), res[4].kind);
         assert_eq!(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Left)), res[5].kind);
-        assert_eq!(TokenKind::Lit(LitKind::String("debugger".into())), res[6].kind);
+        assert_eq!(TokenKind::Lit(LitKind::String), res[6].kind);
+        assert_eq!(lexer.lit_value(LitKind::String, res[6].span), string("debugger", "debugger"));
         assert_eq!(TokenKind::Punc(PuncKind::Parentheses(ParenthesesKind::Right)), res[7].kind);
         assert_eq!(TokenKind::Punc(PuncKind::SemiColon), res[8].kind);
     }
 
     #[test]
-    fn test_operators() {
-        let mut lexer = Lexer::init("+= ");
-        let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::AdditonAssign)), res[0].kind);
+    fn test_identifiers() {
+        let ids = |source: &str| {
+            Lexer::init(source).tokenize().unwrap().into_iter()
+                .filter(|token| token.kind != TokenKind::Eof)
+                .map(|token| (token.kind, token.escaped))
+                .collect::<Vec<_>>()
+        };
+        let id = |name: &str, escaped: bool| (TokenKind::Id(name.into()), escaped);
+
+        assert_eq!(ids("my_var $el _private x1 $ _ $0"), vec![
+            id("my_var", false), id("$el", false), id("_private", false), id("x1", false), id("$", false), id("_", false),
+            id("$0", false),
+        ]);
+
+        // Unicode ID_Start and ID_Continue chars, and the zero width non-joiner and joiner.
+        assert_eq!(ids("café ℘ x\u{0301} ᢅ a\u{200C}b a\u{200D}"), vec![
+            id("café", false), id("℘", false), id("x\u{0301}", false), id("ᢅ", false), id("a\u{200C}b", false),
+            id("a\u{200D}", false),
+        ]);
 
-        let mut lexer = Lexer::init("*= 3");
+        assert_eq!(ids("\\u0061b\\u{63} a\\u{1D49C} \\u{24}_"), vec![
+            id("abc", true), id("a\u{1D49C}", true), id("$_", true),
+        ]);
+
+        // Escaped keywords and literals are identifiers.
+        assert_eq!(ids("\\u0069f \\u{74}rue n\\u0075ll"), vec![id("if", true), id("true", true), id("null", true)]);
+        assert_eq!(ids("if")[0].0, TokenKind::Keyword(KeywordKind::If));
+
+        assert_eq!(ids("#\\u0061b #$c #_"), vec![
+            (TokenKind::PrivateId("ab".into()), false), (TokenKind::PrivateId("$c".into()), false),
+            (TokenKind::PrivateId("_".into()), false),
+        ]);
+
+        // Escapes must encode a char allowed where they appear, and digits can not start an identifier.
+        for (source, span) in [("\\u0031a", Span::new(0, 6)), ("a\\u0020", Span::new(1, 7)), ("a\\u{110000}", Span::new(1, 11)),
+                               ("a\\uD835\\uDC9C", Span::new(1, 7)), ("a\\ux", Span::new(1, 3))] {
+            let err = Lexer::init(source).tokenize().unwrap_err();
+            assert_eq!((err.error, err.span), (LexerErrorKind::InvalidEscape, span), "{}", source);
+        }
+        assert_eq!(Lexer::init("1a").tokenize().unwrap_err().error, LexerErrorKind::InvalidNumericLiteral);
+    }
+
+    #[test]
+    fn test_punctuators() {
+        let punctuators = [
+            PuncKind::Brace(BraceKind::Left),
+            PuncKind::Brace(BraceKind::Right),
+            PuncKind::Bracket(BracketKind::Left),
+            PuncKind::Bracket(BracketKind::Right),
+            PuncKind::Parentheses(ParenthesesKind::Left),
+            PuncKind::Parentheses(ParenthesesKind::Right),
+            PuncKind::Colon,
+            PuncKind::Comma,
+            PuncKind::Dot,
+            PuncKind::Question,
+            PuncKind::SemiColon,
+            PuncKind::Op(OpKind::Addition),
+            PuncKind::Op(OpKind::Increment),
+            PuncKind::Op(OpKind::AdditonAssign),
+            PuncKind::Op(OpKind::Assign),
+            PuncKind::Op(OpKind::Subtraction),
+            PuncKind::Op(OpKind::Decrement),
+            PuncKind::Op(OpKind::SubtractionAssign),
+            PuncKind::Op(OpKind::Multiplication),
+            PuncKind::Op(OpKind::MultiplicationAssign),
+            PuncKind::Op(OpKind::Division),
+            PuncKind::Op(OpKind::DivisionAssign),
+            PuncKind::Op(OpKind::Exponential),
+            PuncKind::Op(OpKind::ExponentialAssign),
+            PuncKind::Op(OpKind::Mod),
+            PuncKind::Op(OpKind::ModAssign),
+            PuncKind::Op(OpKind::LeftShift),
+            PuncKind::Op(OpKind::LeftShiftAssign),
+            PuncKind::Op(OpKind::RightShift),
+            PuncKind::Op(OpKind::RightShiftAssign),
+            PuncKind::Op(OpKind::ZeroFillRightShift),
+            PuncKind::Op(OpKind::UnsignedRightShiftAssign),
+            PuncKind::Op(OpKind::GreaterThan),
+            PuncKind::Op(OpKind::LessThan),
+            PuncKind::Op(OpKind::Equal),
+            PuncKind::Op(OpKind::StrictEquality),
+            PuncKind::Op(OpKind::Not),
+            PuncKind::Op(OpKind::NotEqual),
+            PuncKind::Op(OpKind::StrictInequality),
+            PuncKind::Op(OpKind::GreaterThanEqual),
+            PuncKind::Op(OpKind::LessThanEqual),
+            PuncKind::Op(OpKind::BitAnd),
+            PuncKind::Op(OpKind::BitAndAssign),
+            PuncKind::Op(OpKind::And),
+            PuncKind::Op(OpKind::AndAssign),
+            PuncKind::Op(OpKind::BitOr),
+            PuncKind::Op(OpKind::BitOrAssign),
+            PuncKind::Op(OpKind::Or),
+            PuncKind::Op(OpKind::OrAssign),
+            PuncKind::Op(OpKind::BitXor),
+            PuncKind::Op(OpKind::BitXorAssign),
+            PuncKind::Op(OpKind::BitNot),
+            PuncKind::Op(OpKind::OptionalChain),
+            PuncKind::Op(OpKind::NullishCoalescing),
+            PuncKind::Op(OpKind::NullishCoalescingAssign),
+            PuncKind::Op(OpKind::Spread),
+            PuncKind::Op(OpKind::Arrow),
+        ];
+        for punc in punctuators {
+            // Each punctuator is lexed on its own, and again before a character that cannot extend it.
+            for source in [punc.as_str().to_string(), format!("{}a", punc.as_str())] {
+                let res = Lexer::init(&source).tokenize().unwrap();
+                assert_eq!(TokenKind::Punc(punc), res[0].kind, "{}", source);
+                assert_eq!(Span::new(0, punc.as_str().len()), res[0].span, "{}", source);
+            }
+        }
+
+        let sequences = [
+            ("a++ + b", vec![OpKind::Increment, OpKind::Addition]),
+            ("a-- - -b", vec![OpKind::Decrement, OpKind::Subtraction, OpKind::Subtraction]),
+            ("a+=b-=c", vec![OpKind::AdditonAssign, OpKind::SubtractionAssign]),
+            ("a!==b!=c", vec![OpKind::StrictInequality, OpKind::NotEqual]),
+            ("a>>>=b>>=c>=d", vec![OpKind::UnsignedRightShiftAssign, OpKind::RightShiftAssign, OpKind::GreaterThanEqual]),
+            ("a**=b*=c&=d|=e", vec![OpKind::ExponentialAssign, OpKind::MultiplicationAssign, OpKind::BitAndAssign, OpKind::BitOrAssign]),
+            ("a<<=b<=c", vec![OpKind::LeftShiftAssign, OpKind::LessThanEqual]),
+            ("a??b??=c", vec![OpKind::NullishCoalescing, OpKind::NullishCoalescingAssign]),
+            ("(...a)=>a", vec![OpKind::Spread, OpKind::Arrow]),
+        ];
+        for (source, expected) in sequences {
+            let ops: Vec<OpKind> = Lexer::init(source).tokenize().unwrap().into_iter()
+                .filter_map(|token| match token.kind {
+                    TokenKind::Punc(PuncKind::Op(op)) => Some(op),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(expected, ops, "{}", source);
+        }
+
+        // `?.` followed by a digit is a conditional, as in `a ? .5 : b`.
+        let res = Lexer::init("a?.5:b").tokenize().unwrap();
+        assert_eq!(TokenKind::Punc(PuncKind::Question), res[1].kind);
+        assert_eq!(TokenKind::Lit(LitKind::Num(0.5)), res[2].kind);
+        let res = Lexer::init("a?b:c").tokenize().unwrap();
+        assert_eq!(TokenKind::Punc(PuncKind::Question), res[1].kind);
+    }
+
+    #[test]
+    fn test_null_and_spread() {
+        let mut lexer = Lexer::init("null ...args a?.b");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::MultiplicationAssign)), res[0].kind);
+        assert_eq!(TokenKind::Lit(LitKind::Null), res[0].kind);
+        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Spread)), res[1].kind);
+        assert_eq!(TokenKind::Id("args".into()), res[2].kind);
+        assert_eq!(TokenKind::Id("a".into()), res[3].kind);
+        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::OptionalChain)), res[4].kind);
+        assert_eq!(TokenKind::Id("b".into()), res[5].kind);
+    }
 
-        let mut lexer = Lexer::init("**= 3");
+    #[test]
+    fn test_arrow() {
+        let mut lexer = Lexer::init("a => a == b");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::ExponentialAssign)), res[0].kind);
+        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Arrow)), res[1].kind);
+        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Equal)), res[3].kind);
+    }
 
-        let mut lexer = Lexer::init("** 3");
+    #[test]
+    fn test_preceded_by_newline() {
+        let mut lexer = Lexer::init("a\nb\r\n\n c d\u{2028}e");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Exponential)), res[0].kind);
+        let newlines: Vec<bool> = res.iter().map(|t| t.preceded_by_newline).collect();
+        assert_eq!(vec![false, true, true, false, true, false], newlines);
+        assert_eq!(TokenKind::Eof, res[5].kind);
+    }
 
-        let mut lexer = Lexer::init("& ");
+    #[test]
+    fn test_spans() {
+        // Spans are byte offsets, so `é` occupies two bytes.
+        let mut lexer = Lexer::init("let é = \"ü\" >>>= x");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::BitAnd)), res[0].kind);
+        let spans: Vec<(usize, usize)> = res.iter().map(|t| (t.span.start, t.span.end)).collect();
+        assert_eq!(vec![(0, 3), (4, 6), (7, 8), (9, 13), (14, 18), (19, 20), (20, 20)], spans);
 
-        let mut lexer = Lexer::init("&&= ");
+        let err = Lexer::init("a = \"abc\nd\"").tokenize().unwrap_err();
+        assert_eq!(Span::new(4, 8), err.span);
+    }
+
+    #[test]
+    fn test_private_identifier() {
+        let mut lexer = Lexer::init("this.#count #");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::AndAssign)), res[0].kind);
+        assert_eq!(TokenKind::PrivateId("count".into()), res[2].kind);
+        assert_eq!(TokenKind::Unicode('#'), res[3].kind);
     }
 
     #[test]
     fn test_keywords() {
-        let mut lexer = Lexer::init("await yield");
+        let mut lexer = Lexer::init("if var await yield");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Keyword(KeywordKind::Await), res[0].kind);
-        assert_eq!(TokenKind::Keyword(KeywordKind::Yield), res[1].kind);
+        assert_eq!(TokenKind::Keyword(KeywordKind::If), res[0].kind);
+        assert_eq!(TokenKind::Keyword(KeywordKind::Var), res[1].kind);
 
-        let mut lexer = Lexer::init("let x = await y;");
+        // Contextually reserved words are identifiers, which the parser recognizes as keywords where they are reserved.
+        assert_eq!(TokenKind::Id("await".into()), res[2].kind);
+        assert_eq!(TokenKind::Id("yield".into()), res[3].kind);
+
+        let mut lexer = Lexer::init("let static of async get set as from target meta");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Keyword(KeywordKind::Let), res[0].kind);
-        assert_eq!(TokenKind::Id("x".into()), res[1].kind);
-        assert_eq!(TokenKind::Punc(PuncKind::Op(OpKind::Assign)), res[2].kind);
-        assert_eq!(TokenKind::Keyword(KeywordKind::Await), res[3].kind);
-        assert_eq!(TokenKind::Id("y".into()), res[4].kind);
+        let names = ["let", "static", "of", "async", "get", "set", "as", "from", "target", "meta"];
+        for (token, name) in res.iter().zip(names) {
+            assert_eq!(TokenKind::Id(name.into()), token.kind);
+        }
     }
 
     #[test]
     fn test_numerics() {
         let mut lexer = Lexer::init("356 ");
         let res = lexer.tokenize().unwrap();
-        assert_eq!(TokenKind::Lit(LitKind::Num(356)), res[0].kind);
+        assert_eq!(TokenKind::Lit(LitKind::Num(356.0)), res[0].kind);
+
+        let numbers = [
+            ("1.5", 1.5),
+            (".5", 0.5),
+            ("1.", 1.0),
+            ("0.5", 0.5),
+            ("1e-9", 1e-9),
+            ("1E+3", 1000.0),
+            ("1.5e3", 1500.0),
+            ("0", 0.0),
+            ("0x1F", 31.0),
+            ("0X1f", 31.0),
+            ("0o17", 15.0),
+            ("0b1010", 10.0),
+            ("1_000_000", 1_000_000.0),
+            ("1_0.0_1e1_0", 10.01e10),
+            ("0xF_F", 255.0),
+            ("99999999999999999999", 1e20),
+            ("1e400", f64::INFINITY),
+            // Halfway between two f64 values, rounding to the one with an even significand.
+            ("9007199254740993", 9007199254740992.0),
+            ("0x20000000000001", 9007199254740992.0),
+            ("0x20000000000003", 9007199254740996.0),
+            ("017", 15.0),
+            ("08", 8.0),
+            ("019.5", 19.5),
+        ];
+        for (source, value) in numbers {
+            let res = Lexer::init(source).tokenize().unwrap();
+            assert_eq!(TokenKind::Lit(LitKind::Num(value)), res[0].kind, "{}", source);
+            assert_eq!(TokenKind::Eof, res[1].kind, "{}", source);
+        }
+
+        let big_ints = [
+            ("123n", "123"),
+            ("0n", "0"),
+            ("1_0n", "10"),
+            ("0x1Fn", "31"),
+            ("0b11n", "3"),
+            ("0xFFFFFFFFFFFFFFFFFFn", "4722366482869645213695"),
+        ];
+        for (source, value) in big_ints {
+            let mut lexer = Lexer::init(source);
+            let res = lexer.tokenize().unwrap();
+            assert_eq!(TokenKind::Lit(LitKind::BigInt), res[0].kind, "{}", source);
+            assert_eq!(lexer.lit_value(LitKind::BigInt, res[0].span), LitValue::BigInt(value.into()), "{}", source);
+        }
+
+        let invalid = [
+            "0x", "0b2", "0o8", "0x_1", "1__0", "1_", "1._5", "1_.5", "0_1", "1e", "1e+", "1e_1", "3in", "1.5n", "1e3n", "08n",
+            "017n", ".5n", "1n_", "0xGn",
+        ];
+        for source in invalid {
+            let err = Lexer::init(source).tokenize().unwrap_err();
+            assert_eq!(err.error, LexerErrorKind::InvalidNumericLiteral, "{}", source);
+            assert_eq!(err.span.start, 0, "{}", source);
+        }
+        let err = Lexer::init("a = 3in b").tokenize().unwrap_err();
+        assert_eq!(err.span, Span::new(4, 7));
+
+        // Legacy octal literals are only recorded, as whether they are allowed depends on the code being strict.
+        for (source, value, end) in [("017", 15.0, 3), ("08", 8.0, 2), ("00", 0.0, 2), ("09.5", 9.5, 2)] {
+            let res = Lexer::init(source).tokenize().unwrap();
+            assert_eq!(TokenKind::Lit(LitKind::Num(value)), res[0].kind, "{}", source);
+            assert_eq!(res[0].legacy_octal, Some(Span::new(0, end)), "{}", source);
+        }
+        let res = Lexer::init("0.5 + 0").tokenize().unwrap();
+        assert_eq!(TokenKind::Lit(LitKind::Num(0.5)), res[0].kind);
+        assert!(res.iter().all(|token| token.legacy_octal.is_none()));
+
+        let res = Lexer::init("a.b .5 1..c").tokenize().unwrap();
+        let kinds = res.into_iter().map(|token| token.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![
+            TokenKind::Id("a".into()),
+            TokenKind::Punc(PuncKind::Dot),
+            TokenKind::Id("b".into()),
+            TokenKind::Lit(LitKind::Num(0.5)),
+            TokenKind::Lit(LitKind::Num(1.0)),
+            TokenKind::Punc(PuncKind::Dot),
+            TokenKind::Id("c".into()),
+            TokenKind::Eof,
+        ]);
     }
 }
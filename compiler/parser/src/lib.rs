@@ -1,78 +1,371 @@
 use std::cell::RefCell;
-use std::rc::Rc;
-use crate::ast::{AdditiveExpr, BlockStmt, ConstDecl, ExprKind, IfStmt, LetDecl, PrimaryExprKind, StmtKind, Module};
-use crate::ast::DeclKind::Lexical;
-use crate::ast::LexicalKind::{Const, Let};
-use crate::ast::PrimaryExprKind::{Id, Lit, RegExLiteral};
-use crate::lexer::LexerError;
-use crate::ParseErrorKind::UnexpectedToken;
-use crate::reader::Reader;
-use crate::token::{BraceKind, KeywordKind, LitKind, OpKind, ParenthesesKind, PuncKind, Token, TokenKind};
-use crate::token::TokenKind::Keyword;
+use std::collections::BTreeMap;
+use crate::ast::{ExprKind, ExprStmt, LitValue, Module, ModuleSummary, PrimaryExprKind, Stmt, StmtKind};
+use crate::diagnostic::Diagnostic;
+use crate::ParseErrorKind::{MissingSemicolon, UnexpectedToken};
+use crate::span::Span;
+use crate::token::{is_reserved_word, is_strict_reserved_word, BraceKind, Comment, LitKind, OpKind, PuncKind, Token, TokenKind};
+use crate::token_stream::TokenStream;
 use self::lexer::Lexer;
 
 mod reader;
 mod lexer;
+mod symbol;
 mod token;
+mod token_stream;
 pub mod ast;
+pub mod diagnostic;
+pub mod span;
+pub mod source_map;
+#[cfg(test)]
+mod test_utils;
 mod grammar;
 
+pub use lexer::{LexerError, LexerErrorKind};
+pub use symbol::{sym, Symbol};
+
 #[derive(Clone, Debug)]
 pub enum ParseErrorKind {
-    UnexpectedToken(TokenKind),
+    /// The token found, and the tokens that were checked for in its place.
+    UnexpectedToken(TokenKind, Vec<TokenKind>),
+    InvalidAssignmentTarget,
+    /// A statement is followed by the token on the same line, where automatic semicolon insertion does not apply.
+    MissingSemicolon(TokenKind),
+    /// Statements, expressions or patterns are nested deeper than [MAX_DEPTH] levels.
+    NestingTooDeep,
+    /// An untagged template contains an invalid escape sequence.
+    InvalidTemplateEscape,
+    /// An identifier reference, binding or label spells a reserved word with escape sequences.
+    EscapedKeyword,
+    /// An identifier reference, binding or label is a word reserved in its context, such as `yield` in a generator or
+    /// `let` in strict mode code.
+    ReservedWord(String),
+    /// A let or const declaration binds `let`, which it can not even outside of strict mode code.
+    LexicallyBoundLet,
+    /// A yield or await expression is within the parameters of the function it would suspend.
+    YieldOrAwaitInParameters,
+    /// `eval` or `arguments` is bound or assigned to in strict mode code.
+    StrictEvalOrArguments(String),
+    /// `arguments` is referenced within a class field initializer or static block, outside of any nested function.
+    ArgumentsInClassInitializer,
+    /// A `break` without a label is outside of any loop or switch statement.
+    IllegalBreak,
+    /// A `continue` is outside of any loop, or its label is not on a loop.
+    IllegalContinue,
+    /// A `break` or `continue` refers to a label not on any enclosing statement.
+    UndefinedLabel(String),
+    /// A labelled statement is nested in another statement with the same label.
+    DuplicateLabel(String),
+    /// A with statement is in strict mode code.
+    StrictWith,
+    /// A string literal naming an import or export contains a lone surrogate.
+    IllFormedExportName,
+    /// A class has more than one constructor.
+    DuplicateConstructor,
+    /// A getter has parameters, or a setter does not have exactly one parameter other than a rest parameter.
+    InvalidAccessorParams,
+    /// A name is bound by more than one parameter where parameters must be unique.
+    DuplicateParameter(String),
+    /// An identifier reference is deleted in strict mode code.
+    StrictDelete,
+    /// A token lexed again once the parser knows its goal symbol, such as a regular expression literal, is invalid.
+    Lexer(LexerErrorKind),
 }
 
 #[derive(Clone, Debug)]
 pub struct ParseError {
     pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl ParseErrorKind {
+    /// The stable error code, as described by `hermesc explain`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnexpectedToken(..) => "E0100",
+            ParseErrorKind::InvalidAssignmentTarget => "E0101",
+            ParseErrorKind::MissingSemicolon(_) => "E0102",
+            ParseErrorKind::NestingTooDeep => "E0103",
+            ParseErrorKind::InvalidTemplateEscape => "E0104",
+            ParseErrorKind::EscapedKeyword => "E0105",
+            ParseErrorKind::ReservedWord(_) => "E0106",
+            ParseErrorKind::LexicallyBoundLet => "E0107",
+            ParseErrorKind::YieldOrAwaitInParameters => "E0108",
+            ParseErrorKind::StrictEvalOrArguments(_) => "E0109",
+            ParseErrorKind::ArgumentsInClassInitializer => "E0110",
+            ParseErrorKind::IllegalBreak => "E0111",
+            ParseErrorKind::IllegalContinue => "E0112",
+            ParseErrorKind::UndefinedLabel(_) => "E0113",
+            ParseErrorKind::DuplicateLabel(_) => "E0114",
+            ParseErrorKind::StrictWith => "E0115",
+            ParseErrorKind::IllFormedExportName => "E0116",
+            ParseErrorKind::DuplicateConstructor => "E0117",
+            ParseErrorKind::InvalidAccessorParams => "E0118",
+            ParseErrorKind::DuplicateParameter(_) => "E0119",
+            ParseErrorKind::StrictDelete => "E0120",
+            ParseErrorKind::Lexer(error) => error.code(),
+        }
+    }
+}
+
+impl ParseError {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match &self.kind {
+            UnexpectedToken(found, expected) => match expected.as_slice() {
+                [] => Diagnostic::error(format!("unexpected {}", found))
+                    .with_primary_label(self.span, format!("unexpected {}", found)),
+                [expected] => Diagnostic::error(format!("expected {}, found {}", expected, found))
+                    .with_primary_label(self.span, format!("expected {}", expected)),
+                _ => {
+                    let expected = expected.iter().map(|token| token.to_string()).collect::<Vec<_>>().join(", ");
+                    Diagnostic::error(format!("expected one of {}, found {}", expected, found))
+                        .with_primary_label(self.span, format!("expected one of {}", expected))
+                }
+            },
+            ParseErrorKind::InvalidAssignmentTarget => Diagnostic::error("invalid assignment target")
+                .with_primary_label(self.span, "can not be assigned to")
+                .with_help("only identifiers, member expressions and destructuring patterns can be assigned to"),
+            ParseErrorKind::MissingSemicolon(token) => Diagnostic::error(format!("expected `;`, found {}", token))
+                .with_primary_label(self.span, "expected `;`")
+                .with_suggestion(self.span, "add `;` here", ";"),
+            ParseErrorKind::NestingTooDeep => Diagnostic::error("nesting too deep")
+                .with_primary_label(self.span, format!("more than {} levels deep", MAX_DEPTH))
+                .with_help("move some of the nested code into variables or functions"),
+            ParseErrorKind::InvalidTemplateEscape => Diagnostic::error("invalid escape sequence in template literal")
+                .with_primary_label(self.span, "contains an invalid escape sequence")
+                .with_help("only tagged templates can contain invalid escape sequences, use `\\\\` for a backslash"),
+            ParseErrorKind::EscapedKeyword => Diagnostic::error("keyword written with escape sequences")
+                .with_primary_label(self.span, "reserved word used as an identifier")
+                .with_help("reserved words can only be used as property names, even when escaped"),
+            ParseErrorKind::ReservedWord(word) => {
+                let help = match word.as_str() {
+                    "await" => "`await` is reserved in modules and async functions".to_string(),
+                    "yield" => "`yield` is reserved in generators and strict mode code".to_string(),
+                    _ => format!("`{}` is reserved in strict mode code, which modules and classes always are", word),
+                };
+                Diagnostic::error(format!("`{}` is a reserved word", word))
+                    .with_primary_label(self.span, "can not be used as an identifier here")
+                    .with_help(help)
+            }
+            ParseErrorKind::LexicallyBoundLet => Diagnostic::error("`let` can not be declared by `let` or `const`")
+                .with_primary_label(self.span, "binds `let`")
+                .with_help("rename the binding, or declare it with `var`"),
+            ParseErrorKind::YieldOrAwaitInParameters => Diagnostic::error("`yield` or `await` expression in parameters")
+                .with_primary_label(self.span, "not allowed in parameters")
+                .with_help("parameters are evaluated before the function can be suspended, move the expression into its body"),
+            ParseErrorKind::StrictEvalOrArguments(name) => {
+                Diagnostic::error(format!("`{}` can not be bound or assigned to in strict mode code", name))
+                    .with_primary_label(self.span, "bound or assigned to here")
+                    .with_help("use a different name")
+            }
+            ParseErrorKind::ArgumentsInClassInitializer => Diagnostic::error("`arguments` in class field initializer or static block")
+                .with_primary_label(self.span, "not allowed here")
+                .with_help("class initializers have no `arguments` object, pass the values in some other way"),
+            ParseErrorKind::IllegalBreak => Diagnostic::error("`break` outside of a loop or switch")
+                .with_primary_label(self.span, "nothing to break out of")
+                .with_help("`break` can only be used within a loop or switch statement, or with the label of an enclosing statement"),
+            ParseErrorKind::IllegalContinue => Diagnostic::error("`continue` outside of a loop")
+                .with_primary_label(self.span, "no loop to continue")
+                .with_help("`continue` can only be used within a loop, with no label or the label of an enclosing loop"),
+            ParseErrorKind::UndefinedLabel(name) => Diagnostic::error(format!("undefined label `{}`", name))
+                .with_primary_label(self.span, "not the label of an enclosing statement")
+                .with_help("labels can only be referred to within the statement they label, and not across functions"),
+            ParseErrorKind::DuplicateLabel(name) => Diagnostic::error(format!("duplicate label `{}`", name))
+                .with_primary_label(self.span, "already the label of an enclosing statement")
+                .with_help("rename one of the labels"),
+            ParseErrorKind::StrictWith => Diagnostic::error("`with` statement in strict mode code")
+                .with_primary_label(self.span, "not allowed in strict mode code")
+                .with_help("refer to the properties of the object directly, as in `o.x` instead of `with (o) x`"),
+            ParseErrorKind::IllFormedExportName => Diagnostic::error("import or export name contains a lone surrogate")
+                .with_primary_label(self.span, "not well-formed Unicode")
+                .with_help("string literals naming imports and exports can only contain complete surrogate pairs"),
+            ParseErrorKind::DuplicateConstructor => Diagnostic::error("duplicate constructor")
+                .with_primary_label(self.span, "second constructor of the class")
+                .with_help("a class can only have a single constructor, remove or rename one of them"),
+            ParseErrorKind::InvalidAccessorParams => Diagnostic::error("invalid accessor parameters")
+                .with_primary_label(self.span, "wrong number of parameters")
+                .with_help("a getter takes no parameters, and a setter exactly one parameter that is not a rest parameter"),
+            ParseErrorKind::DuplicateParameter(name) => Diagnostic::error(format!("duplicate parameter `{}`", name))
+                .with_primary_label(self.span, "already bound by an earlier parameter")
+                .with_help("rename one of the parameters"),
+            ParseErrorKind::StrictDelete => Diagnostic::error("`delete` of an identifier in strict mode code")
+                .with_primary_label(self.span, "can not be deleted")
+                .with_help("only properties can be deleted, as in `delete o.x`"),
+            ParseErrorKind::Lexer(error) => return LexerError { span: self.span, error: error.clone() }.to_diagnostic(),
+        };
+        diagnostic.with_code(self.kind.code())
+    }
 }
 
+/// The maximum depth of nested statements, expressions and patterns. Parsing is recursive, so this bounds the stack
+/// used by deeply nested input, to well within the 8MB main thread stack of an unoptimized build.
+pub const MAX_DEPTH: usize = 256;
+
+/// The parsed module, which is partial if any errors occurred, and all errors in source order.
 pub struct ParseResult {
     pub ast: Module,
     pub errors: Vec<ParseError>,
+    /// The comments, which are not part of the AST, keyed by span so tools such as formatters can reattach them to the
+    /// nodes around them.
+    pub comments: BTreeMap<Span, Comment>,
 }
 
 /// Parses source code to AST based on [ECMAScript Lexical Grammar](https://262.ecma-international.org/#sec-intro).
-pub struct Parser {
-    ts: Rc<RefCell<Reader<Token>>>,
-    curr_token: Token,
-    fatal_error: Option<ParseError>,
+pub struct Parser<'a> {
+    /// The tokens, lexed as they are peeked at. A `/` or `/=` token is lexed again where a regular expression literal
+    /// is expected instead.
+    tokens: RefCell<TokenStream<'a>>,
+    /// The end of the last token consumed, which is the end of any node parsed up to this point.
+    prev_end: usize,
     errors: Vec<ParseError>,
+    /// Set after a syntax error until the enclosing statement list resynchronizes. While set, the parser sees the end
+    /// of input, which unwinds the statement being parsed without reporting further errors.
+    recovering: bool,
+    /// The tokens checked for since the last token was consumed, reported as expected by an unexpected token error.
+    expected: Vec<TokenKind>,
+    /// The `[In]` grammar parameter, which is only unset when parsing the head of a `for` statement.
+    allow_in: bool,
+    /// The `=` of each `{ a = b }` property parsed that is yet to be reinterpreted as an assignment pattern.
+    cover_inits: Vec<Span>,
+    /// Each comma directly after a spread element at the end of an array or object literal, which is an error if the
+    /// literal is reinterpreted as a pattern, as in `[...a,] = b`.
+    rest_commas: Vec<Span>,
+    /// The start of the assignment expression being parsed, where parentheses followed by `=>` are the parameters of
+    /// an arrow function.
+    arrow_start: usize,
+    /// Set after an arrow function is parsed in place of a primary expression, until the assignment expression it
+    /// starts returns it. While set, the parser sees the end of input, as no operator or property access can follow
+    /// an arrow function.
+    after_arrow: bool,
+    /// The number of nested statements, expressions and patterns being parsed, limited by [MAX_DEPTH].
+    depth: usize,
+    /// Whether the source is parsed with the Module goal symbol, rather than as a script.
+    module: bool,
+    /// Whether the code being parsed is strict mode code, which modules and class bodies always are.
+    strict: bool,
+    /// The legacy octal escape sequences in the directives of the directive prologue being parsed, which are errors if
+    /// a later `"use strict"` directive makes the code strict, as in `"\01"; "use strict";`. Unset outside of a prologue.
+    prologue_escapes: Option<Vec<Span>>,
+    /// The `[Yield]` grammar parameter, which is set within generators, where `yield` is an operator.
+    allow_yield: bool,
+    /// The `[Await]` grammar parameter, which is set within async functions and at the top level of modules, where
+    /// `await` is an operator.
+    allow_await: bool,
+    /// The `[Return]` grammar parameter, which is set within function bodies, where `return` statements are allowed.
+    allow_return: bool,
+    /// The spans of the yield and await expressions parsed within the function being parsed, which are errors if they
+    /// turn out to be within its parameters, as in `async (a = await b) => a`.
+    yield_await_exprs: Vec<Span>,
+    /// Whether `arguments` may be referenced, which it may not within class field initializers and static blocks
+    /// unless nested in another function other than an arrow function.
+    allow_arguments: bool,
+    /// Whether `new.target` is allowed, which it is within functions other than arrow functions, and within class field
+    /// initializers and static blocks.
+    allow_new_target: bool,
+    /// Whether `super.x` and `super[x]` are allowed, which they are within methods, class field initializers and static
+    /// blocks, and arrow functions within them.
+    allow_super_property: bool,
+    /// Whether `super()` is allowed, which it is within the constructor of a class with a heritage, and arrow functions
+    /// within it.
+    allow_super_call: bool,
+    /// The labels of the statements enclosing the statement being parsed within the current function, which `break`
+    /// and `continue` can refer to.
+    labels: Vec<Label>,
+    /// The number of loops enclosing the statement being parsed within the current function, which `break` and
+    /// `continue` without a label apply to.
+    loop_depth: usize,
+    /// The number of switch statements enclosing the statement being parsed within the current function, which `break`
+    /// without a label applies to.
+    switch_depth: usize,
 }
 
-impl Parser {
-    pub fn init(source: &str) -> Result<Self, LexerError> {
-        match Lexer::init(source).tokenize() {
-            Ok(tokens) => {
-                let ts = Rc::new(RefCell::new(Reader::init(tokens)));
-                let curr_token = ts.borrow().peek_single().unwrap();
-                Ok(Parser { ts, curr_token, fatal_error: None, errors: Vec::new() })
-            }
-            Err(err) => Err(err),
+/// The label of an enclosing labelled statement.
+struct Label {
+    name: Symbol,
+    /// Whether the label is on a loop, directly or through other labels as in `a: b: while (x) {}`, so can be the
+    /// target of a `continue`.
+    is_loop: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// Prepares the source to be parsed as a module, which is strict mode code.
+    pub fn init(source: &'a str) -> Self {
+        Self::init_goal(source, true)
+    }
+
+    /// Prepares the source to be parsed as a script, which is only strict mode code if it starts with a `"use strict"`
+    /// directive.
+    pub fn init_script(source: &'a str) -> Self {
+        Self::init_goal(source, false)
+    }
+
+    fn init_goal(source: &'a str, module: bool) -> Self {
+        let mut lexer = Lexer::init(source);
+        if module {
+            lexer = lexer.module();
+        }
+        Parser {
+            tokens: RefCell::new(TokenStream::new(lexer)),
+            prev_end: 0,
+            errors: Vec::new(),
+            recovering: false,
+            expected: Vec::new(),
+            allow_in: true,
+            cover_inits: Vec::new(),
+            rest_commas: Vec::new(),
+            arrow_start: 0,
+            after_arrow: false,
+            depth: 0,
+            module,
+            strict: module,
+            prologue_escapes: None,
+            allow_yield: false,
+            // Modules allow `await` at the top level.
+            allow_await: module,
+            allow_return: false,
+            yield_await_exprs: Vec::new(),
+            allow_arguments: true,
+            allow_new_target: false,
+            allow_super_property: false,
+            allow_super_call: false,
+            labels: Vec::new(),
+            loop_depth: 0,
+            switch_depth: 0,
         }
     }
 
-    pub fn parse(&mut self, module: &str) -> Result<ParseResult, ParseError> {
+    pub fn parse(&mut self, module: &str) -> ParseResult {
 
         let mut ast = Module::new(module);
 
-        loop {
-            match self.parse_stmt() {
-                Some(stmt) => ast.body.push(stmt),
-                None => {
-                    if self.is_end() {
-                        break;
-                    }
-                    continue;
-                },
-            }
+        // Lexer errors are otherwise skipped as tokens are consumed, but the lexer may already fail on the first one.
+        self.skip_lexer_errors();
+        if self.recovering {
+            self.synchronize(true);
         }
 
-        if self.fatal_error.is_some() {
-            return Err(self.fatal_error.clone().unwrap())
+        let mut prologue = !self.module;
+        self.prologue_escapes = Some(Vec::new());
+        while !self.is_end() {
+            let item = if self.module {
+                self.parse_stmt_list_item(Self::parse_module_item)
+            } else {
+                self.parse_stmt_list_item(Self::parse_stmt)
+            };
+            if let Some(stmt) = item {
+                prologue = prologue && self.apply_directive(&stmt);
+                ast.body.push(stmt);
+            }
         }
+        self.report_cover_inits(0);
+
+        ast.summary = ModuleSummary::from_body(&ast.body);
+        ast.span = Span::new(0, self.peek().span.end);
+
+        let mut errors = std::mem::take(&mut self.errors);
+        errors.sort_by_key(|err| err.span.start);
 
-        Ok(ParseResult { ast, errors: self.errors.clone() })
+        ParseResult { ast, errors, comments: self.tokens.borrow().take_comments() }
     }
 
     fn expect(&mut self, expected: TokenKind) {
@@ -80,11 +373,11 @@ impl Parser {
         self.bump();
     }
 
-    /// Checks the provided token against the next token in the stream. If they don't match, creates fatal error.
+    /// Checks the provided token against the next token in the stream. If they don't match, reports an error and
+    /// recovers.
     fn expect_peek(&mut self, expected: TokenKind) {
-        let actual = self.peek_kind();
-        if actual != expected {
-            self.set_fatal_error(UnexpectedToken(actual))
+        if !self.at(expected) {
+            self.error_and_recover(self.unexpected());
         }
     }
 
@@ -95,12 +388,7 @@ impl Parser {
 
     /// Checks the provided token against the next token in the stream. If matching, bumps the cursor one position.
     fn eat(&mut self, check: TokenKind) -> bool {
-        if self.is_end() {
-            return false;
-        }
-
-        let peek = self.peek_kind();
-        if peek == check {
+        if self.at(check) {
             self.bump();
             true
         } else {
@@ -108,15 +396,26 @@ impl Parser {
         }
     }
 
-    /// Checks the provided token against the token at the current cursor position.
+    /// Checks the provided token against the token at the current cursor position, recording it as expected if it
+    /// doesn't match. An identifier checked for is a contextual keyword, which an escaped name never matches.
     fn at(&mut self, check: TokenKind) -> bool {
-        let peek = self.peek_kind();
-        peek == check
+        let peek = self.peek();
+        if peek.kind == check && !peek.escaped {
+            return true;
+        }
+        if !self.expected.contains(&check) {
+            self.expected.push(check);
+        }
+        false
     }
 
-    /// Peek the next token in the stream.
+    /// Peek the next token in the stream, which is [TokenKind::Eof] while recovering from an error.
     fn peek(&self) -> Token {
-        self.ts.borrow().peek_single().unwrap()
+        let peek = self.tokens.borrow_mut().peek_nth(0);
+        if self.recovering || self.after_arrow {
+            return Token::new(TokenKind::Eof, Span::new(peek.span.start, peek.span.start));
+        }
+        peek
     }
 
     /// Peek the next token kind in the stream.
@@ -124,36 +423,474 @@ impl Parser {
         self.peek().kind
     }
 
-    /// Poll the next token in the stream.
+    /// Checks if the next token is the provided identifier, as used for contextual keywords like `of`. Escaped names
+    /// are never keywords.
+    fn at_id(&self, id: Symbol) -> bool {
+        let peek = self.peek();
+        matches!(peek.kind, TokenKind::Id(name) if name == id) && !peek.escaped
+    }
+
+    /// Section 13.1.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-identifiers-static-semantics-early-errors)
+    ///
+    /// Reports an identifier token used as an identifier reference, binding or label if its name is reserved, either
+    /// spelled with escape sequences or reserved in the current context.
+    fn check_identifier(&mut self, token: Token) {
+        let TokenKind::Id(name) = token.kind else {
+            return;
+        };
+
+        if token.escaped && is_reserved_word(name) {
+            self.push_error_at(ParseErrorKind::EscapedKeyword, token.span);
+        } else if (self.strict && is_strict_reserved_word(name))
+            || (name == sym::YIELD && self.allow_yield)
+            || (name == sym::AWAIT && (self.module || self.allow_await))
+        {
+            self.push_error_at(ParseErrorKind::ReservedWord(name.to_string()), token.span);
+        }
+    }
+
+    /// Section 13.1.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-identifiers-static-semantics-early-errors)
+    ///
+    /// Reports a name bound or assigned to if it is `eval` or `arguments` in strict mode code, where they can only be
+    /// referenced.
+    fn check_strict_target(&mut self, name: Symbol, span: Span) {
+        if self.strict && matches!(name, sym::EVAL | sym::ARGUMENTS) {
+            self.push_error_at(ParseErrorKind::StrictEvalOrArguments(name.to_string()), span);
+        }
+    }
+
+    /// Section 15.7.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-class-definitions-static-semantics-early-errors)
+    ///
+    /// Reports an identifier reference to `arguments` where it is not allowed.
+    fn check_arguments(&mut self, name: Symbol, span: Span) {
+        if name == sym::ARGUMENTS && !self.allow_arguments {
+            self.push_error_at(ParseErrorKind::ArgumentsInClassInitializer, span);
+        }
+    }
+
+    /// Reports the name of a shorthand property, which is an identifier reference or binding, so cannot be a reserved
+    /// word even though other property names can.
+    fn check_shorthand(&mut self, token: Token) {
+        match token.kind {
+            TokenKind::Id(_) => self.check_identifier(token),
+            kind => self.push_error_at(UnexpectedToken(kind, Vec::new()), token.span),
+        }
+    }
+
+    /// Section 11.2.1 [Directive Prologues](https://tc39.es/ecma262/#sec-directive-prologues-and-the-use-strict-directive)
+    ///
+    /// Checks if a statement at the start of a script or function body is a directive, which is a string literal
+    /// expression statement. A `"use strict"` directive makes the code that follows strict mode code, as well as the
+    /// directives before it.
+    fn apply_directive(&mut self, stmt: &Stmt) -> bool {
+        let StmtKind::Expression(ExprStmt(expr)) = &stmt.kind else {
+            self.prologue_escapes = None;
+            return false;
+        };
+        let ExprKind::Primary(PrimaryExprKind::Lit(LitValue::String(lit))) = &expr.kind else {
+            self.prologue_escapes = None;
+            return false;
+        };
+        if lit.raw == "use strict" {
+            self.strict = true;
+            for span in self.prologue_escapes.take().unwrap_or_default() {
+                self.push_error_at(ParseErrorKind::Lexer(LexerErrorKind::LegacyOctalEscape), span);
+            }
+        }
+        true
+    }
+
+    /// Peek the token `n` positions past the next token in the stream, which is [TokenKind::Eof] past the end. `n` must be
+    /// less than [MAX_LOOKAHEAD](token_stream::MAX_LOOKAHEAD).
+    fn peek_nth(&self, n: usize) -> Token {
+        if self.recovering {
+            return Token::new(TokenKind::Eof, Span::default());
+        }
+        self.tokens.borrow_mut().peek_nth(n)
+    }
+
+    /// Peek the token kind `n` positions past the next token in the stream.
+    fn peek_nth_kind(&self, n: usize) -> TokenKind {
+        self.peek_nth(n).kind
+    }
+
+    /// Checks if a line terminator separates the next token from the previous one.
+    fn has_newline_before(&self) -> bool {
+        self.peek().preceded_by_newline
+    }
+
+    /// Section 12.10 [Automatic Semicolon Insertion](https://tc39.es/ecma262/#sec-automatic-semicolon-insertion)
+    ///
+    /// Consumes the semicolon ending a statement, which may be omitted before a `}`, at the end of input, or when the
+    /// next token is on a new line.
+    fn consume_semicolon(&mut self) {
+        if self.eat(TokenKind::Punc(PuncKind::SemiColon)) {
+            return;
+        }
+
+        let can_insert = self.has_newline_before()
+            || matches!(self.peek_kind(), TokenKind::Punc(PuncKind::Brace(BraceKind::Right)) | TokenKind::Eof);
+
+        if !can_insert {
+            // The error points just past the end of the statement, where the semicolon is missing.
+            self.error_and_recover_at(MissingSemicolon(self.peek_kind()), Span::new(self.prev_end, self.prev_end));
+        }
+    }
+
+    /// Peek the operator kind of the next token in the stream, if it is an operator.
+    fn peek_op(&self) -> Option<OpKind> {
+        match self.peek_kind() {
+            TokenKind::Punc(PuncKind::Op(op)) => Some(op),
+            _ => None,
+        }
+    }
+
+    /// Consumes the next token if it is the provided operator. Unlike [Parser::eat], the operator is not recorded as
+    /// expected, as most operators may follow any expression.
+    fn eat_op(&mut self, op: OpKind) -> bool {
+        if self.peek_op() == Some(op) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The position of the next token in the stream, used to check whether any tokens have been consumed.
+    fn pos(&self) -> usize {
+        self.tokens.borrow().pos()
+    }
+
+    /// Poll the next token in the stream. The cursor never moves past the trailing [TokenKind::Eof].
     fn next(&mut self) -> Token {
-        let next = self.ts.borrow_mut().next_single().unwrap();
-        self.curr_token = self.peek();
+        let next = self.peek();
+        if next.kind != TokenKind::Eof {
+            // Legacy octal literals and escapes are only allowed outside of strict mode code, which only the parser can
+            // tell.
+            if let Some(span) = next.legacy_octal {
+                let escape = next.kind == TokenKind::Lit(LitKind::String);
+                if self.strict {
+                    let err = if escape { LexerErrorKind::LegacyOctalEscape } else { LexerErrorKind::LegacyOctalLiteral };
+                    self.push_error_at(ParseErrorKind::Lexer(err), span);
+                } else if escape && let Some(escapes) = &mut self.prologue_escapes {
+                    escapes.push(span);
+                }
+            }
+            self.tokens.borrow_mut().bump();
+            self.prev_end = next.span.end;
+            self.expected.clear();
+            self.skip_lexer_errors();
+        }
         next
     }
 
-    /// Poll the next token kind in the stream.
-    fn next_kind(&mut self) -> TokenKind {
-        self.next().kind
+    /// Reports the error the lexer failed with on the next token, if it did, and recovers from it like from any other
+    /// syntax error. The source text the lexer failed on is skipped.
+    fn skip_lexer_errors(&mut self) {
+        while let Some(err) = self.tokens.get_mut().take_error() {
+            self.error_and_recover_at(ParseErrorKind::Lexer(err.error), err.span);
+        }
+    }
+
+    /// The start of the next token, which is the start of the node about to be parsed.
+    fn start(&self) -> usize {
+        self.peek().span.start
     }
 
-    /// Advances to the end of the token stream.
-    fn advance_to_end(&mut self) {
-        self.ts.borrow_mut().end();
-        self.curr_token = self.ts.borrow().peek_single().unwrap();
+    /// The span of a node starting at `start` and ending with the last token consumed.
+    fn finish(&self, start: usize) -> Span {
+        Span::new(start, self.prev_end.max(start))
     }
 
+    /// Runs the provided parse function with the `[In]` grammar parameter set as provided, restoring it afterward.
+    fn with_in<T>(&mut self, allow_in: bool, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let prev = self.allow_in;
+        self.allow_in = allow_in;
+        let res = parse(self);
+        self.allow_in = prev;
+        res
+    }
+
+    /// Runs the provided parse function with the `[Yield]` and `[Await]` grammar parameters set as provided, as for the
+    /// parameters and body of a function, restoring them afterward.
+    fn with_params<T>(&mut self, allow_yield: bool, allow_await: bool, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let prev = (self.allow_yield, self.allow_await);
+        self.allow_yield = allow_yield;
+        self.allow_await = allow_await;
+        let res = parse(self);
+        (self.allow_yield, self.allow_await) = prev;
+        res
+    }
+
+    /// Runs the provided parse function with `return` statements, `new.target`, `arguments`, `super.x` and `super()`
+    /// allowed as provided, as for the body of a function or a class static block, restoring the previous settings
+    /// afterward.
+    fn with_function<T>(
+        &mut self,
+        allow_return: bool,
+        allow_new_target: bool,
+        allow_arguments: bool,
+        allow_super_property: bool,
+        allow_super_call: bool,
+        parse: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let prev = (self.allow_return, self.allow_new_target, self.allow_arguments);
+        let prev_super = (self.allow_super_property, self.allow_super_call);
+        let yield_await_exprs = self.yield_await_exprs.len();
+        // `break` and `continue` can not cross function boundaries.
+        let labels = std::mem::take(&mut self.labels);
+        let depths = (self.loop_depth, self.switch_depth);
+        (self.loop_depth, self.switch_depth) = (0, 0);
+        self.allow_return = allow_return;
+        self.allow_new_target = allow_new_target;
+        self.allow_arguments = allow_arguments;
+        (self.allow_super_property, self.allow_super_call) = (allow_super_property, allow_super_call);
+        let res = parse(self);
+        (self.allow_return, self.allow_new_target, self.allow_arguments) = prev;
+        (self.allow_super_property, self.allow_super_call) = prev_super;
+        self.labels = labels;
+        (self.loop_depth, self.switch_depth) = depths;
+        // The expressions within a nested function are never within the parameters of the enclosing one.
+        self.yield_await_exprs.truncate(yield_await_exprs);
+        res
+    }
+
+    /// Runs the provided parse function as strict mode code, as for a class body, restoring the previous mode afterward.
+    fn with_strict<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let prev = self.strict;
+        self.strict = true;
+        let res = parse(self);
+        self.strict = prev;
+        res
+    }
+
+    /// Runs the provided parse function one level deeper. Past [MAX_DEPTH] levels, reports an error and recovers
+    /// instead, returning [None].
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        if self.depth >= MAX_DEPTH {
+            self.error_and_recover(ParseErrorKind::NestingTooDeep);
+            return None;
+        }
+        self.depth += 1;
+        let res = parse(self);
+        self.depth -= 1;
+        Some(res)
+    }
+
+    /// Records an error at the next token.
     fn push_error(&mut self, err: ParseErrorKind) {
-        self.errors.push(ParseError { kind: err })
+        self.push_error_at(err, self.peek().span)
     }
 
-    fn set_fatal_error(&mut self, err: ParseErrorKind) {
-        self.fatal_error = Some(ParseError { kind: err });
-        self.advance_to_end();
+    /// Records an error, unless recovering from an earlier error that it is likely a consequence of.
+    fn push_error_at(&mut self, err: ParseErrorKind, span: Span) {
+        if !self.recovering {
+            self.errors.push(ParseError { kind: err, span })
+        }
+    }
+
+    /// Records an error at the next token and abandons the statement being parsed, which the enclosing statement
+    /// list recovers from.
+    fn error_and_recover(&mut self, err: ParseErrorKind) {
+        self.error_and_recover_at(err, self.peek().span)
+    }
 
+    fn error_and_recover_at(&mut self, err: ParseErrorKind, span: Span) {
+        self.push_error_at(err, span);
+        self.recovering = true;
+    }
+
+    /// An unexpected token error for the next token, expecting the tokens checked for since the last one consumed.
+    fn unexpected(&self) -> ParseErrorKind {
+        UnexpectedToken(self.peek_kind(), self.expected.clone())
     }
 
     /// Checks if it is the end of the token stream.
     fn is_end(&self) -> bool {
-        !self.ts.borrow().has_next() || self.curr_token.kind == TokenKind::Eof
+        self.peek_kind() == TokenKind::Eof
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::source_map::SourceMap;
+    use crate::span::Span;
+    use crate::{LexerErrorKind, ParseErrorKind, Parser, MAX_DEPTH};
+
+    /// A xorshift generator with a fixed seed, so the generated inputs are the same on every run.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+            items[self.below(items.len())]
+        }
+    }
+
+    const FRAGMENTS: &[&str] = &[
+        "a", "b", "x", "1", "99999999999999999999", "\"s\"", "#p", "let", "const", "var", "=", "(", ")", "{", "}", "[",
+        "]", ";", ",", ".", "...", "=>", "?", "?.", ":", "class", "extends", "function", "async", "await", "yield",
+        "new", "import", "export", "from", "as", "default", "if", "else", "for", "of", "in", "while", "do", "switch",
+        "case", "return", "break", "continue", "try", "catch", "finally", "throw", "+", "++", "-", "*", "**", "=",
+        "+=", "&&", "||", "??", "!", "get", "set", "static", "super", "this", "target", "meta", "`a${", "}b${", "}`",
+        "`c`", "/", "/=", "/a/g", "//", "/*", "*/", "<!--", "-->", "$_", "\\u0069f", "\n", "",
+    ];
+
+    const CHARS: &[char] = &[
+        'a', '1', ' ', '\n', '\r', '\t', '"', '#', '?', '.', '(', ')', '{', '}', '[', ']', '=', '>', '<', '!', '*', '+',
+        '-', '/', '\\', ';', ':', ',', '`', '\'', '@', '\u{2028}', 'é', '😀',
+    ];
+
+    /// Lexes and parses the source, which must not panic, then renders every error.
+    fn check(source: &str) {
+        let source_map = SourceMap::new(source);
+        let res = Parser::init(source).parse("test");
+        assert!(res.ast.span.end <= source.len(), "module span out of bounds for {:?}", source);
+        for err in res.errors {
+            err.to_diagnostic().render("test.hs", &source_map, false);
+        }
+    }
+
+    #[test]
+    fn test_never_panics() {
+        let sources = [
+            "", " ", "\n", ";", "}", "class A {}", "foo: 1", "switch (x) {}", "99999999999999999999", "let [a, ...b] = c",
+            "async", "new", "new.", "import", "export", "class", "a?.", "(", "=>", "a =", "`", "#",
+        ];
+        for source in sources {
+            check(source);
+        }
+
+        let mut rng = Rng(0x2545f4914f6cdd1d);
+
+        for _ in 0..2000 {
+            let source = (0..rng.below(24)).map(|_| rng.pick(FRAGMENTS)).collect::<Vec<_>>().join(" ");
+            check(&source);
+        }
+
+        for _ in 0..2000 {
+            let source = (0..rng.below(24)).map(|_| rng.pick(CHARS)).collect::<String>();
+            check(&source);
+        }
+
+        // Single edits of a valid program, which mostly produce almost valid programs.
+        let program = "import { a as b } from \"c\";\nclass A extends B { static #x = 1; get y() { return this.#x; } }\n\
+            export default async function f([a, { b = 2 }], ...c) { for (const d of c) { if (d) break; } }\n\
+            let g = (h, i) => h ?? i, j = new A().y?.k ** 2;";
+        let chars = program.chars().collect::<Vec<_>>();
+        for _ in 0..2000 {
+            let mut edited = chars.clone();
+            let at = rng.below(edited.len());
+            match rng.below(3) {
+                0 => {
+                    edited.remove(at);
+                }
+                1 => edited.insert(at, rng.pick(CHARS)),
+                _ => edited[at] = rng.pick(CHARS),
+            }
+            check(&edited.into_iter().collect::<String>());
+        }
+    }
+
+    fn nest(open: &str, inner: &str, close: &str, depth: usize) -> String {
+        format!("{}{}{}", open.repeat(depth), inner, close.repeat(depth))
+    }
+
+    #[test]
+    fn test_lexer_errors() {
+        // A lexer error is recovered from at the statement it occurs in, and the statements after it are parsed.
+        let res = Parser::init("a = 1__0 + b;\nfunction f() { c = 'd\n e }\ng h;").parse("test");
+        let errors: Vec<(&str, Span)> = res.errors.iter().map(|err| (err.kind.code(), err.span)).collect();
+        assert_eq!(errors, vec![("E0002", Span::new(4, 8)), ("E0004", Span::new(33, 35)), ("E0102", Span::new(42, 42))]);
+        assert_eq!(res.ast.body.len(), 3);
+
+        // The source text after a lexer error on the first token is skipped like any other.
+        let res = Parser::init("'a\nb; c").parse("test");
+        assert_eq!(res.errors.len(), 1);
+        assert_eq!(res.ast.body.len(), 1);
+
+        // Lexing a regular expression literal does not depend on the tokens lexed past the `/` as division.
+        assert!(Parser::init("x = /'/.test(y) ? /\"/ : /`/;").parse("test").errors.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_octal_literals() {
+        // Legacy octal literals are errors in any strict mode code, not only in modules.
+        let errors = |source| {
+            let res = Parser::init_script(source).parse("test");
+            res.errors.iter().map(|err| (err.kind.code(), err.span)).collect::<Vec<_>>()
+        };
+        let code = ParseErrorKind::Lexer(LexerErrorKind::LegacyOctalLiteral).code();
+        assert_eq!(errors("\"use strict\"; x = 017;"), vec![(code, Span::new(18, 21))]);
+        assert_eq!(errors("function f() { \"use strict\"; return 08; }"), vec![(code, Span::new(36, 38))]);
+        assert_eq!(errors("class A { m() { return 017; } }"), vec![(code, Span::new(23, 26))]);
+        assert_eq!(errors("017; function f() { return 08; }"), vec![]);
+        assert_eq!(Parser::init("x = 017;").parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_octal_escapes() {
+        // Legacy octal escapes are errors in any strict mode code, including the directives before a `"use strict"`.
+        let errors = |source| {
+            let res = Parser::init_script(source).parse("test");
+            res.errors.iter().map(|err| (err.kind.code(), err.span)).collect::<Vec<_>>()
+        };
+        let code = ParseErrorKind::Lexer(LexerErrorKind::LegacyOctalEscape).code();
+        assert_eq!(errors(r#""use strict"; x = "\01";"#), vec![(code, Span::new(19, 22))]);
+        assert_eq!(errors(r#"function f() { "use strict"; return "\8"; }"#), vec![(code, Span::new(37, 39))]);
+        assert_eq!(errors(r#"class A { m() { return '\9'; } }"#), vec![(code, Span::new(24, 26))]);
+        assert_eq!(errors(r#"function f() { "\01"; "use strict"; }"#), vec![(code, Span::new(16, 19))]);
+        assert_eq!(errors(r#""\01"; '\8'; "use strict";"#), vec![(code, Span::new(1, 4)), (code, Span::new(8, 10))]);
+        for source in [r#""\01"; function f() { "use strict"; }"#, r#"x = "\01"; "use strict";"#, r#"'\8' + "use strict";"#] {
+            assert_eq!(errors(source), vec![], "{}", source);
+        }
+        assert_eq!(Parser::init(r#"x = "\01";"#).parse("test").errors.len(), 1);
+    }
+
+    #[test]
+    fn test_nesting_too_deep() {
+        // Test threads have a smaller stack than the main thread the compiler parses on.
+        let test = std::thread::Builder::new().stack_size(8 * 1024 * 1024).spawn(|| {
+            let depth = 10 * MAX_DEPTH;
+            let sources = [
+                nest("(", "1", ")", depth),
+                nest("[", "1", "]", depth),
+                nest("{", "", "}", depth),
+                nest("a = {b: ", "1", "}", depth),
+                nest("f(", "1", ")", depth),
+                nest("a[", "1", "]", depth),
+                nest("!", "1", "", depth),
+                nest("new ", "A", "", depth),
+                nest("2 ** ", "1", "", depth),
+                nest("a ? b : ", "c", "", depth),
+                nest("x => ", "x", "", depth),
+                nest("if (a) ", "b", "", depth),
+                nest("function f() {", "", "}", depth),
+                format!("({})", nest("class extends ", "A", " {}", depth)),
+                format!("let {} = a", nest("[", "b", "]", depth)),
+                format!("let {} = a", nest("{b: ", "c", "}", depth)),
+            ];
+            for source in sources {
+                let res = Parser::init(&source).parse("test");
+                assert!(
+                    res.errors.iter().any(|err| matches!(err.kind, ParseErrorKind::NestingTooDeep)),
+                    "expected nesting error for {}",
+                    &source[..20],
+                );
+            }
+
+            let res = Parser::init(&nest("(", "1", ")", MAX_DEPTH / 2)).parse("test");
+            assert!(res.errors.is_empty());
+        });
+        test.unwrap().join().unwrap();
+    }
+}
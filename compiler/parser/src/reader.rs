@@ -1,89 +1,73 @@
+/// Reads source text char by char without copying it, at byte offsets into the text.
 #[derive(Clone, Debug)]
-pub struct Reader<S> {
-    source: Vec<S>,
+pub struct Reader<'a> {
+    source: &'a str,
     cursor: usize,
 }
 
-impl<S> Reader<S> where S: Clone + PartialEq {
-    pub fn init(source: Vec<S>) -> Self {
+impl<'a> Reader<'a> {
+    pub fn init(source: &'a str) -> Self {
         Reader { source, cursor: 0 }
     }
 
-    pub fn peek_single(&self) -> Option<S> {
-        match self.peek(1) {
-            Some(char) => Some(char[0].clone()),
+    pub fn peek_single(&self) -> Option<char> {
+        // Most source text is ASCII, which is a single byte.
+        match self.source.as_bytes().get(self.cursor) {
+            Some(&byte) if byte.is_ascii() => Some(byte as char),
+            Some(_) => self.source[self.cursor..].chars().next(),
             None => None,
         }
     }
 
-    pub fn peek(&self, n: usize) -> Option<Vec<S>> {
-        if self.cursor + n - 1 >= self.source.len() {
-            return None;
-        }
+    /// Peeks the char `n` chars past the cursor.
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.source[self.cursor..].chars().nth(n)
+    }
 
-        Some(self.source[self.cursor..self.cursor + n].to_vec())
+    /// Checks if the text at the cursor starts with the provided text.
+    pub fn starts_with(&self, text: &str) -> bool {
+        self.source.as_bytes()[self.cursor..].starts_with(text.as_bytes())
     }
 
-    pub fn next_single(&mut self) -> Option<S> {
-        let next = match self.next(1) {
-            Some(char) => Some(char[0].clone()),
-            None => None,
-        };
-        next
+    pub fn next_single(&mut self) -> Option<char> {
+        let next = self.peek_single()?;
+        self.cursor += next.len_utf8();
+        Some(next)
     }
 
-    pub fn next(&mut self, n: usize) -> Option<Vec<S>> {
-        if self.cursor + n - 1 >= self.source.len() {
-            return None;
+    /// Consumes the next char if it equals `expected`.
+    pub fn eat(&mut self, expected: char) -> bool {
+        let matches = self.peek_single() == Some(expected);
+        if matches {
+            self.cursor += expected.len_utf8();
         }
-
-        let next = Some(&self.source[self.cursor..self.cursor + n]);
-        self.cursor += n;
-        next.map(|slice| slice.to_vec())
+        matches
     }
 
     #[inline]
     pub fn bump(&mut self) {
-        self.skip(1);
+        self.next_single();
     }
 
+    /// Moves the cursor forward `n` chars.
     #[inline]
     pub fn skip(&mut self, n: usize) {
-        self.cursor += n;
-    }
-
-    pub fn has_next(&self) -> bool {
-        self.cursor < self.source.len()
-    }
-
-    pub fn end(&mut self) {
-        self.cursor = self.source.len() - 1
+        for _ in 0..n {
+            self.bump();
+        }
     }
 
     pub fn get_pos(&self) -> usize {
         self.cursor
     }
 
-    pub fn collect_until(&mut self, until: S) -> Vec<S> {
-
-        let mut collected: Vec<S> = Vec::new();
+    pub fn set_pos(&mut self, pos: usize) {
+        self.cursor = pos;
+    }
 
-        loop {
-            let peek = self.peek_single();
-            match peek {
-                Some(peek) => {
-                    if self.source[self.cursor] == until {
-                        return collected;
-                    } else {
-                        collected.push(peek.clone());
-                        self.bump();
-                    }
-                }
-                None => {
-                    return collected;
-                }
-            }
-        }
+    /// The text between two byte offsets, such as the source text of a token.
+    pub fn range(&self, start: usize, end: usize) -> &'a str {
+        self.source.get(start..end).unwrap_or_default()
     }
 }
 
@@ -93,31 +77,30 @@ mod tests {
 
     #[test]
     fn test_reader() {
-        let mut reader = Reader::init("javascript".as_bytes().to_vec());
-        let next = reader.next(4);
-        assert!(next.is_some());
-        assert_eq!(String::from_utf8(next.unwrap()).unwrap(), "java");
-        let peek = reader.peek(6);
-        assert!(peek.is_some());
-        assert_eq!(String::from_utf8(peek.unwrap()).unwrap(), "script");
-        let next = reader.next(6);
-        assert!(next.is_some());
-        assert_eq!(String::from_utf8(next.unwrap()).unwrap(), "script");
-        let peek = reader.peek(1);
-        assert!(peek.is_none());
-        let next = reader.next(1);
-        assert!(next.is_none());
+        let mut reader = Reader::init("javascript");
+        assert!(reader.starts_with("java"));
+        reader.skip(4);
+        assert_eq!(reader.peek_nth(5), Some('t'));
+        assert_eq!(reader.range(4, 10), "script");
+        assert!(reader.eat('s'));
+        assert!(!reader.eat('s'));
+        reader.skip(5);
+        assert_eq!(reader.peek_single(), None);
+        assert_eq!(reader.next_single(), None);
+        assert!(!reader.starts_with("t"));
     }
 
     #[test]
-    fn test_collect_until() {
-        let mut reader = Reader::init("Testing]".chars().collect());
-
-        let testing = reader.collect_until(']');
-
-        let expected: Vec<char> = "Testing".chars().collect();
-        assert_eq!(testing, expected);
-
-        assert_eq!(reader.next_single(), Some(']'));
+    fn test_multi_byte() {
+        // Positions are byte offsets, and chars are read whole.
+        let mut reader = Reader::init("é😀a");
+        assert_eq!(reader.next_single(), Some('é'));
+        assert_eq!(reader.get_pos(), 2);
+        assert_eq!(reader.peek_nth(1), Some('a'));
+        assert!(reader.eat('😀'));
+        assert_eq!(reader.get_pos(), 6);
+        assert_eq!(reader.range(0, 6), "é😀");
+        reader.set_pos(2);
+        assert_eq!(reader.peek_single(), Some('😀'));
     }
 }
@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+/// A position in source text. Lines and columns are zero based, so add one when displaying them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct LineCol {
+    pub line: usize,
+    /// The column in characters.
+    pub column: usize,
+    /// The column in UTF-16 code units, as used by editors and source maps.
+    pub utf16_column: usize,
+}
+
+/// Converts byte offsets, as stored in spans, to lines and columns.
+///
+/// Section 12.3 [Line Terminators](https://tc39.es/ecma262/#sec-line-terminators) end a line, with `\r\n` counted as
+/// a single line terminator.
+#[derive(Clone, Debug)]
+pub struct SourceMap {
+    source: String,
+    /// The byte offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+
+        let mut chars = source.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\r' if matches!(chars.peek(), Some((_, '\n'))) => {}
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => line_starts.push(i + c.len_utf8()),
+                _ => {}
+            }
+        }
+
+        SourceMap { source: source.to_string(), line_starts }
+    }
+
+    /// Finds the line and column of a byte offset, which is clamped to the end of the source and moved back to the
+    /// start of the character it falls within.
+    pub fn lookup(&self, offset: usize) -> LineCol {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let before = &self.source[self.line_starts[line]..offset];
+
+        LineCol { line, column: before.chars().count(), utf16_column: before.encode_utf16().count() }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The text of a zero based line, without its line terminator.
+    pub fn line_text(&self, line: usize) -> &str {
+        let Some(&start) = self.line_starts.get(line) else {
+            return "";
+        };
+        let end = self.line_starts.get(line + 1).copied().unwrap_or(self.source.len());
+        self.source[start..end].trim_end_matches(['\n', '\r', '\u{2028}', '\u{2029}'])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineCol, SourceMap};
+
+    #[test]
+    fn test_lookup() {
+        let map = SourceMap::new("let a;\r\nlet b;\nlet c;");
+        assert_eq!(map.line_count(), 3);
+        assert_eq!(map.lookup(0), LineCol { line: 0, column: 0, utf16_column: 0 });
+        assert_eq!(map.lookup(4), LineCol { line: 0, column: 4, utf16_column: 4 });
+        assert_eq!(map.lookup(12), LineCol { line: 1, column: 4, utf16_column: 4 });
+        assert_eq!(map.lookup(19), LineCol { line: 2, column: 4, utf16_column: 4 });
+        assert_eq!(map.lookup(100), LineCol { line: 2, column: 6, utf16_column: 6 });
+        assert_eq!(map.line_text(0), "let a;");
+        assert_eq!(map.line_text(1), "let b;");
+        assert_eq!(map.line_text(3), "");
+    }
+
+    #[test]
+    fn test_lookup_unicode() {
+        // `é` is two bytes and one UTF-16 unit, `😀` is four bytes and two UTF-16 units.
+        let map = SourceMap::new("\"é😀\" + x\u{2028}y");
+        assert_eq!(map.lookup(9), LineCol { line: 0, column: 5, utf16_column: 6 });
+        // Offsets within a character resolve to its start.
+        assert_eq!(map.lookup(4), LineCol { line: 0, column: 2, utf16_column: 2 });
+        assert_eq!(map.lookup(15), LineCol { line: 1, column: 0, utf16_column: 0 });
+    }
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+/// A range of source text, as byte offsets from the start of the source. The end is exclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// The smallest span covering both this span and the provided one.
+    pub fn to(self, other: Span) -> Span {
+        Span { start: self.start.min(other.start), end: self.end.max(other.end) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Span;
+
+    #[test]
+    fn test_span() {
+        let span = Span::new(2, 5).to(Span::new(7, 9));
+        assert_eq!(span, Span::new(2, 9));
+        assert_eq!(span.len(), 7);
+        assert!(Span::new(3, 3).is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, PoisonError, RwLock};
+use serde::{Serialize, Serializer};
+
+/// An interned identifier name, which is cheap to copy and compare.
+///
+/// The names are kept in a table shared by all threads for the lifetime of the process, so each distinct name is
+/// stored once however often it appears, and is never freed. Only names are interned, as there are far fewer distinct
+/// names than literal values in a program. The words the parser checks for are predefined in [sym], so comparing
+/// against them and reading them back never takes the table's lock.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+macro_rules! symbols {
+    ($($name:ident: $string:literal,)*) => {
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        #[repr(u32)]
+        enum Predefined {
+            $($name,)*
+        }
+
+        /// The strings of the predefined symbols, where the index of each is its symbol.
+        const PREDEFINED: &[&str] = &[$($string,)*];
+
+        /// The predefined symbols, which are interned before any other.
+        pub mod sym {
+            use super::{Predefined, Symbol};
+
+            $(pub const $name: Symbol = Symbol(Predefined::$name as u32);)*
+        }
+    };
+}
+
+symbols! {
+    // Section 12.7.2 Keywords and Reserved Words, which are identifier names as property names.
+    BREAK: "break",
+    CASE: "case",
+    CATCH: "catch",
+    CLASS: "class",
+    CONST: "const",
+    CONTINUE: "continue",
+    DEBUGGER: "debugger",
+    DEFAULT: "default",
+    DELETE: "delete",
+    DO: "do",
+    ELSE: "else",
+    ENUM: "enum",
+    EXPORT: "export",
+    EXTENDS: "extends",
+    FINALLY: "finally",
+    FOR: "for",
+    FUNCTION: "function",
+    IF: "if",
+    IMPORT: "import",
+    IN: "in",
+    INSTANCEOF: "instanceof",
+    NEW: "new",
+    RETURN: "return",
+    SUPER: "super",
+    SWITCH: "switch",
+    THIS: "this",
+    THROW: "throw",
+    TRY: "try",
+    TYPEOF: "typeof",
+    VAR: "var",
+    VOID: "void",
+    WHILE: "while",
+    WITH: "with",
+    TRUE: "true",
+    FALSE: "false",
+    NULL: "null",
+    // Contextual keywords, and the words only reserved in strict mode code.
+    AS: "as",
+    ASYNC: "async",
+    ARGUMENTS: "arguments",
+    AWAIT: "await",
+    CONSTRUCTOR: "constructor",
+    EVAL: "eval",
+    FROM: "from",
+    GET: "get",
+    IMPLEMENTS: "implements",
+    INTERFACE: "interface",
+    LET: "let",
+    META: "meta",
+    OF: "of",
+    PACKAGE: "package",
+    PRIVATE: "private",
+    PROTECTED: "protected",
+    PUBLIC: "public",
+    SET: "set",
+    STATIC: "static",
+    TARGET: "target",
+    YIELD: "yield",
+    // The name given to identifiers missing due to a syntax error.
+    EMPTY: "",
+}
+
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+static INTERNER: LazyLock<RwLock<Interner>> = LazyLock::new(|| {
+    let mut interner = Interner::default();
+    for &string in PREDEFINED {
+        interner.symbols.insert(string, Symbol(interner.strings.len() as u32));
+        interner.strings.push(string);
+    }
+    RwLock::new(interner)
+});
+
+impl Symbol {
+    /// The symbol of a name, which is added to the table if it is not in it yet.
+    pub fn intern(string: &str) -> Self {
+        if let Some(&symbol) = INTERNER.read().unwrap_or_else(PoisonError::into_inner).symbols.get(string) {
+            return symbol;
+        }
+
+        let mut interner = INTERNER.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have added the string since the read lock was released.
+        if let Some(&symbol) = interner.symbols.get(string) {
+            return symbol;
+        }
+        let string: &'static str = Box::leak(string.into());
+        let symbol = Symbol(interner.strings.len() as u32);
+        interner.strings.push(string);
+        interner.symbols.insert(string, symbol);
+        symbol
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match PREDEFINED.get(self.0 as usize) {
+            Some(string) => string,
+            None => INTERNER.read().unwrap_or_else(PoisonError::into_inner).strings[self.0 as usize],
+        }
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(string: &str) -> Self {
+        Symbol::intern(string)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sym, Symbol};
+
+    #[test]
+    fn test_intern() {
+        let a = Symbol::intern("a");
+        assert_eq!(a, Symbol::intern(&String::from("a")));
+        assert_ne!(a, Symbol::intern("b"));
+        assert_eq!(a.as_str(), "a");
+        assert_eq!(a, "a");
+        assert_eq!(format!("{} {:?}", a, a), "a \"a\"");
+        assert_eq!(serde_json::to_string(&Symbol::intern("ü\"")).unwrap(), "\"ü\\\"\"");
+
+        // Predefined symbols are interned ahead of any other.
+        assert_eq!(Symbol::intern("let"), sym::LET);
+        assert_eq!(sym::YIELD.as_str(), "yield");
+        assert_ne!(Symbol::intern("lets"), sym::LET);
+
+        let threads: Vec<_> = (0..4).map(|_| std::thread::spawn(|| Symbol::intern("shared"))).collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), Symbol::intern("shared"));
+        }
+    }
+}
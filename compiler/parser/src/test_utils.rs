@@ -0,0 +1,102 @@
+use serde::Serialize;
+use serde_json::Value;
+use crate::ast::{ClassElement, ClassElementKind, Expr, ExprKind, ExprStmt, LitValue, Module, ObjectPatternProp, ObjectPatternPropKind, Pattern, PatternKind, PrimaryExprKind, Prop, PropKind, Stmt, StmtKind};
+use crate::span::Span;
+use crate::Parser;
+
+/// Asserts two AST nodes are equal, ignoring their spans, so expected trees can be built without positions.
+macro_rules! assert_ast_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        assert_eq!($crate::test_utils::without_spans(&$left), $crate::test_utils::without_spans(&$right))
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        assert_eq!($crate::test_utils::without_spans(&$left), $crate::test_utils::without_spans(&$right), $($arg)+)
+    };
+}
+
+pub(crate) use assert_ast_eq;
+
+/// Serializes a node with every `span` field removed.
+pub(crate) fn without_spans(node: &impl Serialize) -> Value {
+    let mut value = serde_json::to_value(node).unwrap();
+    strip_spans(&mut value);
+    value
+}
+
+fn strip_spans(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("span");
+            map.values_mut().for_each(strip_spans);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_spans),
+        _ => {}
+    }
+}
+
+/// Wraps a node kind in its node with an empty span, for building expected trees compared with [assert_ast_eq].
+pub(crate) trait IntoNode {
+    type Node;
+
+    fn node(self) -> Self::Node;
+}
+
+macro_rules! impl_into_node {
+    ($($kind:ty => $node:ident),* $(,)?) => {
+        $(impl IntoNode for $kind {
+            type Node = $node;
+
+            fn node(self) -> $node {
+                $node::new(self, Span::default())
+            }
+        })*
+    };
+}
+
+impl_into_node! {
+    StmtKind => Stmt,
+    ExprKind => Expr,
+    PatternKind => Pattern,
+    PropKind => Prop,
+    ClassElementKind => ClassElement,
+    ObjectPatternPropKind => ObjectPatternProp,
+}
+
+/// Parses a module that is expected to have no errors.
+pub(crate) fn parse_module(source: &str) -> Module {
+    let res = Parser::init(source).parse("test");
+    assert!(res.errors.is_empty(), "{}: {:?}", source, res.errors);
+    res.ast
+}
+
+/// Parses a module that is expected to have no errors and a single statement.
+pub(crate) fn parse_single(source: &str) -> Stmt {
+    let mut module = parse_module(source);
+    assert_eq!(module.body.len(), 1, "{}", source);
+    module.body.remove(0)
+}
+
+/// Parses a module that is expected to have no errors and a single expression statement.
+pub(crate) fn parse_single_expr(source: &str) -> Expr {
+    match parse_single(source).kind {
+        StmtKind::Expression(ExprStmt(expr)) => expr,
+        stmt => panic!("Expected expression statement, found {:?}", stmt),
+    }
+}
+
+/// The number of errors parsing a module reports.
+pub(crate) fn error_count(source: &str) -> usize {
+    Parser::init(source).parse("test").errors.len()
+}
+
+pub(crate) fn id(name: &str) -> Expr {
+    ExprKind::Primary(PrimaryExprKind::Id(name.into())).node()
+}
+
+pub(crate) fn num(val: i64) -> Expr {
+    ExprKind::Primary(PrimaryExprKind::Lit(LitValue::Num(val as f64))).node()
+}
+
+pub(crate) fn pat(name: &str) -> Pattern {
+    PatternKind::Id(name.into()).node()
+}
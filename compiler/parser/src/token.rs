@@ -1,41 +1,92 @@
+use std::fmt;
 use serde::Serialize;
+use crate::span::Span;
+use crate::symbol::{sym, Symbol};
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct Token {
     pub kind: TokenKind,
-    pub pos: usize,
+    pub span: Span,
+    /// Whether a line terminator appears between this token and the previous one, as used for automatic semicolon
+    /// insertion and the `[no LineTerminator here]` restrictions of the grammar.
+    pub preceded_by_newline: bool,
+    /// Whether an identifier contains Unicode escape sequences, so it is not a keyword even if its name spells one.
+    pub escaped: bool,
+    /// The span of a legacy octal literal such as `017`, or of the first legacy octal escape sequence in a string
+    /// literal such as `"\01"`, which are errors in strict mode code. Whether code is strict is only known to the parser.
+    pub legacy_octal: Option<Span>,
 }
 
 impl Token {
-    pub fn new(kind: TokenKind, pos: usize) -> Self {
-        Self { kind, pos }
+    pub fn new(kind: TokenKind, span: Span) -> Self {
+        Self { kind, span, preceded_by_newline: false, escaped: false, legacy_octal: None }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum TokenKind {
     Comment(CommentKind),
     /// End of file
     Eof,
     /// Alphabetic tokens that are not string literals, boolean literals, or keywords.
-    Id(String),
+    Id(Symbol),
     /// Language keywords
     Keyword(KeywordKind),
+    /// Private class member names, such as `#field`
+    PrivateId(Symbol),
     LineTerminator(LineTerminatorKind),
     /// Value literals
     Lit(LitKind),
+    /// Template literals, which are split into a token for each part between substitutions.
+    Template(TemplateKind),
     Punc(PuncKind),
     WhiteSpace(WhiteSpaceKind),
     /// Any unknown characters that we are unable to identify
-    Unicode(String),
+    Unicode(char),
+}
+
+impl fmt::Display for TokenKind {
+    /// Describes the token for diagnostics, such as "`;`" or "identifier `a`".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Comment(_) => write!(f, "comment"),
+            TokenKind::Eof => write!(f, "end of input"),
+            TokenKind::Id(name) => write!(f, "identifier `{}`", name),
+            TokenKind::Keyword(keyword) => write!(f, "keyword `{}`", keyword.as_str()),
+            TokenKind::PrivateId(name) => write!(f, "private name `#{}`", name),
+            TokenKind::LineTerminator(_) => write!(f, "line terminator"),
+            TokenKind::Lit(LitKind::String) => write!(f, "string literal"),
+            TokenKind::Lit(LitKind::RegEx) => write!(f, "regular expression literal"),
+            TokenKind::Lit(LitKind::Bool(BooleanKind::True)) => write!(f, "`true`"),
+            TokenKind::Lit(LitKind::Bool(BooleanKind::False)) => write!(f, "`false`"),
+            TokenKind::Lit(LitKind::Null) => write!(f, "`null`"),
+            TokenKind::Lit(_) => write!(f, "numeric literal"),
+            TokenKind::Template(TemplateKind::NoSubstitution | TemplateKind::Head) => write!(f, "template literal"),
+            TokenKind::Template(TemplateKind::Middle | TemplateKind::Tail) => write!(f, "`}}`"),
+            TokenKind::Punc(punc) => write!(f, "`{}`", punc.as_str()),
+            TokenKind::WhiteSpace(_) => write!(f, "white space"),
+            TokenKind::Unicode(text) => write!(f, "`{}`", text),
+        }
+    }
 }
 
+/// Section 12.2 [White Space](https://tc39.es/ecma262/#sec-white-space)
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum WhiteSpaceKind {
-    ///
+    /// U+0020
     Space,
     /// \t
     HorizontalTabulation,
+    /// U+000B
+    VerticalTabulation,
+    /// U+000C
+    FormFeed,
+    /// U+00A0
+    NoBreakSpace,
+    /// U+FEFF, also known as the byte order mark
+    ZeroWidthNoBreakSpace,
+    /// Any other char in the Unicode "Space_Separator" (Zs) category, such as U+3000
+    SpaceSeparator(char),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
@@ -44,6 +95,10 @@ pub enum LineTerminatorKind {
     LineFeed,
     /// \r
     CarriageReturn,
+    /// U+2028
+    LineSeparator,
+    /// U+2029
+    ParagraphSeparator,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
@@ -54,36 +109,93 @@ pub enum BooleanKind {
     False,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Section 12.4 [Comments](https://tc39.es/ecma262/#sec-comments)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum CommentKind {
-    SingleLine(String),
-    MultiLine(String),
+    SingleLine,
+    MultiLine,
+    /// Section 12.5 [Hashbang Comments](https://tc39.es/ecma262/#sec-hashbang), which may only start the source.
+    Hashbang,
+    /// Section B.1.1 [HTML-like Comments](https://tc39.es/ecma262/#sec-html-like-comments), opened by `<!--`.
+    HtmlOpen,
+    /// Section B.1.1 [HTML-like Comments](https://tc39.es/ecma262/#sec-html-like-comments), opened by `-->` at the start
+    /// of a line.
+    HtmlClose,
+}
+
+impl CommentKind {
+    /// The lengths of the delimiters opening and closing the comment.
+    fn delimiters(&self) -> (usize, usize) {
+        match self {
+            CommentKind::SingleLine | CommentKind::Hashbang => (2, 0),
+            CommentKind::MultiLine => (2, 2),
+            CommentKind::HtmlOpen => (4, 0),
+            CommentKind::HtmlClose => (3, 0),
+        }
+    }
 }
 
+/// A comment, with the text following the opening delimiter, and before the closing `*/` of a multi-line comment.
 #[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub text: String,
+}
+
+impl Comment {
+    /// The comment of the provided kind, given its source text including the delimiters.
+    pub fn new(kind: CommentKind, source: &str) -> Self {
+        let (open, close) = kind.delimiters();
+        Comment { kind, text: source[open..source.len() - close].to_string() }
+    }
+}
+
+/// The kind of a literal token. Other than numbers and booleans, the values of literals are read from their source
+/// text as the parser consumes them, so tokens stay cheap to copy and no value is built for a token only peeked at.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum LitKind {
-    BigIntSuffix(String),
+    /// Section 12.9.3 [BigInt literals](https://tc39.es/ecma262/#sec-literals-numeric-literals) such as `123n` or
+    /// `0x1Fn`
+    BigInt,
     Bool(BooleanKind),
-    Dec(String),
-    DecimalBigInteger(String),
-    DecimalInteger(String),
-    NonDecimalInteger(NonDecimalIntegerLiteralKind),
     Null,
-    Num(i64),
-    String(String),
-    RegEx(String),
+    /// Section 12.9.3 [Numeric Literals](https://tc39.es/ecma262/#sec-literals-numeric-literals) such as `1.5`, `1e-9` or
+    /// `0x1F`, as their Number value.
+    Num(f64),
+    /// Section 12.9.4 [String Literals](https://tc39.es/ecma262/#sec-literals-string-literals)
+    String,
+    /// Section 12.9.5 [Regular Expression Literals](https://tc39.es/ecma262/#sec-literals-regular-expression-literals)
+    RegEx,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
-pub enum NonDecimalIntegerLiteralKind {
-    BigInteger(String),
-    OctalInteger(String),
-    HexInteger(String),
+/// Section 12.9.6 [Template Literal Lexical Components](https://tc39.es/ecma262/#sec-template-literal-lexical-components)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum TemplateKind {
+    /// `` `a` ``, a template without substitutions
+    NoSubstitution,
+    /// `` `a${ ``, up to the first substitution
+    Head,
+    /// `}a${`, between two substitutions
+    Middle,
+    /// `` }a` ``, after the last substitution
+    Tail,
 }
 
+impl TemplateKind {
+    /// The lengths of the delimiters around the characters of the template token, such as `}` and `${`.
+    pub fn delimiters(&self) -> (usize, usize) {
+        match self {
+            TemplateKind::NoSubstitution | TemplateKind::Tail => (1, 1),
+            TemplateKind::Head | TemplateKind::Middle => (1, 2),
+        }
+    }
+}
+
+/// Section 12.7.2 [Keywords and Reserved Words](https://tc39.es/ecma262/#sec-keywords-and-reserved-words) that are
+/// always reserved. Words reserved only in some contexts, such as `let`, `yield` and `await`, are lexed as identifiers
+/// and recognized by the parser.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum KeywordKind {
-    Await,
     Break,
     Case,
     Catch,
@@ -95,6 +207,7 @@ pub enum KeywordKind {
     Delete,
     Do,
     Else,
+    Enum,
     Export,
     Extends,
     Finally,
@@ -104,10 +217,8 @@ pub enum KeywordKind {
     Import,
     In,
     InstanceOf,
-    Let,
     New,
     Return,
-    Static,
     Super,
     Switch,
     This,
@@ -118,12 +229,106 @@ pub enum KeywordKind {
     Void,
     While,
     With,
-    Yield,
+}
+
+impl KeywordKind {
+    /// The source text of the keyword.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeywordKind::Break => "break",
+            KeywordKind::Case => "case",
+            KeywordKind::Catch => "catch",
+            KeywordKind::Class => "class",
+            KeywordKind::Const => "const",
+            KeywordKind::Continue => "continue",
+            KeywordKind::Debugger => "debugger",
+            KeywordKind::Default => "default",
+            KeywordKind::Delete => "delete",
+            KeywordKind::Do => "do",
+            KeywordKind::Else => "else",
+            KeywordKind::Enum => "enum",
+            KeywordKind::Export => "export",
+            KeywordKind::Extends => "extends",
+            KeywordKind::Finally => "finally",
+            KeywordKind::For => "for",
+            KeywordKind::Function => "function",
+            KeywordKind::If => "if",
+            KeywordKind::Import => "import",
+            KeywordKind::In => "in",
+            KeywordKind::InstanceOf => "instanceof",
+            KeywordKind::New => "new",
+            KeywordKind::Return => "return",
+            KeywordKind::Super => "super",
+            KeywordKind::Switch => "switch",
+            KeywordKind::This => "this",
+            KeywordKind::Throw => "throw",
+            KeywordKind::Try => "try",
+            KeywordKind::TypeOf => "typeof",
+            KeywordKind::Var => "var",
+            KeywordKind::Void => "void",
+            KeywordKind::While => "while",
+            KeywordKind::With => "with",
+        }
+    }
+
+    /// The keyword as an identifier name, such as the property name in `a.default`.
+    pub fn symbol(&self) -> Symbol {
+        match self {
+            KeywordKind::Break => sym::BREAK,
+            KeywordKind::Case => sym::CASE,
+            KeywordKind::Catch => sym::CATCH,
+            KeywordKind::Class => sym::CLASS,
+            KeywordKind::Const => sym::CONST,
+            KeywordKind::Continue => sym::CONTINUE,
+            KeywordKind::Debugger => sym::DEBUGGER,
+            KeywordKind::Default => sym::DEFAULT,
+            KeywordKind::Delete => sym::DELETE,
+            KeywordKind::Do => sym::DO,
+            KeywordKind::Else => sym::ELSE,
+            KeywordKind::Enum => sym::ENUM,
+            KeywordKind::Export => sym::EXPORT,
+            KeywordKind::Extends => sym::EXTENDS,
+            KeywordKind::Finally => sym::FINALLY,
+            KeywordKind::For => sym::FOR,
+            KeywordKind::Function => sym::FUNCTION,
+            KeywordKind::If => sym::IF,
+            KeywordKind::Import => sym::IMPORT,
+            KeywordKind::In => sym::IN,
+            KeywordKind::InstanceOf => sym::INSTANCEOF,
+            KeywordKind::New => sym::NEW,
+            KeywordKind::Return => sym::RETURN,
+            KeywordKind::Super => sym::SUPER,
+            KeywordKind::Switch => sym::SWITCH,
+            KeywordKind::This => sym::THIS,
+            KeywordKind::Throw => sym::THROW,
+            KeywordKind::Try => sym::TRY,
+            KeywordKind::TypeOf => sym::TYPEOF,
+            KeywordKind::Var => sym::VAR,
+            KeywordKind::Void => sym::VOID,
+            KeywordKind::While => sym::WHILE,
+            KeywordKind::With => sym::WITH,
+        }
+    }
+}
+
+/// Section 12.7.2 [ReservedWord](https://tc39.es/ecma262/#prod-ReservedWord), other than the contextually reserved
+/// `await` and `yield`.
+pub fn is_reserved_word(word: Symbol) -> bool {
+    map_keyword(word.as_str()).is_some() || matches!(word, sym::TRUE | sym::FALSE | sym::NULL)
+}
+
+/// Section 13.1.1 [Static Semantics: Early Errors](https://tc39.es/ecma262/#sec-identifiers-static-semantics-early-errors),
+/// the words that can not be identifiers in strict mode code.
+pub fn is_strict_reserved_word(word: Symbol) -> bool {
+    matches!(
+        word,
+        sym::IMPLEMENTS | sym::INTERFACE | sym::LET | sym::PACKAGE | sym::PRIVATE | sym::PROTECTED | sym::PUBLIC
+            | sym::STATIC | sym::YIELD
+    )
 }
 
 pub fn map_keyword(keyword_str: &str) -> Option<KeywordKind> {
     match keyword_str {
-        "await" => Some(KeywordKind::Await),
         "break" => Some(KeywordKind::Break),
         "case" => Some(KeywordKind::Case),
         "catch" => Some(KeywordKind::Catch),
@@ -135,6 +340,7 @@ pub fn map_keyword(keyword_str: &str) -> Option<KeywordKind> {
         "delete" => Some(KeywordKind::Delete),
         "do" => Some(KeywordKind::Do),
         "else" => Some(KeywordKind::Else),
+        "enum" => Some(KeywordKind::Enum),
         "export" => Some(KeywordKind::Export),
         "extends" => Some(KeywordKind::Extends),
         "finally" => Some(KeywordKind::Finally),
@@ -144,10 +350,8 @@ pub fn map_keyword(keyword_str: &str) -> Option<KeywordKind> {
         "import" => Some(KeywordKind::Import),
         "in" => Some(KeywordKind::In),
         "instanceof" => Some(KeywordKind::InstanceOf),
-        "let" => Some(KeywordKind::Let),
         "new" => Some(KeywordKind::New),
         "return" => Some(KeywordKind::Return),
-        "static" => Some(KeywordKind::Static),
         "super" => Some(KeywordKind::Super),
         "switch" => Some(KeywordKind::Switch),
         "this" => Some(KeywordKind::This),
@@ -158,7 +362,6 @@ pub fn map_keyword(keyword_str: &str) -> Option<KeywordKind> {
         "void" => Some(KeywordKind::Void),
         "while" => Some(KeywordKind::While),
         "with" => Some(KeywordKind::With),
-        "yield" => Some(KeywordKind::Yield),
         _ => None,
     }
 }
@@ -178,6 +381,26 @@ pub enum PuncKind {
     SemiColon,
 }
 
+impl PuncKind {
+    /// The source text of the punctuator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PuncKind::Brace(BraceKind::Left) => "{",
+            PuncKind::Brace(BraceKind::Right) => "}",
+            PuncKind::Bracket(BracketKind::Left) => "[",
+            PuncKind::Bracket(BracketKind::Right) => "]",
+            PuncKind::Colon => ":",
+            PuncKind::Comma => ",",
+            PuncKind::Dot => ".",
+            PuncKind::Op(op) => op.as_str(),
+            PuncKind::Question => "?",
+            PuncKind::Parentheses(ParenthesesKind::Left) => "(",
+            PuncKind::Parentheses(ParenthesesKind::Right) => ")",
+            PuncKind::SemiColon => ";",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum ParenthesesKind {
     /// (
@@ -206,7 +429,7 @@ pub enum BraceKind {
 pub enum OpKind {
     /// +
     Addition,
-    /// +
+    /// ++
     Increment,
     /// +=
     AdditonAssign,
@@ -258,6 +481,8 @@ pub enum OpKind {
     Not,
     /// !=
     NotEqual,
+    /// !==
+    StrictInequality,
     /// >=
     GreaterThanEqual,
     /// <=
@@ -282,6 +507,8 @@ pub enum OpKind {
     BitXor,
     /// ^=
     BitXorAssign,
+    /// ~
+    BitNot,
     /// ?.
     OptionalChain,
     /// ??
@@ -290,6 +517,62 @@ pub enum OpKind {
     NullishCoalescingAssign,
     /// ...
     Spread,
+    /// =>
+    Arrow,
+}
+
+impl OpKind {
+    /// The source text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpKind::Addition => "+",
+            OpKind::Increment => "++",
+            OpKind::AdditonAssign => "+=",
+            OpKind::Assign => "=",
+            OpKind::Subtraction => "-",
+            OpKind::Decrement => "--",
+            OpKind::SubtractionAssign => "-=",
+            OpKind::Multiplication => "*",
+            OpKind::MultiplicationAssign => "*=",
+            OpKind::Division => "/",
+            OpKind::DivisionAssign => "/=",
+            OpKind::Exponential => "**",
+            OpKind::ExponentialAssign => "**=",
+            OpKind::Mod => "%",
+            OpKind::ModAssign => "%=",
+            OpKind::LeftShift => "<<",
+            OpKind::LeftShiftAssign => "<<=",
+            OpKind::RightShift => ">>",
+            OpKind::RightShiftAssign => ">>=",
+            OpKind::ZeroFillRightShift => ">>>",
+            OpKind::UnsignedRightShiftAssign => ">>>=",
+            OpKind::GreaterThan => ">",
+            OpKind::LessThan => "<",
+            OpKind::Equal => "==",
+            OpKind::StrictEquality => "===",
+            OpKind::Not => "!",
+            OpKind::NotEqual => "!=",
+            OpKind::StrictInequality => "!==",
+            OpKind::GreaterThanEqual => ">=",
+            OpKind::LessThanEqual => "<=",
+            OpKind::BitAnd => "&",
+            OpKind::BitAndAssign => "&=",
+            OpKind::And => "&&",
+            OpKind::AndAssign => "&&=",
+            OpKind::BitOr => "|",
+            OpKind::BitOrAssign => "|=",
+            OpKind::Or => "||",
+            OpKind::OrAssign => "||=",
+            OpKind::BitXor => "^",
+            OpKind::BitXorAssign => "^=",
+            OpKind::BitNot => "~",
+            OpKind::OptionalChain => "?.",
+            OpKind::NullishCoalescing => "??",
+            OpKind::NullishCoalescingAssign => "??=",
+            OpKind::Spread => "...",
+            OpKind::Arrow => "=>",
+        }
+    }
 }
 
 pub fn is_removable(token: &TokenKind) -> bool {
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, VecDeque};
+use crate::lexer::{Lexer, LexerCheckpoint, LexerError};
+use crate::span::Span;
+use crate::ast::{LitValue, RegExLit, StringLit, TemplateElement};
+use crate::token::{Comment, LitKind, OpKind, PuncKind, TemplateKind, Token, TokenKind};
+
+/// The most tokens the parser looks ahead by, as in `async x =>`, where the third token decides the production.
+pub const MAX_LOOKAHEAD: usize = 3;
+
+/// Pulls tokens from the lexer as the parser needs them, buffering the tokens peeked at but not yet consumed, so the
+/// source is never tokenized all at once. At most [MAX_LOOKAHEAD] tokens are buffered.
+#[derive(Debug)]
+pub struct TokenStream<'a> {
+    lexer: Lexer<'a>,
+    lookahead: VecDeque<Lookahead>,
+    /// The number of tokens consumed.
+    pos: usize,
+}
+
+#[derive(Debug)]
+struct Lookahead {
+    token: Result<Token, LexerError>,
+    /// The lexer state right after a `/` or `/=` token, from where it may be lexed again as a regular expression
+    /// literal.
+    checkpoint: Option<LexerCheckpoint>,
+}
+
+impl<'a> TokenStream<'a> {
+    pub fn new(lexer: Lexer<'a>) -> Self {
+        TokenStream { lexer, lookahead: VecDeque::with_capacity(MAX_LOOKAHEAD), pos: 0 }
+    }
+
+    /// Peeks the token `n` positions past the next one, lexing up to it if needed. A token the lexer failed on is
+    /// seen as the end of input, until it is taken with [TokenStream::take_error].
+    pub fn peek_nth(&mut self, n: usize) -> Token {
+        assert!(n < MAX_LOOKAHEAD, "peeked {} tokens ahead, past the lookahead of {}", n, MAX_LOOKAHEAD);
+        while self.lookahead.len() <= n {
+            let token = self.lexer.next_token();
+            let slash = matches!(
+                &token,
+                Ok(Token { kind: TokenKind::Punc(PuncKind::Op(OpKind::Division | OpKind::DivisionAssign)), .. })
+            );
+            let checkpoint = slash.then(|| self.lexer.checkpoint());
+            self.lookahead.push_back(Lookahead { token, checkpoint });
+        }
+
+        match &self.lookahead[n].token {
+            Ok(token) => *token,
+            Err(err) => Token::new(TokenKind::Eof, Span::new(err.span.start, err.span.start)),
+        }
+    }
+
+    /// Consumes the next token.
+    pub fn bump(&mut self) {
+        self.peek_nth(0);
+        self.lookahead.pop_front();
+        self.pos += 1;
+    }
+
+    /// The number of tokens consumed, used to check whether the parser has made progress.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Takes the error the lexer failed with on the next token, if it did. The lexer continues after the source text
+    /// it failed on, so the token after it becomes the next token.
+    pub fn take_error(&mut self) -> Option<LexerError> {
+        self.peek_nth(0);
+        let err = self.lookahead.front()?.token.as_ref().err()?.clone();
+        self.lookahead.pop_front();
+        Some(err)
+    }
+
+    /// Lexes the next token again as a regular expression literal if it is a `/` or `/=` token, discarding the tokens
+    /// lexed after it. If the literal is malformed, the lexer continues after the source text it failed on.
+    pub fn rescan_regex(&mut self) -> Result<Token, LexerError> {
+        let slash = self.peek_nth(0);
+        let Some(checkpoint) = self.lookahead[0].checkpoint.take() else {
+            return Ok(slash);
+        };
+
+        self.lookahead.clear();
+        let regex = self.lexer.rescan_regex(&slash, checkpoint)?;
+        self.lookahead.push_back(Lookahead { token: Ok(regex), checkpoint: None });
+        Ok(regex)
+    }
+
+    /// The value of a literal token of the provided kind and span.
+    pub fn lit_value(&self, kind: LitKind, span: Span) -> LitValue {
+        self.lexer.lit_value(kind, span)
+    }
+
+    /// The value of a string literal token with the provided span.
+    pub fn string_lit(&self, span: Span) -> StringLit {
+        self.lexer.string_lit(span)
+    }
+
+    /// The pattern and flags of a regular expression literal token with the provided span.
+    pub fn regex_lit(&self, span: Span) -> RegExLit {
+        self.lexer.regex_lit(span)
+    }
+
+    /// The characters of a template token of the provided kind and span.
+    pub fn template_element(&self, kind: TemplateKind, span: Span) -> TemplateElement {
+        self.lexer.template_element(kind, span)
+    }
+
+    /// Takes the comments lexed so far, keyed by span.
+    pub fn take_comments(&self) -> BTreeMap<Span, Comment> {
+        self.lexer.take_comments()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{Lexer, LexerErrorKind};
+    use crate::span::Span;
+    use crate::ast::RegExLit;
+    use crate::token::{LitKind, OpKind, PuncKind, TokenKind};
+    use super::{TokenStream, MAX_LOOKAHEAD};
+
+    #[test]
+    fn test_peek_and_bump() {
+        let mut tokens = TokenStream::new(Lexer::init("a + 'b"));
+        assert_eq!(tokens.peek_nth(1).kind, TokenKind::Punc(PuncKind::Op(OpKind::Addition)));
+        assert_eq!(tokens.peek_nth(0).kind, TokenKind::Id("a".into()));
+        assert!(tokens.take_error().is_none());
+
+        tokens.bump();
+        tokens.bump();
+        assert_eq!(tokens.pos(), 2);
+        // The unterminated string is seen as the end of input, until its error is taken.
+        assert_eq!(tokens.peek_nth(0).kind, TokenKind::Eof);
+        assert_eq!(tokens.peek_nth(0).span, Span::new(4, 4));
+        assert_eq!(tokens.take_error().unwrap().error, LexerErrorKind::UnterminatedString);
+        assert_eq!(tokens.peek_nth(0).kind, TokenKind::Eof);
+        assert_eq!(tokens.peek_nth(0).span, Span::new(6, 6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lookahead_limit() {
+        TokenStream::new(Lexer::init("a b c d")).peek_nth(MAX_LOOKAHEAD);
+    }
+
+    #[test]
+    fn test_rescan_regex() {
+        // The lexer error past the `/` is discarded along with the other tokens peeked at.
+        let mut tokens = TokenStream::new(Lexer::init("/'/ + b"));
+        assert_eq!(tokens.peek_nth(1).kind, TokenKind::Eof);
+        let regex = tokens.rescan_regex().unwrap();
+        assert_eq!(regex.kind, TokenKind::Lit(LitKind::RegEx));
+        assert_eq!(tokens.regex_lit(regex.span), RegExLit { pattern: "'".into(), flags: "".into() });
+        assert_eq!(tokens.peek_nth(0), regex);
+        assert_eq!(tokens.peek_nth(1).kind, TokenKind::Punc(PuncKind::Op(OpKind::Addition)));
+        assert!(tokens.take_error().is_none());
+
+        // Any other token is left as it is.
+        let mut tokens = TokenStream::new(Lexer::init("a /"));
+        assert_eq!(tokens.rescan_regex().unwrap().kind, TokenKind::Id("a".into()));
+
+        // After a malformed literal, lexing continues past it.
+        let mut tokens = TokenStream::new(Lexer::init("/a/gg c"));
+        assert_eq!(tokens.rescan_regex().unwrap_err().error, LexerErrorKind::InvalidRegExFlags);
+        assert_eq!(tokens.peek_nth(0).kind, TokenKind::Id("c".into()));
+    }
+}